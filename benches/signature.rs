@@ -0,0 +1,56 @@
+//! Single-verify latency of the signature check, and of a whole standard
+//! bundle verification, for catching performance regressions.
+//!
+//! Run with `cargo bench --bench signature`; pass an iteration count to
+//! override the default, e.g. `cargo bench --bench signature -- 5000`.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::time::Instant;
+
+use proofcapture_cli::crypto::{decode_base64, parse_public_key, parse_signature, verify_signature};
+use proofcapture_cli::manifest::compute_canonical_hash_from_bytes;
+use proofcapture_cli::verify::verify_standard_bundle;
+use proofcapture_cli::SignedAudioManifest;
+
+const DEFAULT_ITERATIONS: u32 = 1000;
+
+fn main() {
+    // `cargo bench` passes `--bench`; the first number given is the iteration count
+    let iterations = std::env::args().skip(1).find_map(|arg| arg.parse().ok()).unwrap_or(DEFAULT_ITERATIONS);
+    let bundle = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("full_bundle");
+
+    let manifest_bytes = std::fs::read(bundle.join("manifest.json")).unwrap();
+    let manifest = SignedAudioManifest::from_json(&manifest_bytes).unwrap();
+    let public_key = parse_public_key(&decode_base64(&manifest.public_key).unwrap()).unwrap();
+    let signature = parse_signature(&decode_base64(manifest.signature.as_deref().unwrap()).unwrap()).unwrap();
+    let hash = compute_canonical_hash_from_bytes(&manifest_bytes).unwrap();
+
+    report("ECDSA P-256 verify", iterations, || {
+        assert!(verify_signature(black_box(&public_key), black_box(&hash), black_box(&signature)));
+    });
+    report("canonical hash", iterations, || {
+        black_box(compute_canonical_hash_from_bytes(black_box(&manifest_bytes)).unwrap());
+    });
+    report("standard bundle", iterations, || {
+        black_box(verify_standard_bundle(black_box(&bundle)).unwrap());
+    });
+}
+
+fn report(name: &str, iterations: u32, mut run: impl FnMut()) {
+    // Warm caches and the allocator before timing
+    for _ in 0..iterations.min(10) {
+        run();
+    }
+    let started = Instant::now();
+    for _ in 0..iterations {
+        run();
+    }
+    let per_run = started.elapsed() / iterations;
+    println!(
+        "{:<20} {:>10.1} us/op {:>10.0} ops/s",
+        name,
+        per_run.as_secs_f64() * 1e6,
+        1.0 / per_run.as_secs_f64()
+    );
+}
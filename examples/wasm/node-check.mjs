@@ -0,0 +1,24 @@
+// Checks the wasm build against the sealed fixture under Node:
+//
+//   wasm-bindgen --target nodejs --out-dir examples/wasm/node-pkg \
+//       target/wasm32-unknown-unknown/release/proofcapture_cli.wasm
+//   node examples/wasm/node-check.mjs
+import assert from "node:assert/strict";
+import { readFileSync } from "node:fs";
+import { createRequire } from "node:module";
+
+const { verify_sealed } = createRequire(import.meta.url)("./node-pkg/proofcapture_cli.js");
+const bundle = readFileSync(new URL("../../fixtures/sealed_test.proofcapture", import.meta.url));
+
+const started = performance.now();
+const report = verify_sealed(bundle, "test-password-123");
+console.log(`verified in ${Math.round(performance.now() - started)} ms`);
+assert.equal(report.status, "verified");
+assert.ok(report.sealing);
+
+assert.throws(() => verify_sealed(bundle, "wrong-password"), (failed) => {
+  assert.equal(failed.status, "failed");
+  assert.equal(failed.exitCode, 7);
+  return true;
+});
+console.log("ok");
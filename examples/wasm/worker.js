@@ -0,0 +1,14 @@
+// Verifies sealed bundles off the main thread, so the page stays responsive
+// during key derivation. Build pkg/ as described in src/wasm.rs.
+import init, { verify_sealed } from "./pkg/proofcapture_cli.js";
+
+const ready = init();
+
+self.onmessage = async ({ data: { bundle, password } }) => {
+  await ready;
+  try {
+    self.postMessage({ ok: true, report: verify_sealed(new Uint8Array(bundle), password) });
+  } catch (report) {
+    self.postMessage({ ok: false, report });
+  }
+};
@@ -0,0 +1,119 @@
+//! Append-only audit log of verifications.
+//!
+//! Each verification, successful or not, appends one JSON line to the log.
+//! The file is opened in append mode and held under an exclusive lock while
+//! the line is written, so several processes can share one log safely.
+//! Records never contain passwords or media bytes.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+
+use crate::batch::{content_hash, BatchRecord};
+use crate::clock::Clock;
+use crate::error::{Result, VerifyError};
+use crate::options::VerifyOptions;
+use crate::verify::VerificationResult;
+
+/// One line of the audit log.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// RFC 3339 UTC time the record was made.
+    pub timestamp: String,
+    pub path: String,
+    /// Login name of the user who ran the verification, if known.
+    pub operator: Option<String>,
+    /// Content hash, status, trust level and error, as in a batch record.
+    #[serde(flatten)]
+    pub outcome: BatchRecord,
+}
+
+impl AuditRecord {
+    /// Record the outcome of verifying `path`, hashing its current content.
+    ///
+    /// The content hash is empty if `path` can't be read (e.g. a URL).
+    pub fn new(
+        path: &Path,
+        outcome: std::result::Result<&VerificationResult, &VerifyError>,
+        clock: &dyn Clock,
+    ) -> Self {
+        let hash = content_hash(path, &VerifyOptions::default()).unwrap_or_default();
+        Self::from_record(path, BatchRecord::new(hash, outcome), clock)
+    }
+
+    /// Record an outcome already captured as a batch record, timestamped
+    /// with `clock`'s current time.
+    pub fn from_record(path: &Path, outcome: BatchRecord, clock: &dyn Clock) -> Self {
+        AuditRecord {
+            timestamp: clock.now().format(&Rfc3339).unwrap_or_default(),
+            path: path.display().to_string(),
+            operator: std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok(),
+            outcome,
+        }
+    }
+}
+
+/// A JSONL audit log file.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Use the log at `path`; it's created on the first append.
+    pub fn new(path: &Path) -> Self {
+        AuditLog { path: path.to_path_buf() }
+    }
+
+    /// Append `record` as one line, under an exclusive lock.
+    pub fn append(&self, record: &AuditRecord) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.lock()?;
+        // A single write keeps the line whole; the lock is released on drop
+        file.write_all(&line)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::verify::verify_standard_bundle;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name)
+    }
+
+    #[test]
+    fn test_append_writes_one_line_per_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = AuditLog::new(&dir.path().join("audit.jsonl"));
+        let clock = FixedClock::new(time::OffsetDateTime::from_unix_timestamp(1_769_947_200).unwrap());
+
+        let verified = fixture("minimal_bundle");
+        log.append(&AuditRecord::new(&verified, verify_standard_bundle(&verified).as_ref(), &clock)).unwrap();
+        let missing = fixture("nonexistent_bundle");
+        log.append(&AuditRecord::new(&missing, verify_standard_bundle(&missing).as_ref(), &clock)).unwrap();
+
+        let contents = std::fs::read_to_string(dir.path().join("audit.jsonl")).unwrap();
+        let lines: Vec<serde_json::Value> = contents.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0]["status"], "verified");
+        assert_eq!(lines[0]["path"], verified.display().to_string());
+        assert_eq!(lines[0]["contentHash"].as_str().unwrap().len(), 64);
+        assert_eq!(lines[0]["timestamp"], "2026-02-01T12:00:00Z");
+
+        assert_eq!(lines[1]["status"], "failed");
+        assert_eq!(lines[1]["exitCode"], VerifyError::AudioFileMissing.exit_code());
+        assert_eq!(lines[1]["contentHash"], "");
+    }
+}
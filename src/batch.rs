@@ -0,0 +1,841 @@
+//! Batch verification of many bundles.
+//!
+//! A batch is every bundle directly inside a directory: `.proofcapture` and
+//! `.proofbundle` files, and subdirectories holding standard bundles.
+//!
+//! Long runs can be made resumable with a [`BatchState`] file. It records the
+//! final outcome for each bundle, keyed by absolute path and a hash of the
+//! bundle's content, and is saved after every bundle. A re-run skips bundles
+//! whose content is unchanged and re-verifies any that were modified, or
+//! whose last failure (a wrong password, an IO error) may not recur. Bundles
+//! recorded under other policy options, or verified but now older than the
+//! maximum age, are verified again too.
+//!
+//! With a [`BatchExtraction`], each sealed bundle's media is also written to
+//! its own subdirectory, named after the bundle's file stem.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{hex_encode, sha256_bytes};
+use crate::error::{exit_code_name, Result, VerifyError};
+use crate::options::VerifyOptions;
+use crate::sealed::NonceTracker;
+use crate::trust::{check_max_age, TrustVector};
+use crate::verify::{
+    detect_bundle_type, is_tar_archive_name, prepare_extract_dir, read_file, verify_and_extract_sealed_bundle_with_options,
+    verify_bundle, write_extracted_file, BundleType, VerificationResult,
+};
+use crate::warnings::Warning;
+
+/// Current state file format version.
+pub const CURRENT_STATE_VERSION: i32 = 1;
+
+/// Outcome of verifying one bundle, as stored in the state file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRecord {
+    /// Hex SHA-256 over the bundle's content when it was verified.
+    pub content_hash: String,
+    /// `"verified"` or `"failed"`.
+    pub status: String,
+    pub trust_level: Option<String>,
+    pub manifest_hash: Option<String>,
+    pub error: Option<String>,
+    pub exit_code: i32,
+    /// Codes of any warnings raised by a successful verification.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Names of the trust vectors present in a verified manifest.
+    #[serde(default)]
+    pub vectors: Vec<String>,
+    /// Format version of a verified sealed bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_version: Option<i32>,
+    /// `captureEnd` of a verified manifest, to check its age again when resuming.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_end: Option<String>,
+    /// Fingerprint of the policy options the outcome was reached under, set
+    /// once it's recorded in a state file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+}
+
+impl BatchRecord {
+    /// Capture the outcome of one verification.
+    pub fn new(content_hash: String, outcome: std::result::Result<&VerificationResult, &VerifyError>) -> Self {
+        match outcome {
+            Ok(result) => BatchRecord {
+                content_hash,
+                status: "verified".to_string(),
+                trust_level: Some(result.trust_level.display_name().to_string()),
+                manifest_hash: Some(hex_encode(&result.manifest_hash)),
+                error: None,
+                exit_code: 0,
+                warnings: result.warnings.iter().map(|w| w.code().to_string()).collect(),
+                vectors: TrustVector::present(&result.manifest.trust_vectors)
+                    .iter()
+                    .map(|v| v.name().to_string())
+                    .collect(),
+                bundle_version: result.sealing.as_ref().map(|s| s.bundle_version),
+                capture_end: Some(result.manifest.capture_end.clone()),
+                policy: None,
+            },
+            Err(e) => BatchRecord {
+                content_hash,
+                status: "failed".to_string(),
+                trust_level: None,
+                manifest_hash: None,
+                error: Some(e.to_string()),
+                exit_code: e.exit_code(),
+                warnings: Vec::new(),
+                vectors: Vec::new(),
+                bundle_version: None,
+                capture_end: None,
+                policy: None,
+            },
+        }
+    }
+
+    /// Returns true if the bundle verified.
+    pub fn is_verified(&self) -> bool {
+        self.exit_code == 0
+    }
+
+    /// Returns true if verifying the unchanged bundle again would fail the
+    /// same way, because its content failed a check. Failures such as a
+    /// wrong or missing password, IO and network errors, or a policy check
+    /// aren't final.
+    pub fn is_final(&self) -> bool {
+        self.is_verified()
+            || matches!(
+                exit_code_name(self.exit_code),
+                "HashMismatch"
+                    | "SignatureInvalid"
+                    | "ManifestMalformed"
+                    | "SchemaUnsupported"
+                    | "AudioFileMissing"
+                    | "AudioFileCorrupt"
+                    | "BundleCorrupted"
+                    | "UnsupportedBundleVersion"
+                    | "ManifestMissing"
+                    | "AttachmentHashMismatch"
+                    | "EmptyFile"
+                    | "PayloadDigestMismatch"
+            )
+    }
+}
+
+/// One bundle's entry in a batch run.
+#[derive(Debug, Clone)]
+pub struct BatchEntry {
+    pub path: PathBuf,
+    pub record: BatchRecord,
+    /// True if the record was taken from the state file rather than re-verified.
+    pub skipped: bool,
+}
+
+/// Where a batch writes the media of the sealed bundles it verifies.
+#[derive(Debug, Clone)]
+pub struct BatchExtraction {
+    /// Parent of the per-bundle subdirectories.
+    pub output_dir: PathBuf,
+    /// Replace files left by an earlier extraction instead of failing the bundle.
+    pub overwrite: bool,
+}
+
+impl BatchExtraction {
+    /// The subdirectory for `bundle`: `output_dir/<file stem>`.
+    pub fn bundle_dir(&self, bundle: &Path) -> PathBuf {
+        self.output_dir.join(bundle.file_stem().unwrap_or(bundle.as_os_str()))
+    }
+}
+
+/// Totals over a batch: verified bundles by trust level and failures by error.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCounts {
+    pub verified: usize,
+    /// Verified bundles per trust level letter; every level is listed.
+    pub trust_levels: BTreeMap<String, usize>,
+    pub failed: usize,
+    /// Failed bundles per error kind, e.g. `HashMismatch`.
+    pub errors: BTreeMap<String, usize>,
+}
+
+impl BatchCounts {
+    /// Tally the outcomes of `entries`, including ones skipped from a state file.
+    pub fn from_entries(entries: &[BatchEntry]) -> Self {
+        let mut counts = BatchCounts {
+            verified: 0,
+            trust_levels: ["A", "B", "C"].iter().map(|level| (level.to_string(), 0)).collect(),
+            failed: 0,
+            errors: BTreeMap::new(),
+        };
+        for record in entries.iter().map(|e| &e.record) {
+            match &record.trust_level {
+                Some(level) if record.is_verified() => {
+                    counts.verified += 1;
+                    let letter = level.strip_prefix("Level ").unwrap_or(level);
+                    *counts.trust_levels.entry(letter.to_string()).or_default() += 1;
+                }
+                _ => {
+                    counts.failed += 1;
+                    *counts.errors.entry(exit_code_name(record.exit_code).to_string()).or_default() += 1;
+                }
+            }
+        }
+        counts
+    }
+}
+
+impl std::fmt::Display for BatchCounts {
+    /// e.g. `3 verified: A=1 B=0 C=2; 1 failed: HashMismatch=1`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tally = |map: &BTreeMap<String, usize>| {
+            map.iter().map(|(name, n)| format!(" {}={}", name, n)).collect::<String>()
+        };
+        write!(f, "{} verified:{}; {} failed:{}", self.verified, tally(&self.trust_levels), self.failed, tally(&self.errors))
+    }
+}
+
+/// Persistent record of completed bundles, for resuming interrupted runs.
+#[derive(Debug, Clone)]
+pub struct BatchState {
+    path: PathBuf,
+    entries: BTreeMap<String, BatchRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StateFile {
+    state_version: i32,
+    entries: BTreeMap<String, BatchRecord>,
+}
+
+impl BatchState {
+    /// Load the state file at `path`, starting empty if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        let entries = match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice::<StateFile>(&bytes)?.entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(VerifyError::Io(e)),
+        };
+        Ok(BatchState { path: path.to_path_buf(), entries })
+    }
+
+    /// The recorded final outcome for `bundle`, if its content hasn't changed
+    /// since and it was recorded under the same policy as `options`.
+    ///
+    /// With a maximum age in `options`, a recording that has grown too old
+    /// since it verified has no outcome, so that it's verified (and fails)
+    /// again.
+    pub fn get(&self, bundle: &Path, content_hash: &str, options: &VerifyOptions) -> Option<&BatchRecord> {
+        let policy = options.policy_fingerprint();
+        self.entries.get(&state_key(bundle)).filter(|record| {
+            record.content_hash == content_hash
+                && record.is_final()
+                && record.policy.as_ref() == Some(&policy)
+                && within_max_age(record, options)
+        })
+    }
+
+    /// Record the outcome for `bundle`, reached under `options`, and save the
+    /// state file.
+    ///
+    /// An outcome that isn't [final](BatchRecord::is_final) replaces nothing
+    /// and isn't saved.
+    pub fn record(&mut self, bundle: &Path, mut record: BatchRecord, options: &VerifyOptions) -> Result<()> {
+        if !record.is_final() {
+            return Ok(());
+        }
+        record.policy = Some(options.policy_fingerprint());
+        self.entries.insert(state_key(bundle), record);
+        self.save()
+    }
+
+    /// Number of recorded bundles.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no bundles are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn save(&self) -> Result<()> {
+        let file = StateFile {
+            state_version: CURRENT_STATE_VERSION,
+            entries: self.entries.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&file)?;
+
+        // Write then rename, so a crash mid-write never leaves a truncated file
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// List the bundles directly inside `dir`, sorted by path.
+pub fn discover_bundles(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut bundles = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let is_bundle_file = matches!(ext, "proofcapture" | "proofbundle") || is_tar_archive_name(&path);
+        if path.is_dir() || (path.is_file() && is_bundle_file) {
+            bundles.push(path);
+        }
+    }
+    bundles.sort();
+    Ok(bundles)
+}
+
+/// Returns true if any bundle in the batch is sealed and needs a password.
+pub fn needs_password(bundles: &[PathBuf]) -> bool {
+    bundles.iter().any(|path| is_sealed(path))
+}
+
+/// Verify each bundle, calling `on_entry` for each in turn.
+///
+/// Up to `concurrency` bundles are verified at once, on that many threads.
+/// Whatever order they finish in, `on_entry` sees them and the result lists
+/// them in the order of `bundles`. An error from `on_entry` stops the batch
+/// and is returned.
+///
+/// With a `state`, bundles already recorded with unchanged content under the
+/// same policy are skipped and reported from the state file; final outcomes
+/// of the rest are recorded as soon as they finish.
+///
+/// Sealed bundles are also checked for nonces reused across the batch,
+/// unless `options` already carries a nonce tracker. With an `extraction`,
+/// their media is written out once they verify; a failed write fails that
+/// bundle. Bundles skipped from the state file aren't extracted again.
+///
+/// The hash registry and nonce checks run after authentication, one bundle
+/// at a time in the order of `bundles`, so of two bundles sharing an audio
+/// hash or nonce the later one is warned about however many threads run.
+/// Media to extract is held in memory until its bundle reaches that stage.
+pub fn verify_batch(
+    bundles: &[PathBuf],
+    password: Option<&str>,
+    options: &VerifyOptions,
+    mut state: Option<&mut BatchState>,
+    extraction: Option<&BatchExtraction>,
+    concurrency: usize,
+    mut on_entry: impl FnMut(&BatchEntry) -> Result<()>,
+) -> Result<Vec<BatchEntry>> {
+    let mut entries = Vec::with_capacity(bundles.len());
+    let batch_tracker = NonceTracker::new();
+    let tracker = options.nonce_tracker().unwrap_or(&batch_tracker);
+
+    // Workers look up skips in a snapshot; only this thread records outcomes
+    let previous = state.as_deref().cloned();
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, bundles.len().max(1)) {
+            let sender = sender.clone();
+            let (next, stop, previous) = (&next, &stop, previous.as_ref());
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = bundles.get(index) else {
+                        break;
+                    };
+                    let entry = verify_entry(path, password, options, previous, extraction);
+                    if sender.send((index, entry)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Hold entries that finish early until those before them are done
+        let mut finished = BTreeMap::new();
+        let result: Result<()> = receiver.iter().try_for_each(|(index, entry)| {
+            finished.insert(index, entry);
+            while let Some(pending) = finished.remove(&entries.len()) {
+                let entry = settle(pending, options, tracker, extraction);
+                if let (Some(state), false) = (state.as_deref_mut(), entry.skipped) {
+                    state.record(&entry.path, entry.record.clone(), options)?;
+                }
+                on_entry(&entry)?;
+                entries.push(entry);
+            }
+            Ok(())
+        });
+        if result.is_err() {
+            stop.store(true, Ordering::Relaxed);
+        }
+        result
+    })?;
+
+    Ok(entries)
+}
+
+/// A sealed bundle's media files, by file name.
+type Media = Vec<(String, Vec<u8>)>;
+
+/// A bundle a worker is done with, waiting for the batch's stateful checks.
+#[allow(clippy::large_enum_variant)]
+enum Pending {
+    /// Outcome taken from the state file.
+    Skipped(BatchEntry),
+    Verified(Unsettled),
+}
+
+struct Unsettled {
+    path: PathBuf,
+    content_hash: String,
+    outcome: Result<VerificationResult>,
+    /// Salt and nonce of each sealed payload authenticated.
+    nonces: Vec<(String, Vec<u8>)>,
+    /// Media of a sealed bundle still to be extracted.
+    media: Option<Media>,
+}
+
+/// Authenticate one bundle of a batch, or take its outcome from `previous`.
+///
+/// The content hash is only computed with a `previous` state to look it up
+/// in, and is empty otherwise. A bundle that can't be hashed fails alone.
+///
+/// The hash registry in `options` isn't used here, and the nonces that would
+/// be tracked are collected instead, for [`settle`].
+fn verify_entry(
+    path: &Path,
+    password: Option<&str>,
+    options: &VerifyOptions,
+    previous: Option<&BatchState>,
+    extraction: Option<&BatchExtraction>,
+) -> Pending {
+    let content_hash = match previous.map(|_| content_hash(path, options)) {
+        Some(Ok(hash)) => hash,
+        Some(Err(e)) => {
+            let outcome = Err(e);
+            return Pending::Verified(Unsettled { path: path.to_path_buf(), content_hash: String::new(), outcome, nonces: Vec::new(), media: None });
+        }
+        None => String::new(),
+    };
+    if let Some(record) = previous.and_then(|s| s.get(path, &content_hash, options)) {
+        return Pending::Skipped(BatchEntry { path: path.to_path_buf(), record: record.clone(), skipped: true });
+    }
+
+    let nonces = Arc::new(NonceTracker::new());
+    let options = options.clone().without_hash_registry().with_nonce_tracker(nonces.clone());
+    let (outcome, media) = match extraction {
+        Some(_) if is_sealed(path) => match verify_sealed_media(path, password, &options) {
+            Ok((result, media)) => (Ok(result), Some(media)),
+            Err(e) => (Err(e), None),
+        },
+        _ => (verify_bundle(path, password, &options), None),
+    };
+    Pending::Verified(Unsettled { path: path.to_path_buf(), content_hash, outcome, nonces: nonces.drain(), media })
+}
+
+/// Run the hash registry and nonce checks on a worker's outcome, then
+/// extract its media.
+///
+/// Called for each bundle in turn, in batch order.
+fn settle(pending: Pending, options: &VerifyOptions, tracker: &NonceTracker, extraction: Option<&BatchExtraction>) -> BatchEntry {
+    let Unsettled { path, content_hash, outcome, nonces, media } = match pending {
+        Pending::Skipped(entry) => return entry,
+        Pending::Verified(unsettled) => unsettled,
+    };
+
+    let outcome = outcome.and_then(|mut result| {
+        if let Some(registry) = options.hash_registry() {
+            if registry.register(&result.manifest.audio_hash)? {
+                if options.rejects_duplicates() {
+                    return Err(VerifyError::DuplicateRecording);
+                }
+                result.warnings.push(Warning::DuplicateRecording);
+            }
+        }
+        // Record every nonce, even after one is found reused
+        if nonces.iter().filter(|(salt, nonce)| tracker.record(salt, nonce)).count() > 0 {
+            result.warnings.push(Warning::NonceReused);
+        }
+        if let (Some(extraction), Some(media)) = (extraction, media) {
+            write_media(&extraction.bundle_dir(&path), &media, extraction.overwrite)?;
+        }
+        Ok(result)
+    });
+
+    let record = BatchRecord::new(content_hash, outcome.as_ref());
+    BatchEntry { path, record, skipped: false }
+}
+
+/// Verify a sealed bundle, returning its media files alongside the result.
+fn verify_sealed_media(
+    path: &Path,
+    password: Option<&str>,
+    options: &VerifyOptions,
+) -> Result<(VerificationResult, Media)> {
+    let password = password.ok_or(VerifyError::DecryptionFailed)?;
+    let mut result = verify_and_extract_sealed_bundle_with_options(path, password, options)?;
+
+    let mut media = vec![(result.audio_filename.clone(), std::mem::take(&mut result.audio_data))];
+    media.extend(result.attachments.drain(..).map(|a| (a.filename, a.data)));
+    Ok((result.into(), media))
+}
+
+/// Write a bundle's media files into `dir`.
+fn write_media(dir: &Path, media: &Media, overwrite: bool) -> Result<()> {
+    if !overwrite {
+        if let Some(existing) = media.iter().map(|(name, _)| dir.join(name)).find(|path| path.exists()) {
+            return Err(VerifyError::OverwriteRefused { path: existing.display().to_string() });
+        }
+    }
+    prepare_extract_dir(dir)?;
+    for (name, data) in media {
+        write_extracted_file(dir, name, data)?;
+    }
+    Ok(())
+}
+
+fn is_sealed(path: &Path) -> bool {
+    detect_bundle_type(path) == BundleType::Sealed
+}
+
+/// Hex SHA-256 over a bundle file, or over the names and contents of the
+/// files in a bundle directory, read with the retries `options` allow.
+pub fn content_hash(path: &Path, options: &VerifyOptions) -> Result<String> {
+    if !path.is_dir() {
+        return Ok(hex_encode(&sha256_bytes(&read_file(path, options)?)));
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    files.retain(|f| f.is_file());
+    files.sort();
+
+    let mut digest_input = Vec::new();
+    for file in files {
+        let name = file.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        digest_input.extend_from_slice(name.as_bytes());
+        digest_input.push(0);
+        digest_input.extend_from_slice(&sha256_bytes(&read_file(&file, options)?));
+    }
+    Ok(hex_encode(&sha256_bytes(&digest_input)))
+}
+
+/// Returns true unless `record` is of a verification that `options` would now
+/// fail as too old. Failures don't depend on age.
+fn within_max_age(record: &BatchRecord, options: &VerifyOptions) -> bool {
+    match (options.max_age(), &record.capture_end) {
+        (None, _) => true,
+        (Some(max_age), Some(capture_end)) => check_max_age(capture_end, max_age, options.clock().now()).is_ok(),
+        (Some(_), None) => !record.is_verified(),
+    }
+}
+
+/// State file key: the absolute path of the bundle.
+fn state_key(bundle: &Path) -> String {
+    fs::canonicalize(bundle)
+        .unwrap_or_else(|_| bundle.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::SignedAudioManifest;
+    use crate::registry::HashRegistry;
+
+    fn fixtures_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+    }
+
+    fn copy_bundle(name: &str, into: &Path) -> PathBuf {
+        let dest = into.join(name);
+        fs::create_dir_all(&dest).unwrap();
+        for entry in fs::read_dir(fixtures_dir().join(name)).unwrap() {
+            let path = entry.unwrap().path();
+            fs::copy(&path, dest.join(path.file_name().unwrap())).unwrap();
+        }
+        dest
+    }
+
+    fn run(dir: &Path, state: &mut BatchState) -> Vec<BatchEntry> {
+        let bundles = discover_bundles(dir).unwrap();
+        verify_batch(&bundles, None, &VerifyOptions::default(), Some(state), None, 1, |_| Ok(())).unwrap()
+    }
+
+    #[test]
+    fn test_extraction_into_per_bundle_directories() {
+        let batch = tempfile::tempdir().unwrap();
+        for name in ["sealed_test.proofcapture", "sealed_attachment_test.proofcapture"] {
+            fs::copy(fixtures_dir().join(name), batch.path().join(name)).unwrap();
+        }
+        copy_bundle("minimal_bundle", batch.path());
+        let out = tempfile::tempdir().unwrap();
+        let extraction = BatchExtraction { output_dir: out.path().join("extracted"), overwrite: false };
+
+        let bundles = discover_bundles(batch.path()).unwrap();
+        let run = |extraction| verify_batch(&bundles, Some("test-password-123"), &VerifyOptions::default(), None, Some(extraction), 1, |_| Ok(())).unwrap();
+        let entries = run(&extraction);
+        assert!(entries.iter().all(|e| e.record.is_verified()), "{entries:?}");
+        let versions: Vec<_> = entries.iter().map(|e| e.record.bundle_version).collect();
+        assert_eq!(versions, [None, Some(1), Some(1)]);
+
+        let mut dirs: Vec<_> = fs::read_dir(&extraction.output_dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        dirs.sort();
+        assert_eq!(dirs, ["sealed_attachment_test", "sealed_test"]);
+        assert!(extraction.bundle_dir(&bundles[2]).join("recording.m4a").is_file());
+
+        // A second run would overwrite, so fails those bundles unless allowed
+        let entries = run(&extraction);
+        let codes: Vec<_> = entries.iter().map(|e| e.record.exit_code).collect();
+        assert_eq!(codes, [0, 20, 20]);
+        let entries = run(&BatchExtraction { overwrite: true, ..extraction.clone() });
+        assert!(entries.iter().all(|e| e.record.is_verified()));
+    }
+
+    #[test]
+    fn test_discover_bundles() {
+        let bundles = discover_bundles(&fixtures_dir()).unwrap();
+        let names: Vec<_> = bundles.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+
+        // Directories, sealed bundles and tar archives; loose files aren't bundles
+        for name in ["minimal_bundle", "sealed_test.proofcapture", "full_bundle.tar.gz"] {
+            assert!(names.contains(&name), "{name} in {names:?}");
+        }
+        for name in ["embedded_manifest.m4a", "sealed_test_password.txt", "test_vectors.json"] {
+            assert!(!names.contains(&name), "{name} in {names:?}");
+        }
+        assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(needs_password(&bundles));
+    }
+
+    #[test]
+    fn test_counts_over_mixed_batch() {
+        let batch = tempfile::tempdir().unwrap();
+        copy_bundle("minimal_bundle", batch.path());
+        copy_bundle("full_bundle", batch.path());
+        let tampered = copy_bundle("message_signed_bundle", batch.path());
+        fs::write(tampered.join("recording.m4a"), b"not the recording").unwrap();
+        fs::write(batch.path().join("broken.proofbundle"), b"not a zip").unwrap();
+
+        let bundles = discover_bundles(batch.path()).unwrap();
+        let entries = verify_batch(&bundles, None, &VerifyOptions::default(), None, None, 1, |_| Ok(())).unwrap();
+        let counts = BatchCounts::from_entries(&entries);
+
+        assert_eq!(counts.verified, 2);
+        assert_eq!(counts.failed, 2);
+        assert_eq!(counts.to_string(), "2 verified: A=1 B=0 C=1; 2 failed: HashMismatch=1 ManifestMalformed=1");
+    }
+
+    #[test]
+    fn test_concurrent_batch_keeps_bundle_order() {
+        let batch = tempfile::tempdir().unwrap();
+        for name in ["android_key_bundle", "cosigned_bundle", "fast_motion_bundle", "full_bundle", "message_signed_bundle", "minimal_bundle", "skewed_bundle"] {
+            copy_bundle(name, batch.path());
+        }
+        let tampered = copy_bundle("sparse_motion_bundle", batch.path());
+        fs::write(tampered.join("recording.m4a"), b"not the recording").unwrap();
+        fs::write(batch.path().join("broken.proofbundle"), b"not a zip").unwrap();
+        let bundles = discover_bundles(batch.path()).unwrap();
+
+        let run = |concurrency, state: Option<&mut BatchState>| {
+            let mut seen = Vec::new();
+            let entries = verify_batch(&bundles, None, &VerifyOptions::default(), state, None, concurrency, |entry| {
+                seen.push(entry.path.clone());
+                Ok(())
+            })
+            .unwrap();
+            assert_eq!(seen, bundles);
+            // Only the run with a state file hashes the bundles
+            let without_hash = |record| BatchRecord { content_hash: String::new(), ..record };
+            entries.into_iter().map(|e| (e.path, without_hash(e.record))).collect::<Vec<_>>()
+        };
+
+        let state_dir = tempfile::tempdir().unwrap();
+        let mut state = BatchState::load(&state_dir.path().join("state.json")).unwrap();
+        let sequential = run(1, None);
+        assert_eq!(run(4, Some(&mut state)), sequential);
+        assert_eq!(run(16, None), sequential);
+        assert_eq!(state.len(), bundles.len());
+    }
+
+    #[test]
+    fn test_concurrent_batch_warns_about_later_duplicates() {
+        let batch = tempfile::tempdir().unwrap();
+        for name in ["a", "b", "c"] {
+            fs::copy(fixtures_dir().join("sealed_test.proofcapture"), batch.path().join(format!("{name}.proofcapture"))).unwrap();
+        }
+        let bundles = discover_bundles(batch.path()).unwrap();
+
+        for run in 0..2 {
+            let registry = HashRegistry::new(&batch.path().join(format!("seen-{run}.txt")));
+            let options = VerifyOptions::new().with_hash_registry(registry);
+            let entries = verify_batch(&bundles, Some("test-password-123"), &options, None, None, 3, |_| Ok(())).unwrap();
+            let warnings: Vec<_> = entries.iter().map(|e| e.record.warnings.clone()).collect();
+            assert_eq!(warnings, [vec![], vec!["duplicate_recording", "nonce_reused"], vec!["duplicate_recording", "nonce_reused"]]);
+        }
+    }
+
+    #[test]
+    fn test_resumed_run_skips_completed_bundles() {
+        let batch = tempfile::tempdir().unwrap();
+        let state_dir = tempfile::tempdir().unwrap();
+        let state_path = state_dir.path().join("state.json");
+
+        // First run is "interrupted" after the one bundle that existed then
+        copy_bundle("minimal_bundle", batch.path());
+        let mut state = BatchState::load(&state_path).unwrap();
+        let first = run(batch.path(), &mut state);
+        assert_eq!(first.len(), 1);
+        assert!(!first[0].skipped && first[0].record.is_verified());
+
+        copy_bundle("full_bundle", batch.path());
+        let mut state = BatchState::load(&state_path).unwrap();
+        assert_eq!(state.len(), 1);
+        let second = run(batch.path(), &mut state);
+
+        let skipped: Vec<_> = second.iter().map(|e| (e.path.file_name().unwrap().to_owned(), e.skipped)).collect();
+        assert_eq!(skipped, [("full_bundle".into(), false), ("minimal_bundle".into(), true)]);
+        assert!(second.iter().all(|e| e.record.is_verified()));
+        assert_eq!(BatchState::load(&state_path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_changed_bundle_is_reverified() {
+        let batch = tempfile::tempdir().unwrap();
+        let state_dir = tempfile::tempdir().unwrap();
+        let state_path = state_dir.path().join("state.json");
+
+        let bundle = copy_bundle("minimal_bundle", batch.path());
+        run(batch.path(), &mut BatchState::load(&state_path).unwrap());
+
+        fs::write(bundle.join("recording.m4a"), b"tampered").unwrap();
+        let entries = run(batch.path(), &mut BatchState::load(&state_path).unwrap());
+
+        assert!(!entries[0].skipped);
+        assert_eq!(entries[0].record.exit_code, VerifyError::HashMismatch.exit_code());
+
+        // The failure is recorded too, so a further run skips it
+        let entries = run(batch.path(), &mut BatchState::load(&state_path).unwrap());
+        assert!(entries[0].skipped && !entries[0].record.is_verified());
+    }
+
+    #[test]
+    fn test_unreadable_bundle_fails_alone() {
+        let batch = tempfile::tempdir().unwrap();
+        copy_bundle("minimal_bundle", batch.path());
+        copy_bundle("full_bundle", batch.path());
+        let mut bundles = discover_bundles(batch.path()).unwrap();
+        // Removed between discovery and verification
+        bundles.insert(1, batch.path().join("gone.proofcapture"));
+
+        let mut state = BatchState::load(&batch.path().join("state.json")).unwrap();
+        let entries = verify_batch(&bundles, None, &VerifyOptions::default(), Some(&mut state), None, 1, |_| Ok(())).unwrap();
+        let verified: Vec<_> = entries.iter().map(|e| e.record.is_verified()).collect();
+        assert_eq!(verified, [true, false, true]);
+        assert_eq!(exit_code_name(entries[1].record.exit_code), "Io");
+        assert_eq!(state.len(), 2);
+    }
+
+    #[test]
+    fn test_bundles_are_only_hashed_for_a_state_file() {
+        let batch = tempfile::tempdir().unwrap();
+        copy_bundle("minimal_bundle", batch.path());
+        let bundles = discover_bundles(batch.path()).unwrap();
+
+        let entries = verify_batch(&bundles, None, &VerifyOptions::default(), None, None, 1, |_| Ok(())).unwrap();
+        assert_eq!(entries[0].record.content_hash, "");
+
+        let mut state = BatchState::load(&batch.path().join("state.json")).unwrap();
+        let entries = verify_batch(&bundles, None, &VerifyOptions::default(), Some(&mut state), None, 1, |_| Ok(())).unwrap();
+        assert_eq!(entries[0].record.content_hash, content_hash(&bundles[0], &VerifyOptions::default()).unwrap());
+    }
+
+    #[test]
+    fn test_policy_change_reverifies_recorded_bundles() {
+        let batch = tempfile::tempdir().unwrap();
+        let state_path = batch.path().join("state.json");
+        let bundles = [copy_bundle("minimal_bundle", batch.path())];
+        let run = |options: &VerifyOptions| {
+            let mut state = BatchState::load(&state_path).unwrap();
+            verify_batch(&bundles, None, options, Some(&mut state), None, 1, |_| Ok(())).unwrap().remove(0)
+        };
+
+        assert!(run(&VerifyOptions::default()).record.is_verified());
+        assert!(run(&VerifyOptions::default()).skipped);
+
+        // minimal_bundle has no trust vectors, so fails a stricter policy
+        let entry = run(&VerifyOptions::new().with_full_context_required(true));
+        assert!(!entry.skipped && !entry.record.is_verified());
+
+        // A seen-hashes registry gets the skipped bundle's hash
+        let registry = HashRegistry::new(&batch.path().join("seen.txt"));
+        let entry = run(&VerifyOptions::new().with_hash_registry(registry.clone()));
+        assert!(!entry.skipped && entry.record.is_verified());
+        let manifest = SignedAudioManifest::from_json(&fs::read(bundles[0].join("manifest.json")).unwrap()).unwrap();
+        assert!(registry.register(&manifest.audio_hash).unwrap());
+        assert!(run(&VerifyOptions::new().with_hash_registry(registry)).skipped);
+    }
+
+    #[test]
+    fn test_recording_too_old_since_recorded_is_reverified() {
+        let batch = tempfile::tempdir().unwrap();
+        let state_path = batch.path().join("state.json");
+        let bundles = [copy_bundle("minimal_bundle", batch.path())];
+        // minimal_bundle ended at 2026-01-27T02:57:17Z
+        let run = |now: &str| {
+            let now = time::OffsetDateTime::parse(now, &time::format_description::well_known::Rfc3339).unwrap();
+            let clock = Arc::new(crate::clock::FixedClock::new(now));
+            let options = VerifyOptions::new().with_max_age(time::Duration::days(30)).with_clock(clock);
+            let mut state = BatchState::load(&state_path).unwrap();
+            verify_batch(&bundles, None, &options, Some(&mut state), None, 1, |_| Ok(())).unwrap().remove(0)
+        };
+
+        assert!(run("2026-02-01T00:00:00Z").record.is_verified());
+        assert!(run("2026-02-20T00:00:00Z").skipped);
+        let entry = run("2026-03-01T00:00:00Z");
+        assert!(!entry.skipped);
+        assert_eq!(exit_code_name(entry.record.exit_code), "RecordingTooOld");
+    }
+
+    #[test]
+    fn test_sealed_bundle_without_password_fails() {
+        let bundles = [fixtures_dir().join("sealed_test.proofcapture")];
+        let entries = verify_batch(&bundles, None, &VerifyOptions::default(), None, None, 1, |_| Ok(())).unwrap();
+        assert_eq!(entries[0].record.exit_code, VerifyError::DecryptionFailed.exit_code());
+    }
+
+    #[test]
+    fn test_password_failures_are_not_resumed() {
+        let batch = tempfile::tempdir().unwrap();
+        let state_path = batch.path().join("state.json");
+        let sealed = batch.path().join("sealed_test.proofcapture");
+        fs::copy(fixtures_dir().join("sealed_test.proofcapture"), &sealed).unwrap();
+        let bundles = [sealed];
+        let run = |password| {
+            let mut state = BatchState::load(&state_path).unwrap();
+            verify_batch(&bundles, password, &VerifyOptions::default(), Some(&mut state), None, 1, |_| Ok(())).unwrap()
+        };
+
+        let entries = run(Some("wrong-password"));
+        assert!(!entries[0].record.is_final());
+        assert!(BatchState::load(&state_path).unwrap().is_empty());
+
+        let entries = run(Some("test-password-123"));
+        assert!(!entries[0].skipped && entries[0].record.is_verified());
+        assert!(run(None)[0].skipped);
+    }
+}
@@ -0,0 +1,252 @@
+//! Cache of authenticated bundles by bundle content.
+//!
+//! Services that receive the same bundle again can opt in (via
+//! `VerifyOptions::with_result_cache`) to skip key derivation, decryption,
+//! hashing and signature checks for the repeat. Entries are keyed by the
+//! SHA-256 of the raw bundle bytes, so the cache trusts byte-identity: a
+//! bundle that differs in any byte misses and is verified from scratch.
+//!
+//! Only what authentication establishes is cached, and only on success.
+//! Everything that depends on the options or on state runs again on a hit:
+//! the schema and manifest size limits, required vectors, `max_age` against
+//! the clock, the hash registry, the nonce tracker and custom checks. A
+//! repeat therefore raises `DuplicateRecording` and `NonceReused` as a
+//! fresh verification would, and one cache can serve several option sets.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::crypto::sha256_bytes;
+use crate::error::Result;
+use crate::verify::AuthenticatedBundle;
+
+/// Bounded in-memory cache of authenticated bundles.
+///
+/// Sealed bundles are looked up by the password as well, so the same bytes
+/// with a different password are verified again. As with the KDF cache,
+/// only the password's SHA-256 is kept, as part of the lookup key.
+pub struct ResultCache {
+    capacity: usize,
+    entries: Mutex<ResultCacheEntries>,
+}
+
+#[derive(Default)]
+struct ResultCacheEntries {
+    results: HashMap<ResultCacheKey, AuthenticatedBundle>,
+    // Least recently used first
+    order: VecDeque<ResultCacheKey>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ResultCacheKey {
+    content_hash: [u8; 32],
+    password_hash: Option<[u8; 32]>,
+}
+
+impl ResultCache {
+    /// Creates a cache holding at most `capacity` results.
+    ///
+    /// When full, the least recently used result is evicted.
+    pub fn new(capacity: usize) -> Self {
+        ResultCache {
+            capacity,
+            entries: Mutex::new(ResultCacheEntries::default()),
+        }
+    }
+
+    /// Maximum number of results held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of results currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().results.len()
+    }
+
+    /// Returns true if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.results.clear();
+        entries.order.clear();
+    }
+
+    /// Returns the cached authentication of `bundle_bytes` (and `password`,
+    /// for a sealed bundle), running `authenticate` and caching its success
+    /// on a miss.
+    pub(crate) fn get_or_authenticate(
+        &self,
+        bundle_bytes: &[u8],
+        password: Option<&str>,
+        authenticate: impl FnOnce() -> Result<AuthenticatedBundle>,
+    ) -> Result<AuthenticatedBundle> {
+        let cache_key = ResultCacheKey {
+            content_hash: sha256_bytes(bundle_bytes),
+            password_hash: password.map(|p| sha256_bytes(p.as_bytes())),
+        };
+
+        {
+            let mut entries = self.lock();
+            if let Some(result) = entries.results.get(&cache_key).cloned() {
+                entries.touch(&cache_key);
+                return Ok(result);
+            }
+        }
+
+        // Authenticate outside the lock so other threads aren't blocked on the KDF
+        let result = authenticate()?;
+
+        if self.capacity > 0 {
+            let mut entries = self.lock();
+            if entries.results.insert(cache_key.clone(), result.clone()).is_none() {
+                entries.order.push_back(cache_key);
+            }
+            while entries.results.len() > self.capacity {
+                match entries.order.pop_front() {
+                    Some(oldest) => {
+                        entries.results.remove(&oldest);
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ResultCacheEntries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ResultCacheEntries {
+    /// Marks a result as most recently used.
+    fn touch(&mut self, cache_key: &ResultCacheKey) {
+        if let Some(pos) = self.order.iter().position(|k| k == cache_key) {
+            if let Some(k) = self.order.remove(pos) {
+                self.order.push_back(k);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ResultCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::HashRegistry;
+    use crate::verify::verify_sealed_bytes;
+    use crate::clock::FixedClock;
+    use crate::sealed::NonceTracker;
+    use crate::{VerifyError, VerifyOptions, Warning};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use time::{Duration, OffsetDateTime};
+
+    const PASSWORD: &str = "test-password-123";
+
+    fn sealed_fixture() -> Vec<u8> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/sealed_test.proofcapture");
+        std::fs::read(path).unwrap()
+    }
+
+    fn assert_hit(cache: &ResultCache, bundle: &[u8]) {
+        cache.get_or_authenticate(bundle, Some(PASSWORD), || panic!("should be a cache hit")).unwrap();
+    }
+
+    #[test]
+    fn test_repeat_hits_and_changed_byte_misses() {
+        let cache = Arc::new(ResultCache::new(4));
+        let options = VerifyOptions::new().with_shared_result_cache(cache.clone());
+        let bundle = sealed_fixture();
+
+        let first = verify_sealed_bytes(&bundle, PASSWORD, &options).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_hit(&cache, &bundle);
+        let repeat = verify_sealed_bytes(&bundle, PASSWORD, &options).unwrap();
+        assert_eq!(repeat.manifest_hash, first.manifest_hash);
+        assert_eq!(cache.len(), 1);
+
+        // Insignificant to the verifier, but a different byte string
+        let mut changed = bundle.clone();
+        changed.push(b'\n');
+        verify_sealed_bytes(&changed, PASSWORD, &options).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // The password is part of the key, and failures aren't cached
+        let wrong = verify_sealed_bytes(&bundle, "wrong-password", &options);
+        assert!(matches!(wrong, Err(VerifyError::DecryptionFailed)));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_hit_still_runs_stateful_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Arc::new(ResultCache::new(4));
+        let options = VerifyOptions::new()
+            .with_shared_result_cache(cache.clone())
+            .with_hash_registry(HashRegistry::new(&dir.path().join("hashes.txt")))
+            .with_nonce_tracker(Arc::new(NonceTracker::new()));
+        let bundle = sealed_fixture();
+
+        let first = verify_sealed_bytes(&bundle, PASSWORD, &options).unwrap();
+        assert!(!first.warnings.contains(&Warning::DuplicateRecording));
+        assert_hit(&cache, &bundle);
+
+        let repeat = verify_sealed_bytes(&bundle, PASSWORD, &options).unwrap();
+        assert!(repeat.warnings.contains(&Warning::DuplicateRecording));
+        assert!(repeat.warnings.contains(&Warning::NonceReused));
+    }
+
+    #[test]
+    fn test_hit_is_judged_under_the_callers_options() {
+        let cache = Arc::new(ResultCache::new(4));
+        let lenient = VerifyOptions::new().with_shared_result_cache(cache.clone());
+        let strict = VerifyOptions::new()
+            .with_shared_result_cache(cache.clone())
+            .with_clock(Arc::new(FixedClock::new(OffsetDateTime::now_utc() + Duration::days(36_500))))
+            .with_max_age(Duration::hours(1));
+        let bundle = sealed_fixture();
+
+        verify_sealed_bytes(&bundle, PASSWORD, &lenient).unwrap();
+        assert_hit(&cache, &bundle);
+        let result = verify_sealed_bytes(&bundle, PASSWORD, &strict);
+        assert!(matches!(result, Err(VerifyError::RecordingTooOld { .. })), "{result:?}");
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let cache = Arc::new(ResultCache::new(1));
+        let options = VerifyOptions::new().with_shared_result_cache(cache.clone());
+        let first = sealed_fixture();
+        let mut second = first.clone();
+        second.push(b'\n');
+
+        verify_sealed_bytes(&first, PASSWORD, &options).unwrap();
+        verify_sealed_bytes(&second, PASSWORD, &options).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_hit(&cache, &second);
+        let mut missed = false;
+        cache
+            .get_or_authenticate(&first, Some(PASSWORD), || {
+                missed = true;
+                Err(VerifyError::DecryptionFailed)
+            })
+            .unwrap_err();
+        assert!(missed);
+    }
+}
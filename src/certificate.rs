@@ -0,0 +1,187 @@
+//! Printable HTML verification certificates.
+//!
+//! A certificate presents a [`VerificationReport`] to readers who won't run
+//! the verifier themselves: the outcome, trust level, capture details and
+//! what verification does not prove. The document is self-contained, with
+//! inline styles and no scripts or external assets, so it can be archived,
+//! emailed, or printed to PDF from a browser.
+
+use std::fmt::Write;
+
+use crate::messages::{Catalog, MessageKey};
+use crate::report::VerificationReport;
+
+const STYLE: &str = "\
+body { font-family: Georgia, 'Times New Roman', serif; color: #1a1a1a; max-width: 46em; margin: 2em auto; padding: 0 1em; }
+h1 { font-size: 1.6em; border-bottom: 2px solid #1a1a1a; padding-bottom: 0.3em; }
+h2 { font-size: 1.1em; margin-top: 1.6em; text-transform: uppercase; letter-spacing: 0.05em; }
+table { border-collapse: collapse; width: 100%; }
+th { text-align: left; font-weight: normal; color: #555; width: 12em; vertical-align: top; padding: 0.25em 0; }
+td { padding: 0.25em 0; word-break: break-all; }
+.outcome { font-size: 1.3em; padding: 0.6em 0.8em; border: 2px solid; margin: 1em 0; }
+.verified { border-color: #1e7b34; color: #1e7b34; }
+.failed { border-color: #a11; color: #a11; }
+.warnings li { color: #8a5a00; }
+footer { margin-top: 2em; font-size: 0.85em; color: #555; }
+@media print { body { margin: 0; } }
+";
+
+/// Render the report as a self-contained HTML certificate issued at `issued_at`.
+pub fn render_certificate(report: &VerificationReport, issued_at: &str) -> String {
+    let recording = &report.recording;
+    let vectors = &report.trust_vectors;
+    let verified = report.status != "failed";
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>ProofCapture Verification Certificate</title>\n");
+    let _ = writeln!(html, "<style>\n{}</style>\n</head>\n<body>", STYLE);
+    html.push_str("<h1>ProofCapture Verification Certificate</h1>\n");
+
+    let (class, outcome) = if verified {
+        ("verified", "Verified")
+    } else {
+        ("failed", "Verification failed")
+    };
+    let _ = writeln!(
+        html,
+        "<div class=\"outcome {}\">{}: {} ({})</div>",
+        class,
+        outcome,
+        escape_html(&report.trust_level),
+        escape_html(&report.trust_level_label)
+    );
+    if let Some(error) = &report.error {
+        let _ = writeln!(html, "<p>{}</p>", escape_html(error));
+    }
+
+    section(
+        &mut html,
+        "Recording",
+        &[
+            ("Capture start", recording.capture_start.clone()),
+            ("Capture end", recording.capture_end.clone()),
+            ("Duration", format!("{:.1} seconds", recording.duration_seconds)),
+            ("Format", recording.audio_format.to_uppercase()),
+            ("Size", format!("{} bytes", recording.audio_size_bytes)),
+            ("Audio SHA-256", recording.audio_hash.clone()),
+        ],
+    );
+
+    section(
+        &mut html,
+        "Device and App",
+        &[
+            ("Device key ID", report.identity.device_key_id.clone()),
+            ("App", format!("{} v{}", report.identity.app_bundle_id, report.identity.app_version)),
+            ("Manifest SHA-256", report.manifest_hash.clone()),
+        ],
+    );
+
+    let location = vectors.location.as_ref().map_or("Not captured".to_string(), |l| {
+        format!("{}, {} (within {:.0} m)", l.start.lat, l.start.lon, l.start.accuracy)
+    });
+    let motion = vectors.motion.as_ref().map_or("Not captured".to_string(), |m| format!("{} samples", m.sample_count));
+    let continuity = vectors.continuity.as_ref().map_or("Not tracked".to_string(), |c| {
+        if c.uninterrupted {
+            "Uninterrupted".to_string()
+        } else {
+            format!("{} interruption(s)", c.interruption_events.len())
+        }
+    });
+    let time_zone = vectors.clock.as_ref().map_or("Not captured".to_string(), |c| c.time_zone.clone());
+    section(
+        &mut html,
+        "Capture Context",
+        &[("Location", location), ("Motion", motion), ("Continuity", continuity), ("Time zone", time_zone)],
+    );
+
+    if !report.warnings.is_empty() {
+        html.push_str("<h2>Warnings</h2>\n<ul class=\"warnings\">\n");
+        for warning in &report.warnings {
+            let _ = writeln!(html, "<li>{}</li>", escape_html(&warning.message));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if !report.limitations.is_empty() {
+        html.push_str("<h2>Limitations</h2>\n");
+        let _ = writeln!(
+            html,
+            "<p>{}</p>\n<ul>",
+            escape_html(Catalog::english().get(MessageKey::LimitationsIntro))
+        );
+        for limitation in &report.limitations {
+            let _ = writeln!(html, "<li>{}</li>", escape_html(limitation));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    let _ = writeln!(
+        html,
+        "<footer>Issued {} by proofcapture-cli {}.</footer>\n</body>\n</html>",
+        escape_html(issued_at),
+        env!("CARGO_PKG_VERSION")
+    );
+    html
+}
+
+/// Append a titled table of label/value rows.
+fn section(html: &mut String, title: &str, rows: &[(&str, String)]) {
+    let _ = writeln!(html, "<h2>{}</h2>\n<table>", title);
+    for (label, value) in rows {
+        let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", label, escape_html(value));
+    }
+    html.push_str("</table>\n");
+}
+
+/// Escape text for HTML element content and attribute values.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::verify_standard_bundle;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_certificate_contains_key_fields() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("full_bundle");
+        let result = verify_standard_bundle(&path).unwrap();
+        let html = render_certificate(&VerificationReport::from_result(&result), "2026-02-01T12:00:00Z");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Verified: Level A"));
+        for field in [&result.manifest.capture_start, &result.manifest.audio_hash, &result.manifest.device_key_id] {
+            assert!(html.contains(field.as_str()), "{field}");
+        }
+        assert!(html.contains("Who is speaking"));
+        assert!(html.contains("Issued 2026-02-01T12:00:00Z"));
+        // Self-contained: nothing is fetched when it's opened
+        assert!(!html.contains("src=") && !html.contains("href="));
+    }
+
+    #[test]
+    fn test_certificate_escapes_manifest_text() {
+        let mut report = VerificationReport::from_error(&crate::VerifyError::SignatureInvalid, None);
+        report.identity.app_bundle_id = "<script>alert(1)</script>".to_string();
+        let html = render_certificate(&report, "now");
+
+        assert!(html.contains("Verification failed"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+}
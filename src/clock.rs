@@ -0,0 +1,111 @@
+//! Source of the current time.
+//!
+//! Everything that reads the wall clock (receipt and audit timestamps, the
+//! `--max-age` check) asks a [`Clock`] instead of calling
+//! `OffsetDateTime::now_utc()` directly, so the time can be pinned in tests
+//! or overridden with `--now`.
+
+use std::fmt::Debug;
+
+use time::{Duration, OffsetDateTime};
+
+/// Provides the current time.
+pub trait Clock: Debug + Send + Sync {
+    /// The current time.
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// The system clock, in UTC.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// A clock stopped at one instant.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock {
+    at: OffsetDateTime,
+}
+
+impl FixedClock {
+    /// A clock that always reads `at`.
+    pub fn new(at: OffsetDateTime) -> Self {
+        FixedClock { at }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        self.at
+    }
+}
+
+/// Units accepted by [`parse_duration`], largest first.
+const DURATION_UNITS: [(char, i64); 5] = [('w', 604_800), ('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)];
+
+/// Parse a duration such as `30d`, `48h` or `1d12h`.
+///
+/// Units are `w`, `d`, `h`, `m` and `s`; every number needs one. Returns
+/// `None` for anything else, including a zero duration.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let mut seconds: i64 = 0;
+    let mut digits = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let (_, unit) = DURATION_UNITS.iter().find(|(name, _)| *name == c.to_ascii_lowercase())?;
+        let count: i64 = digits.parse().ok()?;
+        seconds = seconds.checked_add(count.checked_mul(*unit)?)?;
+        digits.clear();
+    }
+    (digits.is_empty() && seconds > 0).then(|| Duration::seconds(seconds))
+}
+
+/// Format a duration the way [`parse_duration`] reads it, e.g. `1d12h`.
+/// Weeks are written as days, so `30d` stays `30d`.
+pub fn format_duration(duration: Duration) -> String {
+    let mut remaining = duration.whole_seconds().max(0);
+    let mut text = String::new();
+    for &(name, unit) in &DURATION_UNITS[1..] {
+        if remaining >= unit {
+            text.push_str(&format!("{}{}", remaining / unit, name));
+            remaining %= unit;
+        }
+    }
+    if text.is_empty() {
+        text.push_str("0s");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30d"), Some(Duration::days(30)));
+        assert_eq!(parse_duration("48h"), Some(Duration::hours(48)));
+        assert_eq!(parse_duration("1d12h"), Some(Duration::hours(36)));
+        assert_eq!(parse_duration("2W"), Some(Duration::weeks(2)));
+        for invalid in ["", "30", "d", "30x", "0d", "1.5h", "-1d"] {
+            assert_eq!(parse_duration(invalid), None, "{invalid:?}");
+        }
+        assert_eq!(format_duration(Duration::hours(36)), "1d12h");
+        assert_eq!(format_duration(Duration::days(30)), "30d");
+    }
+
+    #[test]
+    fn test_fixed_clock_does_not_advance() {
+        let at = OffsetDateTime::from_unix_timestamp(1_769_500_000).unwrap();
+        let clock = FixedClock::new(at);
+        assert_eq!(clock.now(), at);
+        assert_eq!(clock.now(), at);
+    }
+}
@@ -0,0 +1,213 @@
+//! Named presets of CLI flags ("profiles") read from a TOML config file.
+//!
+//! A profile is a `[profiles.NAME]` table whose keys are long flag names
+//! without the leading `--`:
+//!
+//! ```toml
+//! [profiles.strict]
+//! require-vectors = ["location", "clock"]
+//! max-age = "30d"
+//! warnings-as-errors = true
+//! ```
+//!
+//! Only the policy and output flags in [`Profile`] may be preset; any other
+//! key in a profile makes the file invalid. Tables other than `profiles`
+//! are ignored.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VerifyError};
+
+/// Config file read when `--profile` is given without `--config`.
+pub const DEFAULT_CONFIG_FILE: &str = "proofcapture.toml";
+
+/// The value a profile gives a flag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfileValue {
+    /// A switch: `true` passes the flag, `false` leaves it out.
+    Flag(bool),
+    /// A string or number, passed as the flag's value.
+    Value(String),
+    /// An array, passing the flag once per item.
+    List(Vec<String>),
+}
+
+/// The flags a profile may set, each named as its long flag.
+///
+/// Values are only type-checked here; the flag's own parser validates them
+/// when the profile is applied.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compact: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_case: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbose: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follow_audio_ref: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_retries: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_manifest_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_schema_version: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settle_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub move_results: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_vectors: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_full_context: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_interruptions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_interruption_seconds: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trusted_cosigner: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redact: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_raw_manifest: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain_failure: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings_as_errors: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seen_hashes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reject_duplicates: Option<bool>,
+}
+
+impl Profile {
+    /// The flags this profile sets, as `(flag, value)` pairs in name order.
+    pub fn flags(&self) -> Vec<(String, ProfileValue)> {
+        let table = toml::Table::try_from(self).expect("a profile serializes to a table");
+        table
+            .into_iter()
+            .map(|(flag, value)| {
+                let value = match value {
+                    toml::Value::Boolean(set) => ProfileValue::Flag(set),
+                    toml::Value::String(text) => ProfileValue::Value(text),
+                    toml::Value::Array(items) => ProfileValue::List(
+                        items.into_iter().map(|item| item.as_str().unwrap_or_default().to_string()).collect(),
+                    ),
+                    other => ProfileValue::Value(other.to_string()),
+                };
+                (flag, value)
+            })
+            .collect()
+    }
+}
+
+/// Top level of the config file.
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// Read profile `name` from the config file at `path`, as `(flag, value)`
+/// pairs in name order.
+pub fn load_profile(path: &Path, name: &str) -> Result<Vec<(String, ProfileValue)>> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| config_error(format!("Cannot read config file {}: {}", path.display(), e)))?;
+    parse_profile(&text, name)?.ok_or_else(|| config_error(format!("No profile {:?} in {}", name, path.display())))
+}
+
+/// Profile `name` in config file text, or `None` if there is no such table.
+pub fn parse_profile(text: &str, name: &str) -> Result<Option<Vec<(String, ProfileValue)>>> {
+    let mut config: ConfigFile = toml::from_str(text).map_err(|e| config_error(format_toml_error(&e, text)))?;
+    Ok(config.profiles.remove(name).map(|profile| profile.flags()))
+}
+
+fn config_error(message: String) -> VerifyError {
+    VerifyError::ConfigInvalid { message }
+}
+
+/// A TOML error on one line, e.g. `Config line 2: unknown field ...`.
+fn format_toml_error(error: &toml::de::Error, text: &str) -> String {
+    match error.span() {
+        Some(span) => {
+            let line = text.as_bytes().iter().take(span.start).filter(|&&b| b == b'\n').count() + 1;
+            format!("Config line {}: {}", line, error.message())
+        }
+        None => format!("Config: {}", error.message()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+# Shared verification policies
+[profiles.strict]
+require-vectors = ["location", "clock"]  # both, always
+max-age = "30d"
+max-interruptions = 2
+warnings-as-errors = true
+reject-duplicates = false
+
+[profiles.lenient]
+max-age = '1y#not a comment'
+
+[other]
+ignored = { inline = "tables" }
+"#;
+
+    #[test]
+    fn test_parse_profile() {
+        let strict = parse_profile(CONFIG, "strict").unwrap().unwrap();
+        assert_eq!(
+            strict,
+            [
+                ("max-age".to_string(), ProfileValue::Value("30d".into())),
+                ("max-interruptions".to_string(), ProfileValue::Value("2".into())),
+                ("reject-duplicates".to_string(), ProfileValue::Flag(false)),
+                ("require-vectors".to_string(), ProfileValue::List(vec!["location".into(), "clock".into()])),
+                ("warnings-as-errors".to_string(), ProfileValue::Flag(true)),
+            ]
+        );
+        let lenient = parse_profile(CONFIG, "lenient").unwrap().unwrap();
+        assert_eq!(lenient, [("max-age".to_string(), ProfileValue::Value("1y#not a comment".into()))]);
+        assert!(parse_profile(CONFIG, "missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_malformed_profile_names_the_line() {
+        let error = parse_profile("[profiles.strict]\nmax-age = 30 days\n", "strict").unwrap_err();
+        assert!(error.to_string().starts_with("Config line 2: "), "{error}");
+        assert!(parse_profile("[profiles.strict\n", "strict").is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_and_wrong_types_are_rejected() {
+        let error = parse_profile("[profiles.strict]\nmax-age = \"30d\"\nmax-agee = \"1d\"\n", "strict").unwrap_err();
+        assert!(error.to_string().starts_with("Config line 3: unknown field `max-agee`"), "{error}");
+        // A typo in another profile still makes the file invalid
+        assert!(parse_profile("[profiles.a]\nredact = true\n[profiles.b]\nredactt = true\n", "a").is_err());
+        let error = parse_profile("[profiles.strict]\nwarnings-as-errors = \"yes\"\n", "strict").unwrap_err();
+        assert!(error.to_string().starts_with("Config line 2: "), "{error}");
+    }
+}
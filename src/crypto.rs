@@ -7,8 +7,17 @@ use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Nonce,
 };
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+
+use base64::engine::general_purpose::{STANDARD as BASE64, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use p256::ecdsa::{
+    signature::{hazmat::PrehashVerifier, Verifier},
+    Signature, VerifyingKey,
+};
+use p256::pkcs8::DecodePublicKey;
 use sha2::{Digest, Sha256};
 
 use crate::error::{Result, VerifyError};
@@ -25,20 +34,38 @@ pub fn sha256_bytes(data: &[u8]) -> [u8; 32] {
     hash.into()
 }
 
-/// Parses a P-256 public key from raw 64-byte format.
+/// Parses a P-256 public key.
 ///
-/// iOS exports public keys as raw x||y coordinates (64 bytes).
-/// We need to prepend 0x04 (uncompressed point marker) for SEC1 parsing.
-pub fn parse_public_key(raw_64_bytes: &[u8]) -> Result<VerifyingKey> {
-    if raw_64_bytes.len() != 64 {
-        return Err(VerifyError::SignatureInvalid);
-    }
+/// Accepted encodings, told apart by length and leading byte:
+/// - raw x||y coordinates (64 bytes), as exported by iOS
+/// - SEC1 uncompressed point (65 bytes, leading 0x04)
+/// - DER SubjectPublicKeyInfo, as exported by Android
+pub fn parse_public_key(bytes: &[u8]) -> Result<VerifyingKey> {
+    let key = match (bytes.len(), bytes.first()) {
+        (64, _) => {
+            // Prepend 0x04 uncompressed point marker
+            let mut sec1_bytes = vec![0x04];
+            sec1_bytes.extend_from_slice(bytes);
+            VerifyingKey::from_sec1_bytes(&sec1_bytes).ok()
+        }
+        (65, Some(0x04)) => VerifyingKey::from_sec1_bytes(bytes).ok(),
+        (_, Some(0x30)) => VerifyingKey::from_public_key_der(bytes).ok(),
+        _ => None,
+    };
+    key.ok_or(VerifyError::SignatureInvalid)
+}
 
-    // Prepend 0x04 uncompressed point marker
-    let mut sec1_bytes = vec![0x04];
-    sec1_bytes.extend_from_slice(raw_64_bytes);
+/// The raw 64-byte x||y coordinates of a public key, the form `deviceKeyId`
+/// fingerprints whatever encoding the manifest used.
+pub fn raw_public_key(key: &VerifyingKey) -> Vec<u8> {
+    // Strip the 0x04 uncompressed point marker
+    key.to_encoded_point(false).as_bytes()[1..].to_vec()
+}
 
-    VerifyingKey::from_sec1_bytes(&sec1_bytes).map_err(|_| VerifyError::SignatureInvalid)
+/// SHA-256 of the raw coordinates of a public key: the fingerprint that
+/// `deviceKeyId` carries in base64.
+pub fn public_key_fingerprint(key: &VerifyingKey) -> [u8; 32] {
+    sha256_bytes(&raw_public_key(key))
 }
 
 /// Parses an ECDSA signature from raw 64-byte format.
@@ -53,6 +80,9 @@ pub fn parse_signature(raw_64_bytes: &[u8]) -> Result<Signature> {
 }
 
 /// Verifies an ECDSA signature over a message hash.
+///
+/// The 32-byte hash is itself the signed message, so ECDSA hashes it again
+/// internally. This is how iOS 1.x signs manifests.
 pub fn verify_signature(
     public_key: &VerifyingKey,
     message_hash: &[u8; 32],
@@ -61,6 +91,16 @@ pub fn verify_signature(
     public_key.verify(message_hash, signature).is_ok()
 }
 
+/// Verifies an ECDSA signature made directly over a message whose SHA-256
+/// is `digest`, i.e. without the extra round of hashing.
+pub fn verify_signature_prehash(
+    public_key: &VerifyingKey,
+    digest: &[u8; 32],
+    signature: &Signature,
+) -> bool {
+    public_key.verify_prehash(digest, signature).is_ok()
+}
+
 /// Derives an AES-256 key from a password using PBKDF2-HMAC-SHA256.
 ///
 /// Parameters match iOS implementation:
@@ -72,20 +112,158 @@ pub fn derive_key_pbkdf2(password: &str, salt: &[u8], iterations: u32) -> [u8; 3
     key
 }
 
+/// Bounded in-memory cache of PBKDF2-derived keys.
+///
+/// Deriving a key at 600,000 iterations is deliberately slow. Services that
+/// verify many sealed bundles protected by the same password can opt in to
+/// this cache (via `VerifyOptions::with_kdf_cache`) so each
+/// `(salt, iterations, password)` combination is only derived once.
+///
+/// Security tradeoff: derived AES keys stay in process memory for as long as
+/// they remain cached. Anyone able to read this process's memory can recover
+/// them and decrypt every bundle sealed with the same password and salt. The
+/// password itself is never stored, only its SHA-256 hash as part of the
+/// lookup key. Only enable this in trusted services and keep the capacity small.
+pub struct KdfCache {
+    capacity: usize,
+    entries: Mutex<KdfCacheEntries>,
+}
+
+#[derive(Default)]
+struct KdfCacheEntries {
+    keys: HashMap<KdfCacheKey, [u8; 32]>,
+    // Least recently used first
+    order: VecDeque<KdfCacheKey>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct KdfCacheKey {
+    salt: Vec<u8>,
+    iterations: u32,
+    password_hash: [u8; 32],
+}
+
+impl KdfCache {
+    /// Creates a cache holding at most `capacity` derived keys.
+    ///
+    /// When full, the least recently used key is evicted.
+    pub fn new(capacity: usize) -> Self {
+        KdfCache {
+            capacity,
+            entries: Mutex::new(KdfCacheEntries::default()),
+        }
+    }
+
+    /// Maximum number of keys held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of keys currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().keys.len()
+    }
+
+    /// Returns true if no keys are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached keys.
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.keys.clear();
+        entries.order.clear();
+    }
+
+    /// Returns the cached key for these inputs, deriving and caching it on a miss.
+    pub fn derive_key(&self, password: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+        let cache_key = KdfCacheKey {
+            salt: salt.to_vec(),
+            iterations,
+            password_hash: sha256_bytes(password.as_bytes()),
+        };
+
+        {
+            let mut entries = self.lock();
+            if let Some(key) = entries.keys.get(&cache_key).copied() {
+                entries.touch(&cache_key);
+                return key;
+            }
+        }
+
+        // Derive outside the lock so other threads aren't blocked on PBKDF2
+        let key = derive_key_pbkdf2(password, salt, iterations);
+
+        if self.capacity > 0 {
+            let mut entries = self.lock();
+            if entries.keys.insert(cache_key.clone(), key).is_none() {
+                entries.order.push_back(cache_key);
+            }
+            while entries.keys.len() > self.capacity {
+                match entries.order.pop_front() {
+                    Some(oldest) => {
+                        entries.keys.remove(&oldest);
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        key
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, KdfCacheEntries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl KdfCacheEntries {
+    /// Marks a key as most recently used.
+    fn touch(&mut self, cache_key: &KdfCacheKey) {
+        if let Some(pos) = self.order.iter().position(|k| k == cache_key) {
+            if let Some(k) = self.order.remove(pos) {
+                self.order.push_back(k);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for KdfCache {
+    // Never print cached key material
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KdfCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Length of the nonce that prefixes an AES-GCM combined payload.
+pub const AES_GCM_NONCE_LEN: usize = 12;
+
+/// Length of the authentication tag that ends an AES-GCM combined payload.
+pub const AES_GCM_TAG_LEN: usize = 16;
+
 /// Decrypts AES-256-GCM combined format (nonce || ciphertext || tag).
 ///
 /// The encrypted payload format from iOS:
 /// - First 12 bytes: nonce
 /// - Middle: ciphertext
 /// - Last 16 bytes: authentication tag
+///
+/// Version 1 bundles are sealed with empty associated data, so the tag covers
+/// the payload alone and not the envelope's `version`, `salt` or `createdAt`:
+/// a payload spliced into another envelope with the same key still decrypts
+/// (see the spec, section 7.4).
 pub fn decrypt_aes_gcm(key: &[u8; 32], combined: &[u8]) -> Result<Vec<u8>> {
-    if combined.len() < 28 {
+    if combined.len() < AES_GCM_NONCE_LEN + AES_GCM_TAG_LEN {
         // Minimum: 12 (nonce) + 0 (ciphertext) + 16 (tag)
         return Err(VerifyError::BundleCorrupted);
     }
 
-    let nonce = Nonce::from_slice(&combined[..12]);
-    let ciphertext_with_tag = &combined[12..];
+    let nonce: &Nonce<_> = combined[..AES_GCM_NONCE_LEN].into();
+    let ciphertext_with_tag = &combined[AES_GCM_NONCE_LEN..];
 
     let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| VerifyError::DecryptionFailed)?;
 
@@ -94,15 +272,58 @@ pub fn decrypt_aes_gcm(key: &[u8; 32], combined: &[u8]) -> Result<Vec<u8>> {
         .map_err(|_| VerifyError::DecryptionFailed)
 }
 
+/// Encodes bytes as a lowercase hex string.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 /// Decodes a base64 string to bytes.
+///
+/// Tolerates encoding drift between producers: tries the standard alphabet,
+/// then URL-safe (`-`/`_`), then both without padding. Errors report the
+/// standard decoder's failure.
 pub fn decode_base64(encoded: &str) -> Result<Vec<u8>> {
-    BASE64.decode(encoded).map_err(VerifyError::from)
+    BASE64.decode(encoded).or_else(|err| {
+        [URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD]
+            .iter()
+            .find_map(|engine| engine.decode(encoded).ok())
+            .ok_or(VerifyError::Base64(err))
+    })
+}
+
+/// Returns true if `encoded` is the base64 SHA-256 of `data`, in any
+/// alphabet accepted by [`decode_base64`].
+pub fn sha256_matches(data: &[u8], encoded: &str) -> bool {
+    decode_base64(encoded).is_ok_and(|expected| expected == sha256_bytes(data))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_public_key_encodings() {
+        // The same key as raw x||y, SEC1 uncompressed, and DER SPKI
+        let raw = decode_base64("AhfmF/C2RDkoJ4+WmZ5pojpPLBUr321s32bluAKC1O0ZSn3ry5dxLS3aPKhaqHZaVvRfx1hZllLyiXxlMG5XlA==").unwrap();
+        let sec1 = [&[0x04][..], &raw].concat();
+        let der = decode_base64(
+            "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEAhfmF/C2RDkoJ4+WmZ5pojpPLBUr321s32bluAKC1O0ZSn3ry5dxLS3aPKhaqHZaVvRfx1hZllLyiXxlMG5XlA==",
+        )
+        .unwrap();
+
+        let key = parse_public_key(&raw).unwrap();
+        assert_eq!(parse_public_key(&sec1).unwrap(), key);
+        assert_eq!(parse_public_key(&der).unwrap(), key);
+        assert_eq!(raw_public_key(&key), raw);
+
+        // A 65-byte key must carry the uncompressed marker; DER must be well-formed
+        let mut bad_marker = sec1.clone();
+        bad_marker[0] = 0x05;
+        assert!(matches!(parse_public_key(&bad_marker), Err(VerifyError::SignatureInvalid)));
+        assert!(matches!(parse_public_key(&der[..der.len() - 1]), Err(VerifyError::SignatureInvalid)));
+        assert!(matches!(parse_public_key(&raw[..32]), Err(VerifyError::SignatureInvalid)));
+    }
+
     #[test]
     fn test_sha256_empty() {
         let hash = sha256_base64(b"");
@@ -115,6 +336,29 @@ mod tests {
         assert_eq!(hash, "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=");
     }
 
+    #[test]
+    fn test_decode_base64_accepts_every_alphabet() {
+        // 0xfb 0xff 0xbf exercises both alphabet-specific characters
+        let bytes = [0xfb, 0xff, 0xbf, 0x01];
+        for encoded in ["+/+/AQ==", "-_-_AQ==", "+/+/AQ", "-_-_AQ"] {
+            assert_eq!(decode_base64(encoded).unwrap(), bytes, "{}", encoded);
+        }
+        assert!(matches!(decode_base64("not base64!"), Err(VerifyError::Base64(_))));
+    }
+
+    #[test]
+    fn test_sha256_matches() {
+        assert!(sha256_matches(b"hello", "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="));
+        assert!(sha256_matches(b"hello", "LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ"));
+        assert!(!sha256_matches(b"hellO", "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="));
+    }
+
+    #[test]
+    fn test_hex_encode() {
+        assert_eq!(hex_encode(&[]), "");
+        assert_eq!(hex_encode(&[0x00, 0x0f, 0xab, 0xff]), "000fabff");
+    }
+
     #[test]
     fn test_pbkdf2_derivation() {
         // Basic test that PBKDF2 produces deterministic output
@@ -126,4 +370,53 @@ mod tests {
         let key3 = derive_key_pbkdf2("different", b"salt", 1000);
         assert_ne!(key1, key3);
     }
+
+    #[test]
+    fn test_kdf_cache_matches_uncached_derivation() {
+        let cache = KdfCache::new(4);
+
+        let uncached = derive_key_pbkdf2("password", b"salt", 1000);
+        let first = cache.derive_key("password", b"salt", 1000);
+        let second = cache.derive_key("password", b"salt", 1000);
+
+        assert_eq!(first, uncached);
+        assert_eq!(second, uncached);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_kdf_cache_keys_on_all_inputs() {
+        let cache = KdfCache::new(4);
+
+        cache.derive_key("password", b"salt", 1000);
+        assert_eq!(cache.derive_key("different", b"salt", 1000), derive_key_pbkdf2("different", b"salt", 1000));
+        assert_eq!(cache.derive_key("password", b"pepper", 1000), derive_key_pbkdf2("password", b"pepper", 1000));
+        assert_eq!(cache.derive_key("password", b"salt", 1001), derive_key_pbkdf2("password", b"salt", 1001));
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn test_kdf_cache_is_bounded() {
+        let cache = KdfCache::new(2);
+
+        cache.derive_key("a", b"salt", 10);
+        cache.derive_key("b", b"salt", 10);
+        // Touch "a" so "b" becomes least recently used
+        cache.derive_key("a", b"salt", 10);
+        cache.derive_key("c", b"salt", 10);
+
+        assert_eq!(cache.len(), 2);
+        let entries = cache.lock();
+        let hashes: Vec<_> = entries.order.iter().map(|k| k.password_hash).collect();
+        assert_eq!(hashes, vec![sha256_bytes(b"a"), sha256_bytes(b"c")]);
+    }
+
+    #[test]
+    fn test_kdf_cache_zero_capacity_stores_nothing() {
+        let cache = KdfCache::new(0);
+        let key = cache.derive_key("password", b"salt", 1000);
+
+        assert_eq!(key, derive_key_pbkdf2("password", b"salt", 1000));
+        assert!(cache.is_empty());
+    }
 }
@@ -0,0 +1,231 @@
+//! Diagnostics for a manifest whose signature doesn't verify.
+//!
+//! A `SignatureInvalid` failure looks the same whether the manifest was
+//! tampered with or the producer got a detail of the format wrong. These
+//! checks rerun verification's steps one at a time, and try the likely
+//! producer mistakes, to give a best guess at which it was.
+
+use std::fmt;
+
+use p256::ecdsa::{Signature, VerifyingKey};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::crypto::{decode_base64, parse_public_key, parse_signature, verify_signature, verify_signature_prehash};
+use crate::error::Result;
+use crate::manifest::{compute_canonical_hash_from_bytes, decompress_manifest, SignatureMode, SignedAudioManifest};
+
+/// The outcome of one diagnostic check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiagnosticCheck {
+    /// What was checked, e.g. `"Public key parses"`.
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Best guess at why a signature doesn't verify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureCause {
+    /// The manifest carries no embedded signature to diagnose.
+    NoEmbeddedSignature,
+    /// `publicKey` isn't a P-256 key in a supported encoding.
+    PublicKeyInvalid,
+    /// The signature isn't raw 64-byte r||s; it looks DER-encoded.
+    SignatureDerEncoded,
+    /// The signature isn't raw 64-byte r||s.
+    SignatureEncoding,
+    /// The canonical JSON changes when the manifest is re-serialized.
+    NonDeterministicCanonicalization,
+    /// The signature verifies under the other signature mode.
+    WrongSignatureMode { declared: SignatureMode },
+    /// Nothing else explains it: the manifest changed after signing, or a
+    /// different key signed it.
+    ModifiedOrWrongKey,
+}
+
+impl FailureCause {
+    /// Stable machine-readable identifier.
+    pub fn code(&self) -> &'static str {
+        match self {
+            FailureCause::NoEmbeddedSignature => "no_embedded_signature",
+            FailureCause::PublicKeyInvalid => "public_key_invalid",
+            FailureCause::SignatureDerEncoded => "signature_der_encoded",
+            FailureCause::SignatureEncoding => "signature_encoding",
+            FailureCause::NonDeterministicCanonicalization => "non_deterministic_canonicalization",
+            FailureCause::WrongSignatureMode { .. } => "wrong_signature_mode",
+            FailureCause::ModifiedOrWrongKey => "modified_or_wrong_key",
+        }
+    }
+}
+
+impl fmt::Display for FailureCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureCause::NoEmbeddedSignature => {
+                write!(f, "The manifest has no embedded signature; detached signatures aren't diagnosed")
+            }
+            FailureCause::PublicKeyInvalid => write!(
+                f,
+                "publicKey is not a P-256 key as raw x||y (64 bytes), SEC1 (65 bytes) or DER SubjectPublicKeyInfo"
+            ),
+            FailureCause::SignatureDerEncoded => {
+                write!(f, "The signature is DER-encoded; it must be the raw 64-byte r||s form")
+            }
+            FailureCause::SignatureEncoding => write!(f, "The signature is not a raw 64-byte r||s value"),
+            FailureCause::NonDeterministicCanonicalization => write!(
+                f,
+                "The manifest doesn't survive re-serialization unchanged (e.g. numbers that lose precision), so its canonical JSON is ambiguous"
+            ),
+            FailureCause::WrongSignatureMode { declared: SignatureMode::Digest } => write!(
+                f,
+                "The canonical JSON was signed directly. The signer should set \"signatureMode\": \"message\" before signing"
+            ),
+            FailureCause::WrongSignatureMode { declared: SignatureMode::Message } => write!(
+                f,
+                "The canonical hash was signed as the message, but the manifest declares \"signatureMode\": \"message\". The signer should use \"digest\""
+            ),
+            FailureCause::ModifiedOrWrongKey => write!(
+                f,
+                "The manifest was modified after signing, or was signed by a key other than publicKey"
+            ),
+        }
+    }
+}
+
+/// The diagnostic checks run and the cause they point to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureDiagnosis {
+    pub checks: Vec<DiagnosticCheck>,
+    pub cause: FailureCause,
+}
+
+/// Diagnose why the embedded signature of `manifest_bytes` doesn't verify.
+///
+/// Fails only if the manifest can't be parsed at all.
+pub fn diagnose_signature(manifest_bytes: &[u8]) -> Result<SignatureDiagnosis> {
+    let manifest = SignedAudioManifest::from_json(manifest_bytes)?;
+    let mut checks = Vec::new();
+    let mut check = |name, passed, detail: String| {
+        checks.push(DiagnosticCheck { name, passed, detail });
+        passed
+    };
+
+    let Some(encoded_signature) = &manifest.signature else {
+        check("Embedded signature present", false, "No signature field".to_string());
+        return Ok(SignatureDiagnosis { checks, cause: FailureCause::NoEmbeddedSignature });
+    };
+
+    let public_key_bytes = decode_base64(&manifest.public_key).unwrap_or_default();
+    let public_key = parse_public_key(&public_key_bytes).ok();
+    if !check("Public key parses", public_key.is_some(), format!("{} bytes", public_key_bytes.len())) {
+        return Ok(SignatureDiagnosis { checks, cause: FailureCause::PublicKeyInvalid });
+    }
+
+    let signature_bytes = decode_base64(encoded_signature).unwrap_or_default();
+    let signature = parse_signature(&signature_bytes).ok();
+    if !check("Signature is raw r||s", signature.is_some(), format!("{} bytes", signature_bytes.len())) {
+        // A DER ECDSA signature is a SEQUENCE (0x30) of about 70 bytes
+        let cause = match signature_bytes.first() {
+            Some(0x30) => FailureCause::SignatureDerEncoded,
+            _ => FailureCause::SignatureEncoding,
+        };
+        return Ok(SignatureDiagnosis { checks, cause });
+    }
+
+    let manifest_hash = compute_canonical_hash_from_bytes(manifest_bytes)?;
+    let deterministic = reserialized_hash(manifest_bytes)? == manifest_hash;
+    let detail = if deterministic {
+        "Same hash after re-serializing the manifest"
+    } else {
+        "Hash changes when the manifest is re-serialized"
+    };
+    if !check("Canonical hash is deterministic", deterministic, detail.to_string()) {
+        return Ok(SignatureDiagnosis { checks, cause: FailureCause::NonDeterministicCanonicalization });
+    }
+
+    let (public_key, signature) = (public_key.unwrap(), signature.unwrap());
+    let low_s = signature.normalize_s().is_none();
+    let detail = if low_s {
+        "s is in the lower half of the curve order"
+    } else {
+        "s is in the upper half; some verifiers reject this"
+    };
+    check("Signature is low-S", low_s, detail.to_string());
+
+    let declared = manifest.signature_mode.unwrap_or(SignatureMode::Digest);
+    let other = match declared {
+        SignatureMode::Digest => SignatureMode::Message,
+        SignatureMode::Message => SignatureMode::Digest,
+    };
+    let verifies_as_other = verifies(&public_key, &manifest_hash, &signature, other);
+    let detail = format!("Declared {}; tried {}", mode_name(declared), mode_name(other));
+    if check("Verifies under the other signature mode", verifies_as_other, detail) {
+        return Ok(SignatureDiagnosis { checks, cause: FailureCause::WrongSignatureMode { declared } });
+    }
+
+    Ok(SignatureDiagnosis { checks, cause: FailureCause::ModifiedOrWrongKey })
+}
+
+/// The canonical hash of the manifest after a parse/serialize round trip.
+fn reserialized_hash(manifest_bytes: &[u8]) -> Result<[u8; 32]> {
+    let value: Value = serde_json::from_slice(&decompress_manifest(manifest_bytes)?)?;
+    compute_canonical_hash_from_bytes(&serde_json::to_vec(&value)?)
+}
+
+fn verifies(key: &VerifyingKey, hash: &[u8; 32], signature: &Signature, mode: SignatureMode) -> bool {
+    match mode {
+        SignatureMode::Digest => verify_signature(key, hash, signature),
+        SignatureMode::Message => verify_signature_prehash(key, hash, signature),
+    }
+}
+
+fn mode_name(mode: SignatureMode) -> &'static str {
+    match mode {
+        SignatureMode::Digest => "digest",
+        SignatureMode::Message => "message",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::raw_public_key;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+    use p256::ecdsa::signature::hazmat::PrehashSigner;
+    use p256::ecdsa::SigningKey;
+    use std::path::PathBuf;
+
+    fn fixture_manifest(name: &str) -> Value {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name).join("manifest.json");
+        serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_undeclared_message_mode_is_diagnosed() {
+        // Signed over the canonical JSON directly, without declaring "message"
+        let key = SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let mut manifest = fixture_manifest("minimal_bundle");
+        manifest["publicKey"] = BASE64.encode(raw_public_key(key.verifying_key())).into();
+        let hash = compute_canonical_hash_from_bytes(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        let signature: Signature = key.sign_prehash(&hash).unwrap();
+        manifest["signature"] = BASE64.encode(signature.to_bytes()).into();
+
+        let diagnosis = diagnose_signature(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        assert_eq!(diagnosis.cause, FailureCause::WrongSignatureMode { declared: SignatureMode::Digest });
+        assert!(diagnosis.cause.to_string().contains("\"signatureMode\": \"message\""));
+        assert!(diagnosis.checks.iter().all(|c| c.passed));
+    }
+
+    #[test]
+    fn test_tampered_manifest_is_diagnosed_as_modified() {
+        let mut manifest = fixture_manifest("minimal_bundle");
+        manifest["durationSeconds"] = 99.into();
+        let diagnosis = diagnose_signature(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        assert_eq!(diagnosis.cause, FailureCause::ModifiedOrWrongKey);
+
+        manifest["signature"] = BASE64.encode([0x30; 70]).into();
+        let diagnosis = diagnose_signature(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        assert_eq!(diagnosis.cause, FailureCause::SignatureDerEncoded);
+    }
+}
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::trust::TrustVector;
+
 /// Verification errors with specific exit codes.
 #[derive(Error, Debug)]
 pub enum VerifyError {
@@ -20,18 +22,84 @@ pub enum VerifyError {
     #[error("Audio file not found")]
     AudioFileMissing,
 
+    #[error("Manifest file not found. Pass --manifest with the audio file")]
+    ManifestMissing,
+
     #[error("Audio file is corrupted")]
     AudioFileCorrupt,
 
     #[error("Could not decrypt. Check your password")]
     DecryptionFailed,
 
+    #[error("Password cannot be empty")]
+    PasswordEmpty,
+
     #[error("This file has been modified and cannot be opened")]
     BundleCorrupted,
 
     #[error("This sealed proof requires a newer app version")]
     UnsupportedBundleVersion { version: i32 },
 
+    #[error("Receipt signing key is invalid")]
+    ReceiptKeyInvalid,
+
+    #[error("Verification receipt is invalid")]
+    ReceiptInvalid,
+
+    #[error("Verification receipt is for a different bundle: its {field} does not match")]
+    ReceiptStale { field: String },
+
+    #[error("Required trust vector missing: {vector}")]
+    RequiredVectorMissing { vector: TrustVector },
+
+    #[error("Full context required; missing trust vectors: {}", list_vectors(.missing))]
+    IncompleteContext { missing: Vec<TrustVector> },
+
+    #[error("Refusing to overwrite {path}. Pass --assume-yes to replace it")]
+    OverwriteRefused { path: String },
+
+    #[error("This recording has been verified before")]
+    DuplicateRecording,
+
+    #[error("File is empty")]
+    EmptyFile,
+
+    #[error("Recording has {count} interruption(s), more than the {max} allowed")]
+    TooManyInterruptions { count: usize, max: usize },
+
+    #[error("Found {count} structural problem(s)")]
+    LintFailed { count: usize },
+
+    #[error("Extract path is not a directory: {path}")]
+    ExtractPathNotDirectory { path: String },
+
+    #[error("Extract directory is not writable: {path}")]
+    ExtractDirNotWritable { path: String },
+
+    #[error("Recording ended at {capture_end}, more than {max_age} ago")]
+    RecordingTooOld { capture_end: String, max_age: String },
+
+    #[error("{message}")]
+    ConfigInvalid { message: String },
+
+    #[error("Verified with {count} warning(s)")]
+    WarningsPresent { count: usize },
+
+    #[error("Attachment {filename} has been modified")]
+    AttachmentHashMismatch { filename: String },
+
+    #[error("Sealed payload {field} does not match its digest")]
+    PayloadDigestMismatch { field: String },
+
+    #[error("A password is needed up front. Pass --password or --password-env")]
+    PasswordRequired,
+
+    #[error("This option needs a bundle PATH")]
+    PathRequired,
+
+    #[error("Network error: {0}")]
+    Network(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -56,10 +124,185 @@ impl VerifyError {
             VerifyError::BundleCorrupted => 8,
             VerifyError::UnsupportedBundleVersion { .. } => 9,
             VerifyError::Io(_) => 10,
+            VerifyError::ReceiptKeyInvalid => 11,
+            VerifyError::ReceiptInvalid => 12,
+            VerifyError::RequiredVectorMissing { .. } => 13,
+            VerifyError::Network(_) => 14,
+            VerifyError::ManifestMissing => 15,
+            VerifyError::WarningsPresent { .. } => 16,
+            VerifyError::AttachmentHashMismatch { .. } => 17,
+            VerifyError::PasswordEmpty => 18,
+            VerifyError::IncompleteContext { .. } => 19,
+            VerifyError::OverwriteRefused { .. } => 20,
+            VerifyError::DuplicateRecording => 21,
+            VerifyError::EmptyFile => 22,
+            VerifyError::TooManyInterruptions { .. } => 23,
+            VerifyError::LintFailed { .. } => 24,
+            VerifyError::ExtractPathNotDirectory { .. } => 25,
+            VerifyError::ExtractDirNotWritable { .. } => 26,
+            VerifyError::RecordingTooOld { .. } => 27,
+            VerifyError::ConfigInvalid { .. } => 28,
+            VerifyError::PayloadDigestMismatch { .. } => 29,
+            VerifyError::PasswordRequired => 30,
+            VerifyError::ReceiptStale { .. } => 31,
+            VerifyError::PathRequired => 32,
             VerifyError::Json(_) => 3, // Treat as manifest malformed
             VerifyError::Base64(_) => 3,
         }
     }
 }
 
+/// Name of the error kind behind an exit code, e.g. `HashMismatch` for 1.
+///
+/// Lets outcomes stored only as exit codes, such as batch records, be grouped
+/// by kind. Unknown codes are `Unknown`.
+pub fn exit_code_name(code: i32) -> &'static str {
+    match code {
+        1 => "HashMismatch",
+        2 => "SignatureInvalid",
+        3 => "ManifestMalformed",
+        4 => "SchemaUnsupported",
+        5 => "AudioFileMissing",
+        6 => "AudioFileCorrupt",
+        7 => "DecryptionFailed",
+        8 => "BundleCorrupted",
+        9 => "UnsupportedBundleVersion",
+        10 => "Io",
+        11 => "ReceiptKeyInvalid",
+        12 => "ReceiptInvalid",
+        13 => "RequiredVectorMissing",
+        14 => "Network",
+        15 => "ManifestMissing",
+        16 => "WarningsPresent",
+        17 => "AttachmentHashMismatch",
+        18 => "PasswordEmpty",
+        19 => "IncompleteContext",
+        20 => "OverwriteRefused",
+        21 => "DuplicateRecording",
+        22 => "EmptyFile",
+        23 => "TooManyInterruptions",
+        24 => "LintFailed",
+        25 => "ExtractPathNotDirectory",
+        26 => "ExtractDirNotWritable",
+        27 => "RecordingTooOld",
+        28 => "ConfigInvalid",
+        29 => "PayloadDigestMismatch",
+        30 => "PasswordRequired",
+        31 => "ReceiptStale",
+        32 => "PathRequired",
+        _ => "Unknown",
+    }
+}
+
+fn list_vectors(vectors: &[TrustVector]) -> String {
+    vectors.iter().map(TrustVector::name).collect::<Vec<_>>().join(", ")
+}
+
 pub type Result<T> = std::result::Result<T, VerifyError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One error of each kind. The match has no wildcard, so a new variant
+    /// doesn't compile until it's listed here too.
+    fn one_of_each() -> Vec<VerifyError> {
+        let text = || "x".to_string();
+        let errors = vec![
+            VerifyError::HashMismatch,
+            VerifyError::SignatureInvalid,
+            VerifyError::ManifestMalformed,
+            VerifyError::SchemaUnsupported { version: 9 },
+            VerifyError::AudioFileMissing,
+            VerifyError::ManifestMissing,
+            VerifyError::AudioFileCorrupt,
+            VerifyError::DecryptionFailed,
+            VerifyError::PasswordEmpty,
+            VerifyError::BundleCorrupted,
+            VerifyError::UnsupportedBundleVersion { version: 9 },
+            VerifyError::ReceiptKeyInvalid,
+            VerifyError::ReceiptInvalid,
+            VerifyError::ReceiptStale { field: text() },
+            VerifyError::RequiredVectorMissing { vector: TrustVector::Location },
+            VerifyError::IncompleteContext { missing: vec![] },
+            VerifyError::OverwriteRefused { path: text() },
+            VerifyError::DuplicateRecording,
+            VerifyError::EmptyFile,
+            VerifyError::TooManyInterruptions { count: 2, max: 1 },
+            VerifyError::LintFailed { count: 1 },
+            VerifyError::ExtractPathNotDirectory { path: text() },
+            VerifyError::ExtractDirNotWritable { path: text() },
+            VerifyError::RecordingTooOld { capture_end: text(), max_age: text() },
+            VerifyError::ConfigInvalid { message: text() },
+            VerifyError::WarningsPresent { count: 1 },
+            VerifyError::AttachmentHashMismatch { filename: text() },
+            VerifyError::PayloadDigestMismatch { field: text() },
+            VerifyError::PasswordRequired,
+            VerifyError::PathRequired,
+            VerifyError::Network(text()),
+            VerifyError::Io(std::io::ErrorKind::Other.into()),
+            VerifyError::Json(serde_json::from_str::<()>("").unwrap_err()),
+            VerifyError::Base64(base64::DecodeError::InvalidLength),
+        ];
+        for error in &errors {
+            match error {
+                VerifyError::HashMismatch
+                | VerifyError::SignatureInvalid
+                | VerifyError::ManifestMalformed
+                | VerifyError::SchemaUnsupported { .. }
+                | VerifyError::AudioFileMissing
+                | VerifyError::ManifestMissing
+                | VerifyError::AudioFileCorrupt
+                | VerifyError::DecryptionFailed
+                | VerifyError::PasswordEmpty
+                | VerifyError::BundleCorrupted
+                | VerifyError::UnsupportedBundleVersion { .. }
+                | VerifyError::ReceiptKeyInvalid
+                | VerifyError::ReceiptInvalid
+                | VerifyError::ReceiptStale { .. }
+                | VerifyError::RequiredVectorMissing { .. }
+                | VerifyError::IncompleteContext { .. }
+                | VerifyError::OverwriteRefused { .. }
+                | VerifyError::DuplicateRecording
+                | VerifyError::EmptyFile
+                | VerifyError::TooManyInterruptions { .. }
+                | VerifyError::LintFailed { .. }
+                | VerifyError::ExtractPathNotDirectory { .. }
+                | VerifyError::ExtractDirNotWritable { .. }
+                | VerifyError::RecordingTooOld { .. }
+                | VerifyError::ConfigInvalid { .. }
+                | VerifyError::WarningsPresent { .. }
+                | VerifyError::AttachmentHashMismatch { .. }
+                | VerifyError::PayloadDigestMismatch { .. }
+                | VerifyError::PasswordRequired
+                | VerifyError::PathRequired
+                | VerifyError::Network(_)
+                | VerifyError::Io(_)
+                | VerifyError::Json(_)
+                | VerifyError::Base64(_) => {}
+            }
+        }
+        errors
+    }
+
+    #[test]
+    fn test_exit_code_names_match_variants() {
+        let mut codes = Vec::new();
+        for error in one_of_each() {
+            let code = error.exit_code();
+            let debug = format!("{:?}", error);
+            let variant = debug.split(|c: char| !c.is_alphanumeric()).next().unwrap();
+            match error {
+                // Parse errors share ManifestMalformed's code
+                VerifyError::Json(_) | VerifyError::Base64(_) => assert_eq!(exit_code_name(code), "ManifestMalformed"),
+                _ => {
+                    assert_eq!(exit_code_name(code), variant);
+                    assert!(!codes.contains(&code), "{} reuses exit code {}", variant, code);
+                    codes.push(code);
+                }
+            }
+        }
+        assert_eq!(exit_code_name(0), "Unknown");
+        assert_eq!(exit_code_name(codes.iter().max().unwrap() + 1), "Unknown");
+    }
+}
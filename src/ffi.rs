@@ -0,0 +1,166 @@
+//! C-compatible interface for embedding the verifier in non-Rust apps.
+//!
+//! Available with the `ffi` cargo feature. Build a shared or static library
+//! with e.g. `cargo rustc --release --lib --features ffi --crate-type cdylib`.
+//!
+//! ```c
+//! typedef struct {
+//!     int32_t status;       /* 0 on success, otherwise the CLI exit code */
+//!     uint8_t trust_level;  /* 'A', 'B' or 'C' on success, 0 on failure */
+//!     char *message;        /* error message on failure, NULL on success */
+//! } PcResult;
+//!
+//! int32_t pc_verify_sealed_bytes(const uint8_t *bundle, size_t len,
+//!                                const char *password, PcResult *out);
+//! int32_t pc_verify_open_bytes(const uint8_t *bundle, size_t len, PcResult *out);
+//! void pc_string_free(char *s);
+//! ```
+//!
+//! Ownership: the caller owns `bundle`, `password` and `out`, and only needs
+//! them valid for the duration of the call. `out->message` is allocated by
+//! this library and must be released with `pc_string_free`, never `free`.
+//! Each function returns the same value it stores in `out->status`.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use std::slice;
+
+use crate::error::{Result, VerifyError};
+use crate::options::VerifyOptions;
+use crate::verify::{verify_open_bytes, verify_sealed_bytes, VerificationResult};
+
+/// Status returned when a required pointer argument is null or invalid.
+pub const PC_STATUS_INVALID_ARGUMENT: i32 = -1;
+
+/// Verification outcome filled in by the `pc_verify_*` functions.
+#[repr(C)]
+#[derive(Debug)]
+pub struct PcResult {
+    /// 0 on success, otherwise the CLI exit code for the failure.
+    pub status: i32,
+    /// ASCII trust level letter on success, 0 on failure.
+    pub trust_level: u8,
+    /// Error message on failure, null on success. Free with [`pc_string_free`].
+    pub message: *mut c_char,
+}
+
+/// Verify a sealed (.proofcapture) bundle held in memory.
+///
+/// # Safety
+///
+/// `bundle` must point to `len` readable bytes, `password` must be a valid
+/// NUL-terminated UTF-8 string, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pc_verify_sealed_bytes(
+    bundle: *const u8,
+    len: usize,
+    password: *const c_char,
+    out: *mut PcResult,
+) -> i32 {
+    if bundle.is_null() || password.is_null() || out.is_null() {
+        return PC_STATUS_INVALID_ARGUMENT;
+    }
+    let bytes = slice::from_raw_parts(bundle, len);
+    let result = match CStr::from_ptr(password).to_str() {
+        Ok(password) => verify_sealed_bytes(bytes, password, &VerifyOptions::default()),
+        Err(_) => Err(VerifyError::DecryptionFailed),
+    };
+    write_result(&result, out)
+}
+
+/// Verify an open (.proofbundle) bundle held in memory.
+///
+/// # Safety
+///
+/// `bundle` must point to `len` readable bytes and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pc_verify_open_bytes(bundle: *const u8, len: usize, out: *mut PcResult) -> i32 {
+    if bundle.is_null() || out.is_null() {
+        return PC_STATUS_INVALID_ARGUMENT;
+    }
+    let bytes = slice::from_raw_parts(bundle, len);
+    write_result(&verify_open_bytes(bytes, &VerifyOptions::default()), out)
+}
+
+/// Free a string allocated by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer returned by this library that hasn't
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn pc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+unsafe fn write_result(result: &Result<VerificationResult>, out: *mut PcResult) -> i32 {
+    let pc_result = match result {
+        Ok(result) => PcResult {
+            status: 0,
+            trust_level: result.trust_level.letter().as_bytes()[0],
+            message: ptr::null_mut(),
+        },
+        Err(e) => PcResult {
+            status: e.exit_code(),
+            trust_level: 0,
+            // Error messages never contain NUL bytes
+            message: CString::new(e.to_string()).map_or(ptr::null_mut(), CString::into_raw),
+        },
+    };
+    let status = pc_result.status;
+    out.write(pc_result);
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> Vec<u8> {
+        std::fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name)).unwrap()
+    }
+
+    fn empty_result() -> PcResult {
+        PcResult { status: 99, trust_level: 0, message: ptr::null_mut() }
+    }
+
+    #[test]
+    fn test_ffi_sealed_round_trip() {
+        let bundle = fixture("sealed_fast_test.proofcapture");
+        let password = CString::new("test-password-123").unwrap();
+        let mut out = empty_result();
+
+        let status = unsafe { pc_verify_sealed_bytes(bundle.as_ptr(), bundle.len(), password.as_ptr(), &mut out) };
+
+        assert_eq!(status, 0);
+        assert_eq!(out.status, 0);
+        assert_eq!(out.trust_level, b'C');
+        assert!(out.message.is_null());
+    }
+
+    #[test]
+    fn test_ffi_failure_allocates_message() {
+        let bundle = fixture("sealed_fast_test.proofcapture");
+        let password = CString::new("wrong").unwrap();
+        let mut out = empty_result();
+
+        let status = unsafe { pc_verify_sealed_bytes(bundle.as_ptr(), bundle.len(), password.as_ptr(), &mut out) };
+
+        assert_eq!(status, VerifyError::DecryptionFailed.exit_code());
+        assert_eq!(out.trust_level, 0);
+        let message = unsafe { CStr::from_ptr(out.message) }.to_str().unwrap().to_string();
+        assert_eq!(message, VerifyError::DecryptionFailed.to_string());
+        unsafe { pc_string_free(out.message) };
+    }
+
+    #[test]
+    fn test_ffi_rejects_null_arguments() {
+        let mut out = empty_result();
+        let status = unsafe { pc_verify_open_bytes(ptr::null(), 0, &mut out) };
+        assert_eq!(status, PC_STATUS_INVALID_ARGUMENT);
+        assert_eq!(out.status, 99); // untouched
+    }
+}
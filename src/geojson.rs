@@ -0,0 +1,122 @@
+//! GeoJSON export of a recording's location vector.
+//!
+//! A verified recording with a location vector becomes a FeatureCollection
+//! of its start and end points and the LineString between them, so it can
+//! be loaded directly into GIS tools. Coordinates are `[lon, lat]` as
+//! GeoJSON requires; accuracy (meters) is a property of each point.
+
+use serde_json::{json, Value};
+
+use crate::manifest::LocationSnapshot;
+use crate::report::redact_coordinate;
+use crate::verify::VerificationResult;
+
+/// Note carried by the collection of a recording without a location vector.
+pub const NO_LOCATION_NOTE: &str = "Recording has no location vector";
+
+/// The GeoJSON FeatureCollection for a verified recording.
+///
+/// Without a location vector the collection is empty and has a `note`.
+/// With `redact`, coordinates are rounded as in redacted reports.
+pub fn feature_collection(result: &VerificationResult, redact: bool) -> Value {
+    let manifest = &result.manifest;
+    let Some(location) = &manifest.trust_vectors.location else {
+        return json!({
+            "type": "FeatureCollection",
+            "features": [],
+            "note": NO_LOCATION_NOTE,
+        });
+    };
+
+    let properties = |role: &str| {
+        json!({
+            "role": role,
+            "trustLevel": result.trust_level.letter(),
+            "captureStart": manifest.capture_start,
+            "captureEnd": manifest.capture_end,
+        })
+    };
+    let coordinates = |snapshot: &LocationSnapshot| {
+        if redact {
+            json!([redact_coordinate(snapshot.lon), redact_coordinate(snapshot.lat)])
+        } else {
+            json!([snapshot.lon, snapshot.lat])
+        }
+    };
+    let point = |role: &str, snapshot: &LocationSnapshot| {
+        let mut properties = properties(role);
+        properties["accuracy"] = snapshot.accuracy.into();
+        json!({
+            "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": coordinates(snapshot) },
+            "properties": properties,
+        })
+    };
+
+    json!({
+        "type": "FeatureCollection",
+        "features": [
+            point("start", &location.start),
+            point("end", &location.end),
+            {
+                "type": "Feature",
+                "geometry": {
+                    "type": "LineString",
+                    "coordinates": [coordinates(&location.start), coordinates(&location.end)],
+                },
+                "properties": properties("path"),
+            },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::verify_standard_bundle;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> VerificationResult {
+        verify_standard_bundle(&PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_feature_collection_has_points_and_line() {
+        let result = fixture("full_bundle");
+        let location = result.manifest.trust_vectors.location.clone().unwrap();
+        let collection = feature_collection(&result, false);
+
+        assert_eq!(collection["type"], "FeatureCollection");
+        let features = collection["features"].as_array().unwrap();
+        let geometries: Vec<_> = features.iter().map(|f| f["geometry"]["type"].as_str().unwrap()).collect();
+        assert_eq!(geometries, ["Point", "Point", "LineString"]);
+        assert!(features.iter().all(|f| f["type"] == "Feature"));
+
+        let start = json!([location.start.lon, location.start.lat]);
+        let end = json!([location.end.lon, location.end.lat]);
+        assert_eq!(features[0]["geometry"]["coordinates"], start);
+        assert_eq!(features[2]["geometry"]["coordinates"], json!([start, end]));
+        assert_eq!(features[1]["properties"]["trustLevel"], result.trust_level.letter());
+        assert_eq!(features[1]["properties"]["captureEnd"], result.manifest.capture_end.as_str());
+    }
+
+    #[test]
+    fn test_feature_collection_without_location_is_empty() {
+        let collection = feature_collection(&fixture("minimal_bundle"), false);
+        assert_eq!(collection["features"], json!([]));
+        assert_eq!(collection["note"], NO_LOCATION_NOTE);
+    }
+
+    #[test]
+    fn test_feature_collection_redacts_coordinates() {
+        let result = fixture("full_bundle");
+        let start = result.manifest.trust_vectors.location.clone().unwrap().start;
+        let collection = feature_collection(&result, true);
+
+        let redacted = json!([redact_coordinate(start.lon), redact_coordinate(start.lat)]);
+        assert_ne!(redacted, json!([start.lon, start.lat]));
+        assert_eq!(collection["features"][0]["geometry"]["coordinates"], redacted);
+        assert_eq!(collection["features"][2]["geometry"]["coordinates"][0], redacted);
+        assert!(!collection.to_string().contains(&start.lat.to_string()));
+    }
+}
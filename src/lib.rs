@@ -18,14 +18,45 @@
 //! let result = verify_sealed_bundle(Path::new("evidence.proofcapture"), "password");
 //! ```
 
+pub mod audit;
+pub mod batch;
+pub mod cache;
+pub mod certificate;
+pub mod clock;
+pub mod config;
 pub mod crypto;
+pub mod diagnose;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod geojson;
+pub mod lint;
 pub mod manifest;
+pub mod messages;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod mp4;
+pub mod options;
+pub mod receipt;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "remote")]
+pub mod remote;
 pub mod sealed;
+pub mod tar;
+pub mod timezones;
 pub mod trust;
 pub mod verify;
+pub mod warnings;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub mod wasm;
+pub mod watch;
 
 pub use error::{Result, VerifyError};
 pub use manifest::SignedAudioManifest;
-pub use trust::TrustLevel;
-pub use verify::{verify_audio_and_manifest, verify_sealed_bundle, verify_and_extract_sealed_bundle, verify_standard_bundle, verify_open_bundle, VerificationResult, SealedVerificationResult};
+pub use options::VerifyOptions;
+pub use receipt::VerificationReceipt;
+pub use report::{verify_bundle_to_report, verify_sealed_bytes_to_report, VerificationReport};
+pub use trust::{TrustLevel, TrustPolicy, TrustVector};
+pub use warnings::Warning;
+pub use verify::{detect_bundle_type, CoSignerResult, inspect_sealed, resolve_bundle_path, BundlePreference, BundleType, verify_audio_and_manifest, verify_audio_with_manifest_and_bytes, verify_integrity_only, verify_bundle, verify_sealed_bundle, verify_and_extract_sealed_bundle, verify_standard_bundle, verify_open_bundle, verify_archive_bundle, VerificationResult, SealedVerificationResult};
@@ -0,0 +1,204 @@
+//! Structural checks that skip all cryptography.
+//!
+//! Linting reports what is malformed in a bundle without hashing the audio,
+//! deriving keys or checking signatures, so it's cheap enough to triage a
+//! large intake before verifying it. A bundle that lints clean can still fail
+//! verification, e.g. if its audio or manifest was modified.
+//!
+//! Sealed bundles are encrypted, so only their envelope is checked.
+
+use std::fs;
+use std::path::Path;
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::crypto::{decode_base64, parse_public_key, AES_GCM_NONCE_LEN, AES_GCM_TAG_LEN};
+use crate::error::Result;
+use crate::manifest::{decompress_manifest, validate_trust_vectors, SignedAudioManifest, CURRENT_SCHEMA_VERSION};
+use crate::sealed::{SealedProofBundle, CURRENT_BUNDLE_VERSION, MIN_SALT_LEN};
+use crate::verify::{bundle_manifest_path, detect_bundle_type, load_manifest_bytes, BundleType};
+
+/// Length of a SHA-256 digest, as carried by `audioHash` and `deviceKeyId`.
+const SHA256_LEN: usize = 32;
+
+/// Length of a raw r||s P-256 signature.
+const SIGNATURE_LEN: usize = 64;
+
+/// List the structural problems in the bundle at `path`; empty if none.
+///
+/// Bundle directories need only a manifest; the audio isn't read. Fails
+/// only if the bundle can't be found or read.
+pub fn lint_bundle(path: &Path) -> Result<Vec<String>> {
+    match detect_bundle_type(path) {
+        BundleType::Sealed => Ok(lint_sealed_envelope(&fs::read(path)?)),
+        BundleType::Standard if path.is_dir() => {
+            let manifest = fs::read(bundle_manifest_path(path)?)?;
+            Ok(lint_manifest(&manifest))
+        }
+        _ => Ok(lint_manifest(&load_manifest_bytes(path, None)?)),
+    }
+}
+
+/// List the structural problems in manifest JSON (optionally gzipped).
+pub fn lint_manifest(manifest_bytes: &[u8]) -> Vec<String> {
+    let json = match decompress_manifest(manifest_bytes) {
+        Ok(json) => json,
+        Err(_) => return vec!["manifest: gzip stream is corrupt".to_string()],
+    };
+    let manifest: SignedAudioManifest = match serde_json::from_slice(&json) {
+        Ok(manifest) => manifest,
+        Err(e) => return vec![format!("manifest: does not parse: {}", e)],
+    };
+
+    let mut problems = Vec::new();
+    if manifest.schema_version > CURRENT_SCHEMA_VERSION {
+        problems.push(format!("schemaVersion: {} is not supported", manifest.schema_version));
+    }
+
+    check_decoded_len(&mut problems, "audioHash", &manifest.audio_hash, SHA256_LEN);
+    check_decoded_len(&mut problems, "deviceKeyId", &manifest.device_key_id, SHA256_LEN);
+    if let Some(signature) = &manifest.signature {
+        check_decoded_len(&mut problems, "signature", signature, SIGNATURE_LEN);
+    }
+    check_public_key(&mut problems, "publicKey", &manifest.public_key);
+    for (i, co) in manifest.signatures.iter().enumerate() {
+        check_decoded_len(&mut problems, &format!("signatures[{}].signature", i), &co.signature, SIGNATURE_LEN);
+        check_public_key(&mut problems, &format!("signatures[{}].publicKey", i), &co.public_key);
+    }
+
+    if manifest.audio_size_bytes < 0 {
+        problems.push("audioSizeBytes: negative".to_string());
+    }
+    if manifest.duration_seconds < 0.0 {
+        problems.push("durationSeconds: negative".to_string());
+    }
+
+    let vectors = &manifest.trust_vectors;
+    let mut timestamps = vec![("captureStart", &manifest.capture_start), ("captureEnd", &manifest.capture_end)];
+    if let Some(clock) = &vectors.clock {
+        timestamps.push(("clock.wallClockStart", &clock.wall_clock_start));
+        timestamps.push(("clock.wallClockEnd", &clock.wall_clock_end));
+    }
+    if let Some(continuity) = &vectors.continuity {
+        timestamps.extend(continuity.interruption_events.iter().map(|e| ("continuity.interruptionEvents", &e.timestamp)));
+    }
+    for (field, value) in timestamps {
+        check_timestamp(&mut problems, field, value);
+    }
+
+    if validate_trust_vectors(vectors).is_err() {
+        problems.push("trustVectors: values out of range".to_string());
+    }
+
+    problems
+}
+
+/// List the structural problems in a sealed bundle's unencrypted envelope.
+pub fn lint_sealed_envelope(bundle_bytes: &[u8]) -> Vec<String> {
+    let bundle = match SealedProofBundle::from_json(bundle_bytes) {
+        Ok(bundle) => bundle,
+        Err(_) => return vec!["envelope: does not parse".to_string()],
+    };
+
+    let mut problems = Vec::new();
+    if bundle.version > CURRENT_BUNDLE_VERSION {
+        problems.push(format!("version: {} is not supported", bundle.version));
+    }
+    if bundle.kdf_algorithm != "pbkdf2" {
+        problems.push(format!("kdfAlgorithm: {:?} is not supported", bundle.kdf_algorithm));
+    }
+    match decode_base64(&bundle.salt) {
+        Ok(salt) if salt.len() < MIN_SALT_LEN => {
+            problems.push(format!("salt: decodes to {} bytes, expected at least {}", salt.len(), MIN_SALT_LEN));
+        }
+        Ok(_) => {}
+        Err(_) => problems.push("salt: not valid base64".to_string()),
+    }
+    match decode_base64(&bundle.encrypted_payload) {
+        Ok(payload) if payload.len() < AES_GCM_NONCE_LEN + AES_GCM_TAG_LEN => {
+            problems.push(format!("encryptedPayload: {} bytes is too short", payload.len()));
+        }
+        Ok(_) => {}
+        Err(_) => problems.push("encryptedPayload: not valid base64".to_string()),
+    }
+    check_timestamp(&mut problems, "createdAt", &bundle.created_at);
+
+    problems
+}
+
+fn check_decoded_len(problems: &mut Vec<String>, field: &str, value: &str, expected: usize) {
+    match decode_base64(value) {
+        Ok(bytes) if bytes.len() != expected => {
+            problems.push(format!("{}: decodes to {} bytes, expected {}", field, bytes.len(), expected));
+        }
+        Ok(_) => {}
+        Err(_) => problems.push(format!("{}: not valid base64", field)),
+    }
+}
+
+fn check_public_key(problems: &mut Vec<String>, field: &str, value: &str) {
+    match decode_base64(value) {
+        Ok(bytes) if parse_public_key(&bytes).is_err() => {
+            problems.push(format!("{}: not a P-256 public key", field));
+        }
+        Ok(_) => {}
+        Err(_) => problems.push(format!("{}: not valid base64", field)),
+    }
+}
+
+fn check_timestamp(problems: &mut Vec<String>, field: &str, value: &str) {
+    if OffsetDateTime::parse(value, &Rfc3339).is_err() {
+        problems.push(format!("{}: {:?} is not an RFC 3339 timestamp", field, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::VerifyError;
+    use crate::verify::verify_standard_bundle;
+    use std::path::PathBuf;
+
+    fn fixtures_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+    }
+
+    #[test]
+    fn test_tampered_manifest_lints_clean_but_fails_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["manifest.json", "recording.m4a"] {
+            fs::copy(fixtures_dir().join("full_bundle").join(name), dir.path().join(name)).unwrap();
+        }
+        let manifest_path = dir.path().join("manifest.json");
+        let tampered = fs::read_to_string(&manifest_path).unwrap().replace("1.0.0", "9.9.9");
+        fs::write(&manifest_path, tampered).unwrap();
+
+        assert_eq!(lint_bundle(dir.path()).unwrap(), Vec::<String>::new());
+        assert!(matches!(verify_standard_bundle(dir.path()), Err(VerifyError::SignatureInvalid)));
+    }
+
+    #[test]
+    fn test_lint_reports_structural_problems() {
+        let bytes = fs::read(fixtures_dir().join("full_bundle/manifest.json")).unwrap();
+        let mut manifest: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        manifest["audioHash"] = "AAAA".into();
+        manifest["captureEnd"] = "yesterday".into();
+
+        let problems = lint_manifest(&serde_json::to_vec(&manifest).unwrap());
+        assert_eq!(
+            problems,
+            ["audioHash: decodes to 3 bytes, expected 32", "captureEnd: \"yesterday\" is not an RFC 3339 timestamp"]
+        );
+
+        manifest.as_object_mut().unwrap().remove("publicKey");
+        let problems = lint_manifest(&serde_json::to_vec(&manifest).unwrap());
+        assert!(problems[0].contains("missing field `publicKey`"), "{problems:?}");
+    }
+
+    #[test]
+    fn test_lint_sealed_envelope_without_password() {
+        assert!(lint_bundle(&fixtures_dir().join("sealed_test.proofcapture")).unwrap().is_empty());
+        assert_eq!(lint_sealed_envelope(b"{}"), ["envelope: does not parse"]);
+    }
+}
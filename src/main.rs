@@ -2,160 +2,1496 @@
 //!
 //! Verify ProofCapture recordings from the command line.
 
+use std::borrow::Cow;
+use std::ffi::OsString;
 use std::fs;
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use clap::Parser;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, Parser};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
-use proofcapture_cli::verify::{verify_sealed_bundle, verify_and_extract_sealed_bundle, verify_standard_bundle, verify_open_bundle, VerificationResult};
-use proofcapture_cli::VerifyError;
+use proofcapture_cli::audit::{AuditLog, AuditRecord};
+use proofcapture_cli::clock::{parse_duration, Clock, FixedClock, SystemClock};
+use proofcapture_cli::batch::{content_hash, discover_bundles, needs_password, verify_batch, BatchCounts, BatchEntry, BatchExtraction, BatchState};
+use proofcapture_cli::certificate::render_certificate;
+use proofcapture_cli::config::{load_profile, ProfileValue, DEFAULT_CONFIG_FILE};
+use proofcapture_cli::crypto::{decode_base64, hex_encode, parse_public_key, public_key_fingerprint};
+use proofcapture_cli::diagnose::{diagnose_signature, SignatureDiagnosis};
+use proofcapture_cli::geojson;
+use proofcapture_cli::lint::lint_bundle;
+use proofcapture_cli::manifest::{canonical_summary, CURRENT_SCHEMA_VERSION};
+use proofcapture_cli::messages::{Catalog, MessageKey};
+use proofcapture_cli::mp4::AudioProperties;
+use proofcapture_cli::receipt::{load_operator_public_key, load_signing_key};
+use proofcapture_cli::registry::HashRegistry;
+use proofcapture_cli::sealed::{NonceTracker, SealedProofBundle};
+use proofcapture_cli::trust::{vector_tag, TrustPolicy};
+use proofcapture_cli::report::{
+    redact_coordinate, redact_key_id, verification_report_schema, REDACTED_COORDINATE_DECIMALS, ExtractedFileReport, ExtractionReport, JsonCase, VerificationReport,
+};
+#[cfg(feature = "remote")]
+use proofcapture_cli::remote;
+use proofcapture_cli::warnings::{distance_meters, Warning};
+use proofcapture_cli::watch::{file_result, verify_arrival, DirectoryWatcher};
+use proofcapture_cli::verify::{
+    detect_bundle_type, inspect_sealed, load_manifest_bytes, resolve_bundle_path, BundlePreference, resolve_audio_for_manifest, resolve_standard_bundle, verify_audio_and_manifest_with_options, verify_and_extract_sealed_bundle_with_options, verify_files, verify_open_bundle_with_options, verify_archive_bundle_with_options, prepare_extract_dir, write_extracted_file,
+    verify_sealed_bundle_with_options, verify_standard_bundle_with_options, BundleType, VerificationResult,
+};
+use proofcapture_cli::{SignedAudioManifest, TrustVector, VerificationReceipt, VerifyError, VerifyOptions};
 
 /// ProofCapture CLI Verifier - Verify ProofCapture recordings
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(name = "proofcapture-cli")]
 #[command(author = "Best Day Labs")]
 #[command(version)]
 #[command(about = "Verify ProofCapture recordings from the command line")]
+#[command(group = clap::ArgGroup::new("extraction").args(["extract", "output_dir"]))]
 struct Args {
     /// Path to a proof bundle (.proofcapture, .proofbundle, or directory)
-    #[arg(value_name = "PATH")]
-    path: PathBuf,
+    ///
+    /// With the `remote` feature, an http:// or https:// URL to a .proofcapture or .proofbundle file.
+    #[arg(value_name = "PATH", required_unless_present_any = ["print_schema", "audio_b64"])]
+    path: Option<PathBuf>,
 
     /// Password for sealed bundles (will prompt if not provided)
     #[arg(short, long)]
     password: Option<String>,
 
-    /// Output format: text or json
-    #[arg(short, long, default_value = "text")]
+    /// Read the sealed bundle password from environment variable VAR
+    #[arg(long, value_name = "VAR", conflicts_with = "password")]
+    password_env: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "auto", ignore_case = true)]
     format: OutputFormat,
 
+    /// Overrides terminal detection for `--format auto`; set by tests.
+    #[arg(skip)]
+    stdout_terminal: Option<bool>,
+
+    /// Emit compact single-line JSON instead of pretty-printed JSON
+    #[arg(long)]
+    compact: bool,
+
+    /// Key casing of JSON output: camel or snake
+    #[arg(long, value_name = "CASE", default_value = "camel")]
+    json_case: JsonCase,
+
     /// Show verbose output
     #[arg(short, long)]
     verbose: bool,
 
+    /// Language of explanatory text, e.g. en (defaults to LANG, then English)
+    #[arg(long, value_name = "CODE")]
+    lang: Option<String>,
+
+    /// Units for distances and sizes in text output: metric (m/km, KiB/MiB) or imperial (ft/mi, KB/MB)
+    #[arg(long, value_name = "UNITS", default_value = "metric")]
+    units: Units,
+
     /// Extract audio file from sealed bundle to specified directory
     #[arg(short, long, value_name = "DIR")]
     extract: Option<PathBuf>,
+
+    /// Overwrite existing files when extracting, without asking
+    #[arg(short = 'y', long, visible_alias = "force", requires = "extraction")]
+    assume_yes: bool,
+
+    /// In a directory holding both a .proofcapture file and a loose bundle, verify this one: sealed or standard
+    #[arg(long, value_name = "VARIANT", default_value = "sealed")]
+    prefer: BundlePreference,
+
+    /// Audio file to verify against the manifest given as PATH
+    #[arg(long, value_name = "FILE")]
+    audio: Option<PathBuf>,
+
+    /// Manifest to verify the audio file given as PATH against
+    #[arg(long, value_name = "FILE")]
+    manifest: Option<PathBuf>,
+
+    /// Fetch the audio from the manifest's audioRef when no local audio is found (see README)
+    #[arg(long)]
+    follow_audio_ref: bool,
+
+    /// Read the manifest JSON from stdin and verify the audio at PATH against it
+    #[arg(long, conflicts_with_all = ["manifest", "batch", "debug_canonical", "benchmark"])]
+    manifest_stdin: bool,
+
+    /// Audio to verify, inline as base64, instead of a PATH (requires --manifest-b64)
+    #[arg(
+        long,
+        value_name = "BASE64",
+        requires = "manifest_b64",
+        conflicts_with_all = [
+            "path", "audio", "manifest", "manifest_stdin", "follow_audio_ref", "batch", "watch", "lint",
+            "debug_canonical", "list_vectors", "extract", "audit_log"
+        ]
+    )]
+    audio_b64: Option<String>,
+
+    /// Manifest JSON to verify the --audio-b64 audio against, inline as base64
+    #[arg(long, value_name = "BASE64", requires = "audio_b64")]
+    manifest_b64: Option<String>,
+
+    /// Sign a verification receipt with the operator key in KEYFILE
+    #[arg(long, value_name = "KEYFILE")]
+    sign_receipt: Option<PathBuf>,
+
+    /// Check an earlier verification receipt in FILE against this bundle,
+    /// failing unless it is signed by --receipt-key and its hashes match
+    #[arg(long, value_name = "FILE", requires = "receipt_key", conflicts_with_all = ["batch", "watch"])]
+    verify_receipt: Option<PathBuf>,
+
+    /// Operator public key file (base64 raw x||y) that signed --verify-receipt
+    #[arg(long, value_name = "KEYFILE", requires = "verify_receipt")]
+    receipt_key: Option<PathBuf>,
+
+    /// Print the JSON Schema of the --format json output and exit
+    #[arg(long)]
+    print_schema: bool,
+
+    /// Verify every bundle directly inside the directory given as PATH
+    #[arg(long, conflicts_with_all = ["extract", "audio", "manifest", "sign_receipt"])]
+    batch: bool,
+
+    /// Verify bundles as they arrive in the directory given as PATH, until interrupted
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "batch", "extract", "audio", "manifest", "sign_receipt", "lint", "inspect", "debug_canonical",
+            "print_public_key", "manifest_only_json", "benchmark",
+        ]
+    )]
+    watch: bool,
+
+    /// With --watch, wait until a bundle has been unchanged for SECS before verifying it
+    #[arg(long, value_name = "SECS", default_value_t = 2, requires = "watch")]
+    settle_seconds: u64,
+
+    /// With --watch, move each bundle into verified/ or failed/ once checked
+    #[arg(long, requires = "watch")]
+    move_results: bool,
+
+    /// With --batch, verify up to N bundles at once (default: one per CPU)
+    #[arg(long, value_name = "N", requires = "batch", value_parser = clap::value_parser!(u32).range(1..))]
+    concurrency: Option<u32>,
+
+    /// Record batch progress in FILE so an interrupted run can resume
+    #[arg(long, value_name = "FILE", requires = "batch")]
+    state_file: Option<PathBuf>,
+
+    /// Preset flags from profile NAME in the config file; flags given here win
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Config file holding the profiles (default proofcapture.toml)
+    #[arg(long, value_name = "FILE", requires = "profile")]
+    config: Option<PathBuf>,
+
+    /// Retry a failed bundle read up to N times with backoff, for flaky network storage
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    read_retries: u32,
+
+    /// Refuse to parse manifests larger than N bytes (default 4 MiB)
+    #[arg(long, value_name = "N")]
+    max_manifest_bytes: Option<usize>,
+
+    /// Accept manifests up to schema version N, for testing draft schemas (experimental)
+    #[arg(long, value_name = "N")]
+    allow_schema_version: Option<i32>,
+
+    /// With --batch, extract each sealed bundle's media into DIR/<file stem>/
+    #[arg(long, value_name = "DIR", requires = "batch")]
+    output_dir: Option<PathBuf>,
+
+    /// With --batch, print only totals by trust level and error
+    #[arg(long, requires = "batch", conflicts_with = "list_vectors")]
+    count: bool,
+
+    /// Fail unless these trust vectors are present (comma-separated: location,motion,continuity,clock)
+    #[arg(long, value_name = "LIST", value_delimiter = ',')]
+    require_vectors: Vec<TrustVector>,
+
+    /// Fail unless all four trust vectors (location, motion, continuity, clock) are present
+    #[arg(long)]
+    require_full_context: bool,
+
+    /// Fail if the recording has more than N continuity interruptions
+    #[arg(long, value_name = "N")]
+    max_interruptions: Option<usize>,
+
+    /// Still award Level A if interruptions total less than SECS (all events must record a duration)
+    #[arg(long, value_name = "SECS", value_parser = parse_max_interruption_seconds)]
+    max_interruption_seconds: Option<f64>,
+
+    /// Fail if the recording ended more than DURATION ago, e.g. 30d or 48h (see --now)
+    #[arg(long, value_name = "DURATION", value_parser = parse_max_age)]
+    max_age: Option<time::Duration>,
+
+    /// Trust co-signatures made by KEY (base64 raw P-256 public key); repeatable
+    #[arg(long = "trusted-cosigner", value_name = "KEY", value_parser = parse_cosigner_key)]
+    trusted_cosigners: Vec<String>,
+
+    /// Check the bundle's structure only, skipping hashing, key derivation and signatures
+    #[arg(
+        long,
+        conflicts_with_all = ["batch", "debug_canonical", "list_vectors", "print", "extract", "sign_receipt", "benchmark"]
+    )]
+    lint: bool,
+
+    /// Print a sealed bundle's unencrypted metadata (version, KDF, creation time) without a password
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "batch", "lint", "debug_canonical", "list_vectors", "print", "extract", "sign_receipt", "benchmark",
+            "audio_b64"
+        ]
+    )]
+    inspect: bool,
+
+    /// Print the parsed manifest as JSON, without verifying it
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "batch", "lint", "inspect", "debug_canonical", "print_public_key", "list_vectors", "print", "extract",
+            "sign_receipt", "benchmark", "audio_b64"
+        ]
+    )]
+    manifest_only_json: bool,
+
+    /// Print the manifest's public key and its SHA-256 fingerprint (hex), without verifying
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "batch", "lint", "inspect", "debug_canonical", "list_vectors", "print", "extract", "sign_receipt", "benchmark",
+            "audio_b64"
+        ]
+    )]
+    print_public_key: bool,
+
+    /// Print the manifest's canonical top-level keys and length, without verifying
+    #[arg(long, conflicts_with = "batch")]
+    debug_canonical: bool,
+
+    /// Print one line per bundle with its trust level and present vectors
+    #[arg(long)]
+    list_vectors: bool,
+
+    /// Print only one value: trust-level, audio-hash, capture-start, duration or app-version
+    #[arg(
+        long,
+        value_name = "FIELD",
+        conflicts_with_all = ["batch", "debug_canonical", "list_vectors", "sign_receipt", "benchmark"]
+    )]
+    print: Option<PrintField>,
+
+    /// Round coordinates and mask key identifiers in the output
+    #[arg(long)]
+    redact: bool,
+
+    /// Embed the manifest bytes as verified, base64-encoded, in JSON output
+    #[arg(long, conflicts_with = "redact")]
+    include_raw_manifest: bool,
+
+    /// On a signature failure, run diagnostics and print the likely cause
+    #[arg(long, conflicts_with_all = ["batch", "watch"])]
+    explain_failure: bool,
+
+    /// Exit nonzero if verification raised any warnings
+    #[arg(long, visible_alias = "strict")]
+    warnings_as_errors: bool,
+
+    /// Write the recording's location as a GeoJSON FeatureCollection to FILE
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["batch", "debug_canonical", "list_vectors", "print", "benchmark"])]
+    geojson_out: Option<PathBuf>,
+
+    /// Write a printable HTML verification certificate to FILE
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["batch", "watch", "debug_canonical", "list_vectors", "print", "benchmark"])]
+    certificate: Option<PathBuf>,
+
+    /// Append a JSON line recording each verification to FILE
+    #[arg(long, value_name = "FILE", conflicts_with = "debug_canonical")]
+    audit_log: Option<PathBuf>,
+
+    /// Warn about audio hashes already listed in FILE, and add new ones to it
+    #[arg(long, value_name = "FILE")]
+    seen_hashes: Option<PathBuf>,
+
+    /// Fail instead of warning when the audio hash is in the --seen-hashes file
+    #[arg(long, requires = "seen_hashes")]
+    reject_duplicates: bool,
+
+    /// Judge the recording's --max-age as of TIMESTAMP (RFC 3339)
+    #[arg(long, value_name = "TIMESTAMP", value_parser = parse_now)]
+    now: Option<OffsetDateTime>,
+
+    /// Verify PATH N times and print latency statistics
+    #[arg(
+        long,
+        value_name = "N",
+        hide = true,
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with_all = ["batch", "debug_canonical", "list_vectors", "extract", "sign_receipt", "audit_log", "seen_hashes"]
+    )]
+    benchmark: Option<u32>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl Args {
+    /// The output format, with `auto` resolved by whether stdout is a terminal.
+    fn format(&self) -> OutputFormat {
+        match self.format {
+            OutputFormat::Auto if self.stdout_is_terminal() => OutputFormat::Text,
+            OutputFormat::Auto => OutputFormat::Json,
+            format => format,
+        }
+    }
+
+    /// Whether JSON is compact: with --compact, or when `auto` picked JSON.
+    fn compact(&self) -> bool {
+        self.compact || (self.format == OutputFormat::Auto && !self.stdout_is_terminal())
+    }
+
+    fn stdout_is_terminal(&self) -> bool {
+        self.stdout_terminal.unwrap_or_else(|| io::stdout().is_terminal())
+    }
+
+    /// `self` with the password taken from the --password-env variable, if
+    /// given, using `lookup` to read the environment.
+    fn with_password_from_env(self, lookup: impl Fn(&str) -> Option<String>) -> Result<Self, VerifyError> {
+        let Some(var) = &self.password_env else {
+            return Ok(self);
+        };
+        let password = lookup(var).ok_or(VerifyError::PasswordRequired)?;
+        Ok(Args { password: Some(password), ..self })
+    }
+
+    /// `self` with a directory PATH holding a sealed bundle resolved per
+    /// --prefer. Explicit --audio/--manifest/--manifest-stdin keep PATH as is.
+    fn with_bundle_resolved(self) -> Self {
+        if self.audio.is_some() || self.manifest.is_some() || self.manifest_stdin {
+            return self;
+        }
+        let path = self.path.as_deref().map(|path| resolve_bundle_path(path, self.prefer));
+        Args { path, ..self }
+    }
+
+    /// The bundle path. clap requires it unless --print-schema or --audio-b64
+    /// was given; an option reading it alongside inline input gets
+    /// `PathRequired`.
+    fn path(&self) -> Result<&Path, VerifyError> {
+        self.path.as_deref().ok_or(VerifyError::PathRequired)
+    }
+
+    /// The bundle path for messages, `-` for inline input.
+    fn path_label(&self) -> &Path {
+        self.path.as_deref().unwrap_or(Path::new("-"))
+    }
+
+    /// The --now clock, or the system clock.
+    ///
+    /// Only judging the recording uses it; receipts, audit records and
+    /// certificates record when they were actually made.
+    fn clock(&self) -> Arc<dyn Clock> {
+        match self.now {
+            Some(now) => Arc::new(FixedClock::new(now)),
+            None => Arc::new(SystemClock),
+        }
+    }
+
+    /// Message catalog for --lang, else the LANG environment variable.
+    fn catalog(&self) -> Catalog {
+        match self.lang.clone().or_else(|| std::env::var("LANG").ok()) {
+            Some(code) => Catalog::for_language(&code),
+            None => Catalog::english(),
+        }
+    }
+}
+
+/// Parse a --now timestamp.
+fn parse_now(value: &str) -> Result<OffsetDateTime, String> {
+    OffsetDateTime::parse(value, &Rfc3339).map_err(|_| format!("Invalid timestamp: {}. Use RFC 3339, e.g. 2026-02-01T12:00:00Z", value))
+}
+
+/// Parse a --max-age duration.
+fn parse_max_age(value: &str) -> Result<time::Duration, String> {
+    parse_duration(value).ok_or_else(|| format!("Invalid duration: {}. Use e.g. 30d, 48h or 1d12h", value))
+}
+
+/// Parse a --max-interruption-seconds threshold: a finite, non-negative number.
+fn parse_max_interruption_seconds(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .ok_or_else(|| format!("Invalid number of seconds: {}. Use a non-negative number, e.g. 2 or 0.5", value))
+}
+
+/// Parse a --trusted-cosigner public key.
+fn parse_cosigner_key(value: &str) -> Result<String, String> {
+    decode_base64(value)
+        .and_then(|bytes| parse_public_key(&bytes))
+        .map(|_| value.to_string())
+        .map_err(|_| format!("Invalid public key: {}. Use the base64 key from the manifest's signatures array", value))
+}
+
+/// Everything produced by a successful run.
+struct Outcome {
+    result: VerificationResult,
+    receipt: Option<VerificationReceipt>,
+    /// The --verify-receipt receipt, checked against this verification.
+    checked_receipt: Option<VerificationReceipt>,
+    /// Files written by --extract.
+    extraction: Option<ExtractionReport>,
+    /// Wall-clock time spent in `run`.
+    elapsed: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
 enum OutputFormat {
+    /// Text on a terminal, compact JSON when piped or redirected
+    Auto,
+    /// Human-readable summary
     Text,
+    /// JSON report
     Json,
 }
 
-impl std::str::FromStr for OutputFormat {
+/// Unit system for distances and sizes in text output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Units {
+    /// Meters/kilometers and binary sizes (KiB, MiB).
+    Metric,
+    /// Feet/miles and decimal sizes (KB, MB).
+    Imperial,
+}
+
+impl std::str::FromStr for Units {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "text" => Ok(OutputFormat::Text),
-            "json" => Ok(OutputFormat::Json),
-            _ => Err(format!("Unknown format: {}. Use 'text' or 'json'", s)),
+            "metric" => Ok(Units::Metric),
+            "imperial" => Ok(Units::Imperial),
+            _ => Err(format!("Unknown units: {}. Use 'metric' or 'imperial'", s)),
+        }
+    }
+}
+
+/// Format a distance, switching to km/mi once it reaches one of them.
+fn format_distance(meters: f64, units: Units) -> String {
+    const FEET_PER_METER: f64 = 3.280_84;
+    const FEET_PER_MILE: f64 = 5280.0;
+
+    match units {
+        Units::Metric if meters >= 1000.0 => format!("{:.1} km", meters / 1000.0),
+        Units::Metric => format!("{:.0} m", meters),
+        Units::Imperial => {
+            let feet = meters * FEET_PER_METER;
+            if feet >= FEET_PER_MILE {
+                format!("{:.1} mi", feet / FEET_PER_MILE)
+            } else {
+                format!("{:.0} ft", feet)
+            }
+        }
+    }
+}
+
+/// Format a byte count in the largest unit it reaches, followed by the exact count.
+fn format_size(bytes: i64, units: Units) -> String {
+    let (base, suffixes) = match units {
+        Units::Metric => (1024.0, ["KiB", "MiB", "GiB"]),
+        Units::Imperial => (1000.0, ["KB", "MB", "GB"]),
+    };
+
+    let mut value = bytes as f64;
+    let mut suffix = None;
+    for next in suffixes {
+        if value < base {
+            break;
+        }
+        value /= base;
+        suffix = Some(next);
+    }
+
+    match suffix {
+        Some(suffix) => format!("{:.1} {} ({} bytes)", value, suffix, bytes),
+        None => format!("{} bytes", bytes),
+    }
+}
+
+/// A single value printed by --print.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PrintField {
+    TrustLevel,
+    AudioHash,
+    CaptureStart,
+    Duration,
+    AppVersion,
+}
+
+impl std::str::FromStr for PrintField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "trust-level" => Ok(PrintField::TrustLevel),
+            "audio-hash" => Ok(PrintField::AudioHash),
+            "capture-start" => Ok(PrintField::CaptureStart),
+            "duration" => Ok(PrintField::Duration),
+            "app-version" => Ok(PrintField::AppVersion),
+            _ => Err(format!(
+                "Unknown field: {}. Use trust-level, audio-hash, capture-start, duration or app-version",
+                s
+            )),
+        }
+    }
+}
+
+impl PrintField {
+    fn value(self, result: &VerificationResult) -> String {
+        let m = &result.manifest;
+        match self {
+            PrintField::TrustLevel => result.trust_level.letter().to_string(),
+            PrintField::AudioHash => m.audio_hash.clone(),
+            PrintField::CaptureStart => m.capture_start.clone(),
+            PrintField::Duration => m.duration_seconds.to_string(),
+            PrintField::AppVersion => m.app_version.clone(),
         }
     }
 }
 
 fn main() -> ExitCode {
     let args = Args::parse();
+    let args = match &args.profile {
+        Some(name) => match with_profile(std::env::args_os().collect(), &args, name) {
+            Ok(profiled) => profiled,
+            Err(e) => return error_exit(&e, &args),
+        },
+        None => args,
+    };
+    let args = match args.clone().with_password_from_env(|var| std::env::var(var).ok()) {
+        Ok(args) => args,
+        Err(e) => return error_exit(&e, &args),
+    };
+
+    if args.print_schema {
+        println!("{}", render_json(&verification_report_schema(), args.compact(), JsonCase::Camel));
+        return ExitCode::SUCCESS;
+    }
+
+    if let Some(version) = args.allow_schema_version.filter(|&v| v > CURRENT_SCHEMA_VERSION) {
+        eprintln!(
+            "WARNING: accepting schemaVersion up to {} (this release supports {}). Results are experimental.",
+            version, CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    if args.batch {
+        return report(run_batch(&args), &args, |code| code);
+    }
+
+    if args.watch {
+        return report(run_watch(&args), &args, |code| code);
+    }
+
+    let args = args.with_bundle_resolved();
+
+    if args.debug_canonical {
+        return report(debug_canonical(&args), &args, |()| ExitCode::SUCCESS);
+    }
+
+    if args.manifest_only_json {
+        return report(manifest_only_json(&args), &args, |json| {
+            eprintln!("Unverified: the manifest was parsed but its signature was not checked");
+            println!("{}", json);
+            ExitCode::SUCCESS
+        });
+    }
+
+    if args.print_public_key {
+        return report(public_key_info(&args), &args, |(public_key, fingerprint)| {
+            print_public_key(&public_key, &fingerprint, &args);
+            ExitCode::SUCCESS
+        });
+    }
+
+    if args.lint {
+        return report(args.path().and_then(lint_bundle), &args, |problems| {
+            print_lint(&problems, &args);
+            match problems.len() {
+                0 => ExitCode::SUCCESS,
+                count => ExitCode::from(VerifyError::LintFailed { count }.exit_code() as u8),
+            }
+        });
+    }
+
+    if args.inspect {
+        return report(inspect(&args), &args, |bundle| {
+            print_inspect(&bundle, &args);
+            ExitCode::SUCCESS
+        });
+    }
 
-    match run(&args) {
-        Ok(result) => {
-            print_success(&result, &args);
+    if let Some(iterations) = args.benchmark {
+        return report(benchmark(&args, iterations), &args, |stats| {
+            print_benchmark(&stats, &args);
             ExitCode::SUCCESS
+        });
+    }
+
+    // Everything below verifies once; a sealed bundle's password is asked
+    // for here, before timing starts, and reused by --explain-failure
+    let args = match with_password(&args) {
+        Ok(args) => args.into_owned(),
+        Err(e) => return error_exit(&e, &args),
+    };
+
+    if args.list_vectors {
+        let outcome = verify(&args);
+        if let Err(e) = audit(&args, outcome.as_ref()) {
+            return error_exit(&e, &args);
+        }
+        return match outcome {
+            Ok(result) => {
+                let present = TrustVector::present(&result.manifest.trust_vectors);
+                println!("{}", vectors_line(args.path_label(), result.trust_level.letter(), &present));
+                warnings_exit(&result, &args)
+            }
+            Err(e) => {
+                println!("{}", failed_line(args.path_label(), &e.to_string()));
+                ExitCode::from(e.exit_code() as u8)
+            }
+        };
+    }
+
+    if let Some(field) = args.print {
+        let outcome = verify(&args);
+        if let Err(e) = audit(&args, outcome.as_ref()) {
+            return error_exit(&e, &args);
+        }
+        return report(outcome, &args, |result| {
+            println!("{}", field.value(&result));
+            warnings_exit(&result, &args)
+        });
+    }
+
+    let outcome = run(&args);
+    if let Err(e) = audit(&args, outcome.as_ref().map(|o| &o.result)) {
+        return error_exit(&e, &args);
+    }
+
+    match outcome {
+        Ok(outcome) => {
+            print_success(&outcome, &args);
+            warnings_exit(&outcome.result, &args)
         }
         Err(e) => {
-            print_error(&e, &args);
+            match explain_failure(&e, &args) {
+                Some(diagnosis) => print_error_with_diagnosis(&e, &diagnosis, &args),
+                None => print_error(&e, &args),
+            }
             ExitCode::from(e.exit_code() as u8)
         }
     }
-}
+}
+
+/// The exit code `print` gives a successful `outcome`, or the error's code
+/// once it's printed.
+fn report<T>(outcome: Result<T, VerifyError>, args: &Args, print: impl FnOnce(T) -> ExitCode) -> ExitCode {
+    match outcome {
+        Ok(value) => print(value),
+        Err(e) => error_exit(&e, args),
+    }
+}
+
+/// Print `error` and return its exit code.
+fn error_exit(error: &VerifyError, args: &Args) -> ExitCode {
+    print_error(error, args);
+    ExitCode::from(error.exit_code() as u8)
+}
+
+/// Success, or the --warnings-as-errors exit code if `result` raised warnings.
+fn warnings_exit(result: &VerificationResult, args: &Args) -> ExitCode {
+    match warnings_error(result, args) {
+        Some(e) => ExitCode::from(e.exit_code() as u8),
+        None => ExitCode::SUCCESS,
+    }
+}
+
+/// With --explain-failure, diagnostics for a signature failure. None for
+/// other failures, or if the manifest can't be read.
+fn explain_failure(error: &VerifyError, args: &Args) -> Option<SignatureDiagnosis> {
+    if !args.explain_failure || !matches!(error, VerifyError::SignatureInvalid) {
+        return None;
+    }
+    diagnose_signature(&unverified_manifest_bytes(args).ok()?).ok()
+}
+
+/// `args` re-parsed from `argv` with profile `name`'s flags added.
+fn with_profile(argv: Vec<OsString>, args: &Args, name: &str) -> Result<Args, VerifyError> {
+    let config = args.config.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE));
+    args_with_profile(argv, &load_profile(&config, name)?)
+}
+
+/// Parse `argv` with `profile`'s flags appended, except those `argv` sets
+/// itself, so explicit flags override the profile.
+fn args_with_profile(mut argv: Vec<OsString>, profile: &[(String, ProfileValue)]) -> Result<Args, VerifyError> {
+    let command = Args::command();
+    let matches = command.clone().try_get_matches_from(&argv).map_err(profile_error)?;
+
+    for (flag, value) in profile {
+        let arg = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(flag.as_str()) && !matches!(flag.as_str(), "profile" | "config"))
+            .ok_or_else(|| VerifyError::ConfigInvalid {
+                message: format!("Profile sets unknown option {:?}", flag),
+            })?;
+        if matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine) {
+            continue;
+        }
+
+        let long = OsString::from(format!("--{}", flag));
+        match value {
+            ProfileValue::Flag(true) => argv.push(long),
+            ProfileValue::Flag(false) => {}
+            ProfileValue::Value(value) => argv.extend([long, value.into()]),
+            ProfileValue::List(items) => {
+                for item in items {
+                    argv.extend([long.clone(), item.into()]);
+                }
+            }
+        }
+    }
+
+    Args::try_parse_from(argv).map_err(profile_error)
+}
+
+fn profile_error(error: clap::Error) -> VerifyError {
+    let rendered = error.to_string();
+    let first_line = rendered.lines().next().unwrap_or_default();
+    VerifyError::ConfigInvalid {
+        message: format!("Profile: {}", first_line.trim_start_matches("error: ")),
+    }
+}
+
+fn run(args: &Args) -> Result<Outcome, VerifyError> {
+    // Prompt up front so the time spent typing isn't counted
+    let args = &*with_password(args)?;
+    let started = Instant::now();
+
+    // Load the operator key before verifying so a bad key fails fast
+    let signing_key = args.sign_receipt.as_deref().map(load_signing_key).transpose()?;
+    let checked_receipt = match (&args.verify_receipt, &args.receipt_key) {
+        (Some(path), Some(key)) => Some((VerificationReceipt::load(path)?, load_operator_public_key(key)?)),
+        _ => None,
+    };
+
+    let (mut result, extraction) = match verify_and_extract(args) {
+        Ok(verified) => verified,
+        Err(e) => {
+            if let Some(path) = &args.certificate {
+                let manifest_bytes = failed_manifest_bytes(args);
+                write_certificate(path, VerificationReport::from_error(&e, manifest_bytes.as_deref()), args)?;
+            }
+            return Err(e);
+        }
+    };
+
+    let checked_receipt = checked_receipt
+        .map(|(receipt, key)| receipt.verify_for(&key, &result).map(|()| receipt))
+        .transpose()?;
+
+    // Only sealed verifications report sealing; a script passing a password
+    // otherwise likely expects a sealed bundle it didn't get
+    if args.password.is_some() && result.sealing.is_none() {
+        result.warnings.push(Warning::PasswordUnused);
+    }
+
+    if let Some(path) = &args.geojson_out {
+        let collection = serde_json::to_string_pretty(&geojson::feature_collection(&result, args.redact))?;
+        fs::write(path, collection + "\n")?;
+    }
+
+    if let Some(path) = &args.certificate {
+        write_certificate(path, VerificationReport::from_result(&result), args)?;
+    }
+
+    let receipt = signing_key
+        .map(|key| VerificationReceipt::issue(&result, &key, &SystemClock))
+        .transpose()?;
+
+    Ok(Outcome {
+        result,
+        receipt,
+        checked_receipt,
+        extraction,
+        elapsed: started.elapsed(),
+    })
+}
+
+/// Write `report` as the --certificate, redacted with --redact.
+fn write_certificate(path: &Path, mut report: VerificationReport, args: &Args) -> Result<(), VerifyError> {
+    if args.redact {
+        report.redact();
+    }
+    let issued_at = SystemClock.now().format(&Rfc3339).unwrap_or_default();
+    fs::write(path, render_certificate(&report, &issued_at))?;
+    Ok(())
+}
+
+/// The manifest of a failed verification's input, if it can still be read,
+/// so its report describes the recording.
+fn failed_manifest_bytes(args: &Args) -> Option<Vec<u8>> {
+    if let Some(manifest) = &args.manifest_b64 {
+        return decode_base64(manifest).ok();
+    }
+    if let Some(manifest) = &args.manifest {
+        return fs::read(manifest).ok();
+    }
+    load_manifest_bytes(args.path.as_deref()?, args.password.as_deref()).ok()
+}
+
+/// `args` with the sealed bundle password filled in, prompting if needed.
+fn with_password(args: &Args) -> Result<Cow<'_, Args>, VerifyError> {
+    let sealed = args.path.as_deref().is_some_and(|path| detect_bundle_type(path) == BundleType::Sealed);
+    #[cfg(feature = "remote")]
+    let sealed = sealed || args
+        .path
+        .as_deref()
+        .and_then(Path::to_str)
+        .is_some_and(|path| remote::is_url(path) && remote::is_sealed_url(path));
+    if args.password.is_none() && sealed {
+        Ok(Cow::Owned(Args {
+            password: Some(prompt_password()?),
+            ..args.clone()
+        }))
+    } else {
+        Ok(Cow::Borrowed(args))
+    }
+}
+
+/// Latency statistics from a --benchmark run.
+#[derive(Debug)]
+struct BenchmarkStats {
+    iterations: usize,
+    mean: Duration,
+    median: Duration,
+    p99: Duration,
+    /// Verifications per second over the whole run.
+    throughput: f64,
+}
+
+/// Verify the bundle `iterations` times through the full path, file reads
+/// and key derivation included, stopping at the first failure.
+fn benchmark(args: &Args, iterations: u32) -> Result<BenchmarkStats, VerifyError> {
+    let args = &*with_password(args)?;
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    let started = Instant::now();
+    for _ in 0..iterations {
+        let sample_started = Instant::now();
+        verify(args)?;
+        samples.push(sample_started.elapsed());
+    }
+    Ok(benchmark_stats(samples, started.elapsed()))
+}
+
+fn benchmark_stats(mut samples: Vec<Duration>, total: Duration) -> BenchmarkStats {
+    samples.sort();
+    let n = samples.len();
+    let median = if n.is_multiple_of(2) {
+        (samples[n / 2 - 1] + samples[n / 2]) / 2
+    } else {
+        samples[n / 2]
+    };
+    // Nearest-rank percentile
+    let p99 = samples[(n * 99).div_ceil(100) - 1];
+
+    BenchmarkStats {
+        iterations: n,
+        mean: samples.iter().sum::<Duration>() / n as u32,
+        median,
+        p99,
+        throughput: n as f64 / total.as_secs_f64(),
+    }
+}
+
+fn print_benchmark(stats: &BenchmarkStats, args: &Args) {
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+    if args.format() == OutputFormat::Json {
+        let json = serde_json::json!({
+            "iterations": stats.iterations,
+            "meanMs": ms(stats.mean),
+            "medianMs": ms(stats.median),
+            "p99Ms": ms(stats.p99),
+            "throughputPerSecond": stats.throughput,
+        });
+        println!("{}", render_json(&json, args.compact(), args.json_case));
+    } else {
+        println!("Iterations:  {}", stats.iterations);
+        println!("Mean:        {:.2} ms", ms(stats.mean));
+        println!("Median:      {:.2} ms", ms(stats.median));
+        println!("p99:         {:.2} ms", ms(stats.p99));
+        println!("Throughput:  {:.1} verifications/s", stats.throughput);
+    }
+}
+
+/// Append the outcome to the --audit-log file, if one was given.
+fn audit(args: &Args, outcome: Result<&VerificationResult, &VerifyError>) -> Result<(), VerifyError> {
+    match &args.audit_log {
+        Some(log) => AuditLog::new(log).append(&AuditRecord::new(args.path()?, outcome, &SystemClock)),
+        None => Ok(()),
+    }
+}
+
+/// The error to exit with when --warnings-as-errors applies to `result`.
+fn warnings_error(result: &VerificationResult, args: &Args) -> Option<VerifyError> {
+    let count = result.warnings.len();
+    (args.warnings_as_errors && count > 0).then_some(VerifyError::WarningsPresent { count })
+}
+
+fn verify_options(args: &Args) -> VerifyOptions {
+    let options = VerifyOptions::new()
+        .with_required_vectors(args.require_vectors.iter().copied())
+        .with_full_context_required(args.require_full_context)
+        .with_duplicates_rejected(args.reject_duplicates)
+        .with_audio_refs_followed(args.follow_audio_ref)
+        .with_clock(args.clock())
+        .with_trust_policy(TrustPolicy {
+            max_interruption_seconds: args.max_interruption_seconds,
+        });
+    let options = match args.max_interruptions {
+        Some(max) => options.with_max_interruptions(max),
+        None => options,
+    };
+    let options = match args.max_age {
+        Some(max_age) => options.with_max_age(max_age),
+        None => options,
+    };
+    let options = options.with_read_retries(args.read_retries);
+    let options = args.trusted_cosigners.iter().fold(options, |options, key| options.with_trusted_cosigner(key.as_str()));
+    let options = match args.max_manifest_bytes {
+        Some(max) => options.with_max_manifest_bytes(max),
+        None => options,
+    };
+    let options = match args.allow_schema_version {
+        Some(version) => options.with_allowed_schema_version(version),
+        None => options,
+    };
+    match &args.seen_hashes {
+        Some(path) => options.with_hash_registry(HashRegistry::new(path)),
+        None => options,
+    }
+}
+
+fn verify(args: &Args) -> Result<VerificationResult, VerifyError> {
+    verify_and_extract(args).map(|(result, _)| result)
+}
+
+/// Verify, and with --extract also write out the sealed media and report what was written.
+fn verify_and_extract(args: &Args) -> Result<(VerificationResult, Option<ExtractionReport>), VerifyError> {
+    let options = verify_options(args);
+    if let (Some(audio), Some(manifest)) = (&args.audio_b64, &args.manifest_b64) {
+        return verify_inline(audio, manifest, &options).map(|result| (result, None));
+    }
+    let path = args.path()?;
+
+    #[cfg(feature = "remote")]
+    if let Some(url) = path.to_str().filter(|p| remote::is_url(p)) {
+        return verify_remote(url, args, &options).map(|result| (result, None));
+    }
+
+    match detect_bundle_type(path) {
+        BundleType::Sealed => {
+            // Sealed bundle - requires password
+            let password = match &args.password {
+                Some(p) => p.clone(),
+                None => prompt_password()?,
+            };
+
+            if let Some(extract_dir) = &args.extract {
+                let result = verify_and_extract_sealed_bundle_with_options(path, &password, &options)?;
+
+                let destinations: Vec<PathBuf> = std::iter::once(&result.audio_filename)
+                    .chain(result.attachments.iter().map(|a| &a.filename))
+                    .map(|name| extract_dir.join(name))
+                    .collect();
+                check_overwrite(&destinations, args.assume_yes, confirm_overwrite)?;
+
+                prepare_extract_dir(extract_dir)?;
+
+                let mut written = Vec::new();
+                let mut write = |filename: &str, data: &[u8]| -> Result<PathBuf, VerifyError> {
+                    let path = write_extracted_file(extract_dir, filename, data)?;
+                    written.push(ExtractedFileReport {
+                        path: path.display().to_string(),
+                        size_bytes: data.len() as u64,
+                    });
+                    Ok(path)
+                };
+
+                let audio_path = write(&result.audio_filename, &result.audio_data)?;
+                eprintln!("Audio extracted to: {}", audio_path.display());
+
+                for attachment in &result.attachments {
+                    let attachment_path = write(&attachment.filename, &attachment.data)?;
+                    eprintln!("Attachment extracted to: {}", attachment_path.display());
+                }
+                let extraction = ExtractionReport { files: written };
+
+                Ok((result.into(), Some(extraction)))
+            } else {
+                verify_sealed_bundle_with_options(path, &password, &options).map(|result| (result, None))
+            }
+        }
+        BundleType::Open => {
+            // Open proof bundle - no password needed
+            if args.extract.is_some() {
+                eprintln!("Note: --extract only applies to sealed .proofcapture files.");
+                eprintln!("      Open bundles already contain unencrypted media.");
+            }
+            verify_open_bundle_with_options(path, &options).map(|result| (result, None))
+        }
+        BundleType::Archive => {
+            if args.extract.is_some() {
+                eprintln!("Note: --extract only applies to sealed .proofcapture files.");
+                eprintln!("      Tar archives already contain the audio file.");
+            }
+            verify_archive_bundle_with_options(path, &options).map(|result| (result, None))
+        }
+        BundleType::Standard => {
+            // Standard bundle (directory or loose files)
+            if args.extract.is_some() {
+                eprintln!("Note: --extract only applies to sealed .proofcapture files.");
+                eprintln!("      Standard bundles already contain the audio file.");
+            }
+            if args.manifest_stdin {
+                return verify_piped_manifest(args, io::stdin().lock(), &options).map(|result| (result, None));
+            }
+            if args.audio.is_none() && args.manifest.is_none() {
+                // Also covers audio files carrying an embedded manifest
+                return verify_standard_bundle_with_options(path, &options).map(|result| (result, None));
+            }
+            let (audio, manifest) = resolve_standard_bundle(path, args.audio.as_deref(), args.manifest.as_deref())?;
+            verify_files(&audio, &manifest, &options).map(|result| (result, None))
+        }
+    }
+}
+
+/// Verify the audio at PATH (or --audio) against manifest bytes read from `input`.
+fn verify_piped_manifest(args: &Args, mut input: impl Read, options: &VerifyOptions) -> Result<VerificationResult, VerifyError> {
+    let mut manifest = Vec::new();
+    input.read_to_end(&mut manifest)?;
+
+    let audio_path = match &args.audio {
+        Some(audio) => audio.clone(),
+        None => resolve_audio_for_manifest(args.path()?, &manifest)?,
+    };
+    let audio = fs::read(&audio_path).map_err(|_| VerifyError::AudioFileMissing)?;
+    verify_audio_and_manifest_with_options(&audio, &manifest, options)
+}
+
+/// Verify base64 audio and manifest given on the command line.
+fn verify_inline(audio: &str, manifest: &str, options: &VerifyOptions) -> Result<VerificationResult, VerifyError> {
+    let audio = decode_base64(audio)?;
+    let manifest = decode_base64(manifest).map_err(|_| VerifyError::ManifestMalformed)?;
+    verify_audio_and_manifest_with_options(&audio, &manifest, options)
+}
+
+/// Verify a directory of bundles, exiting with the first failure's code.
+fn run_batch(args: &Args) -> Result<ExitCode, VerifyError> {
+    let bundles = discover_bundles(args.path()?)?;
+    let mut state = args.state_file.as_deref().map(BatchState::load).transpose()?;
+
+    let password = match &args.password {
+        Some(p) => Some(p.clone()),
+        None if needs_password(&bundles) => Some(prompt_password()?),
+        None => None,
+    };
+
+    let audit_log = args.audit_log.as_deref().map(AuditLog::new);
+
+    let extraction = args.output_dir.as_ref().map(|dir| BatchExtraction {
+        output_dir: dir.clone(),
+        overwrite: args.assume_yes,
+    });
+
+    let concurrency = match args.concurrency {
+        Some(n) => n as usize,
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+
+    let options = verify_options(args);
+    let entries = verify_batch(&bundles, password.as_deref(), &options, state.as_mut(), extraction.as_ref(), concurrency, |entry| {
+        // Skipped bundles weren't verified by this run
+        if let (Some(log), false) = (&audit_log, entry.skipped) {
+            let mut record = entry.record.clone();
+            // The batch only hashes bundles when there's a state file to look them up in
+            if record.content_hash.is_empty() {
+                record.content_hash = content_hash(&entry.path, &options).unwrap_or_default();
+            }
+            log.append(&AuditRecord::from_record(&entry.path, record, &SystemClock))?;
+        }
+        if !args.count {
+            print_batch_entry(entry, args);
+        }
+        Ok(())
+    })?;
+
+    if args.count {
+        let counts = BatchCounts::from_entries(&entries);
+        if args.format() == OutputFormat::Json {
+            println!("{}", render_json(&serde_json::json!(counts), args.compact(), args.json_case));
+        } else {
+            println!("{}", counts);
+        }
+    } else if args.format() == OutputFormat::Text && !args.list_vectors {
+        print_batch_summary(&entries);
+    }
+
+    let code = entries
+        .iter()
+        .map(|e| batch_exit_code(e, args))
+        .find(|&code| code != 0)
+        .unwrap_or(0);
+    Ok(ExitCode::from(code as u8))
+}
+
+/// Verify bundles as they finish arriving in PATH, until interrupted.
+///
+/// Bundles already there are verified first. A password can't be prompted
+/// for per bundle, so sealed bundles need --password or --password-env.
+fn run_watch(args: &Args) -> Result<ExitCode, VerifyError> {
+    let dir = args.path()?;
+    if args.password.is_none() && needs_password(&discover_bundles(dir)?) {
+        return Err(VerifyError::PasswordRequired);
+    }
+
+    // One tracker for the whole run, so nonces reused across arrivals are caught
+    let options = verify_options(args).with_nonce_tracker(Arc::new(NonceTracker::new()));
+    let mut watcher = DirectoryWatcher::new(dir, Duration::from_secs(args.settle_seconds));
+    eprintln!("Watching {} for bundles (Ctrl-C to stop)", dir.display());
+
+    loop {
+        for path in watcher.poll(Instant::now())? {
+            // A bundle removed before it could be read is reported and skipped
+            let entry = match verify_arrival(&path, args.password.as_deref(), &options) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    print_error(&e, args);
+                    continue;
+                }
+            };
+            print_batch_entry(&entry, args);
+            if args.move_results {
+                if let Err(e) = file_result(&path, batch_exit_code(&entry, args) == 0) {
+                    print_error(&e, args);
+                }
+            }
+        }
+        watcher.wait();
+    }
+}
+
+fn batch_exit_code(entry: &BatchEntry, args: &Args) -> i32 {
+    let record = &entry.record;
+    if record.is_verified() && args.warnings_as_errors && !record.warnings.is_empty() {
+        VerifyError::WarningsPresent { count: record.warnings.len() }.exit_code()
+    } else {
+        record.exit_code
+    }
+}
+
+/// Print what the signature covers: canonical keys and byte length.
+fn debug_canonical(args: &Args) -> Result<(), VerifyError> {
+    let summary = canonical_summary(&unverified_manifest_bytes(args)?)?;
+
+    if args.format() == OutputFormat::Json {
+        let json = serde_json::json!({
+            "canonicalKeys": summary.keys,
+            "canonicalLength": summary.length,
+        });
+        println!("{}", render_json(&json, args.compact(), args.json_case));
+    } else {
+        println!("Canonical keys:   {}", summary.keys.join(", "));
+        println!("Canonical length: {} bytes", summary.length);
+    }
+    Ok(())
+}
+
+/// The manifest of PATH (or --manifest), read without verifying it. Sealed
+/// bundles still need their password, prompting if it wasn't given.
+fn unverified_manifest_bytes(args: &Args) -> Result<Vec<u8>, VerifyError> {
+    if let Some(manifest) = &args.manifest_b64 {
+        return decode_base64(manifest).map_err(|_| VerifyError::ManifestMalformed);
+    }
+    let path = args.path()?;
+    let password = match &args.password {
+        Some(p) => Some(p.clone()),
+        None if detect_bundle_type(path) == BundleType::Sealed => Some(prompt_password()?),
+        None => None,
+    };
+
+    match &args.manifest {
+        Some(manifest) => fs::read(manifest).map_err(|_| VerifyError::ManifestMalformed),
+        None => load_manifest_bytes(path, password.as_deref()),
+    }
+}
+
+/// The parsed manifest as pretty-printed JSON.
+fn manifest_only_json(args: &Args) -> Result<String, VerifyError> {
+    let manifest = SignedAudioManifest::from_json(&unverified_manifest_bytes(args)?)?;
+    Ok(serde_json::to_string_pretty(&manifest)?)
+}
+
+/// What the audio container records, e.g. `12.0s, mp4a, 2 ch, 44100 Hz`.
+fn describe_audio_properties(detected: &AudioProperties) -> String {
+    let parts: Vec<String> = [
+        detected.duration_seconds.map(|seconds| format!("{:.1}s", seconds)),
+        detected.codec.clone(),
+        detected.channels.map(|channels| format!("{} ch", channels)),
+        detected.sample_rate_hz.map(|rate| format!("{} Hz", rate)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if parts.is_empty() {
+        "MP4, no audio properties recorded".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// The manifest's base64 public key and its hex SHA-256 fingerprint.
+fn public_key_info(args: &Args) -> Result<(String, String), VerifyError> {
+    let manifest = SignedAudioManifest::from_json(&unverified_manifest_bytes(args)?)?;
+    let key = parse_public_key(&decode_base64(&manifest.public_key)?)?;
+    Ok((manifest.public_key, hex_encode(&public_key_fingerprint(&key))))
+}
+
+fn print_public_key(public_key: &str, fingerprint: &str, args: &Args) {
+    if args.format() == OutputFormat::Json {
+        let json = serde_json::json!({
+            "publicKey": public_key,
+            "fingerprint": fingerprint,
+        });
+        println!("{}", render_json(&json, args.compact(), args.json_case));
+    } else {
+        println!("Public key:  {}", public_key);
+        println!("Fingerprint: {}", fingerprint);
+    }
+}
+
+/// Print the structural problems found by --lint.
+fn print_lint(problems: &[String], args: &Args) {
+    if args.format() == OutputFormat::Json {
+        let json = serde_json::json!({
+            "path": args.path_label().display().to_string(),
+            "problems": problems,
+        });
+        println!("{}", render_json(&json, args.compact(), args.json_case));
+    } else if problems.is_empty() {
+        println!("{}: no structural problems", args.path_label().display());
+    } else {
+        println!("{}: {} structural problem(s)", args.path_label().display(), problems.len());
+        for problem in problems {
+            println!("  - {}", problem);
+        }
+    }
+}
+
+/// Read the sealed bundle at PATH without decrypting it.
+fn inspect(args: &Args) -> Result<SealedProofBundle, VerifyError> {
+    let path = args.path()?;
+    if detect_bundle_type(path) != BundleType::Sealed {
+        eprintln!("Note: --inspect only applies to sealed .proofcapture files.");
+    }
+    inspect_sealed(&fs::read(path)?)
+}
+
+fn print_inspect(bundle: &SealedProofBundle, args: &Args) {
+    if args.format() == OutputFormat::Json {
+        // The same fields as the `sealing` object of a verification report
+        let mut json = serde_json::to_value(bundle.sealing_info()).unwrap();
+        json["path"] = args.path_label().display().to_string().into();
+        json["createdAt"] = bundle.created_at.clone().into();
+        println!("{}", render_json(&json, args.compact(), args.json_case));
+    } else {
+        let sealing = bundle.sealing_info();
+        println!("{}: sealed bundle v{}", args.path_label().display(), sealing.bundle_version);
+        println!("Created:     {}", bundle.created_at);
+        println!(
+            "KDF:         {} ({} iterations, {} KB memory, parallelism {})",
+            sealing.kdf_algorithm, sealing.iterations, sealing.memory_cost_kb, sealing.parallelism
+        );
+    }
+}
+
+#[cfg(feature = "remote")]
+fn verify_remote(url: &str, args: &Args, options: &VerifyOptions) -> Result<VerificationResult, VerifyError> {
+    if args.extract.is_some() {
+        eprintln!("Note: --extract is not supported for URLs.");
+    }
+
+    // A sealed URL's password was already asked for by with_password
+    remote::verify_url(url, args.password.as_deref(), remote::DEFAULT_MAX_DOWNLOAD_BYTES, options)
+}
+
+/// Fail unless every existing file in `destinations` may be overwritten.
+///
+/// With `assume_yes` nothing is asked; otherwise `confirm` decides for each
+/// existing file. All files are checked before any is written.
+fn check_overwrite(
+    destinations: &[PathBuf],
+    assume_yes: bool,
+    mut confirm: impl FnMut(&Path) -> bool,
+) -> Result<(), VerifyError> {
+    if assume_yes {
+        return Ok(());
+    }
+    match destinations.iter().find(|path| path.exists() && !confirm(path)) {
+        Some(path) => Err(VerifyError::OverwriteRefused { path: path.display().to_string() }),
+        None => Ok(()),
+    }
+}
+
+/// Ask on the terminal whether to overwrite `path`; refuses if stdin isn't a terminal.
+fn confirm_overwrite(path: &Path) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    eprint!("{} already exists. Overwrite? [y/N] ", path.display());
+    io::stderr().flush().ok();
 
-fn run(args: &Args) -> Result<VerificationResult, VerifyError> {
-    let path = &args.path;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).is_ok() && matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
+}
 
-    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+fn prompt_password() -> Result<String, VerifyError> {
+    let _interrupt = PromptInterruptGuard::install();
+    eprint!("Password: ");
+    io::stderr().flush().ok();
 
-    match ext {
-        "proofcapture" => {
-            // Sealed bundle - requires password
-            let password = match &args.password {
-                Some(p) => p.clone(),
-                None => prompt_password()?,
-            };
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .map_err(VerifyError::Io)?;
 
-            if let Some(extract_dir) = &args.extract {
-                let result = verify_and_extract_sealed_bundle(path, &password)?;
+    Ok(strip_line_ending(&line).to_string())
+}
 
-                fs::create_dir_all(extract_dir).map_err(|e| VerifyError::Io(e))?;
+/// Exit code after Ctrl-C at the password prompt: 128 + SIGINT, as shells report it.
+#[cfg(unix)]
+const INTERRUPTED_EXIT_CODE: i32 = 130;
 
-                let audio_path = extract_dir.join(&result.audio_filename);
-                fs::write(&audio_path, &result.audio_data).map_err(|e| VerifyError::Io(e))?;
+/// Terminal settings when the prompt started, restored on Ctrl-C.
+#[cfg(unix)]
+static PROMPT_TERMINAL: std::sync::OnceLock<libc::termios> = std::sync::OnceLock::new();
 
-                eprintln!("Audio extracted to: {}", audio_path.display());
+/// While alive, Ctrl-C ends the process cleanly instead of mid-prompt: the
+/// terminal settings from before the prompt are restored, the cursor moves
+/// to a fresh line and the exit code is [`INTERRUPTED_EXIT_CODE`]. Dropping
+/// the guard puts the previous SIGINT handler back.
+#[cfg(unix)]
+struct PromptInterruptGuard {
+    previous: libc::sighandler_t,
+}
 
-                Ok(VerificationResult {
-                    manifest: result.manifest,
-                    trust_level: result.trust_level,
-                })
-            } else {
-                verify_sealed_bundle(path, &password)
+#[cfg(unix)]
+impl PromptInterruptGuard {
+    fn install() -> Self {
+        extern "C" fn on_interrupt(_: libc::c_int) {
+            // Only async-signal-safe calls from here on
+            unsafe {
+                if let Some(settings) = PROMPT_TERMINAL.get() {
+                    libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, settings);
+                }
+                libc::write(libc::STDERR_FILENO, b"\n".as_ptr().cast(), 1);
+                libc::_exit(INTERRUPTED_EXIT_CODE);
             }
         }
-        "proofbundle" => {
-            // Open proof bundle - no password needed
-            if args.extract.is_some() {
-                eprintln!("Note: --extract only applies to sealed .proofcapture files.");
-                eprintln!("      Open bundles already contain unencrypted media.");
+
+        // SAFETY: termios is plain data, filled in by tcgetattr before use
+        unsafe {
+            let mut settings: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut settings) == 0 {
+                let _ = PROMPT_TERMINAL.set(settings);
             }
-            verify_open_bundle(path)
         }
-        _ => {
-            // Standard bundle (directory or loose files)
-            if args.extract.is_some() {
-                eprintln!("Note: --extract only applies to sealed .proofcapture files.");
-                eprintln!("      Standard bundles already contain the audio file.");
-            }
-            verify_standard_bundle(path)
+        let handler = on_interrupt as extern "C" fn(libc::c_int);
+        // SAFETY: the handler only makes async-signal-safe calls
+        let previous = unsafe { libc::signal(libc::SIGINT, handler as libc::sighandler_t) };
+        PromptInterruptGuard { previous }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for PromptInterruptGuard {
+    fn drop(&mut self) {
+        // SAFETY: restores whatever handler was installed before
+        unsafe {
+            libc::signal(libc::SIGINT, self.previous);
         }
     }
 }
 
-fn prompt_password() -> Result<String, VerifyError> {
-    eprint!("Password: ");
-    io::stderr().flush().ok();
+/// Ctrl-C already leaves the console usable outside Unix; nothing to do.
+#[cfg(not(unix))]
+struct PromptInterruptGuard;
 
-    let mut password = String::new();
-    io::stdin()
-        .read_line(&mut password)
-        .map_err(|e| VerifyError::Io(e))?;
+#[cfg(not(unix))]
+impl PromptInterruptGuard {
+    fn install() -> Self {
+        PromptInterruptGuard
+    }
+}
 
-    Ok(password.trim().to_string())
+/// Remove the trailing newline from a line read from stdin.
+///
+/// Only the line ending is stripped; spaces are part of the password.
+fn strip_line_ending(line: &str) -> &str {
+    line.strip_suffix('\n')
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .unwrap_or(line)
 }
 
-fn print_success(result: &VerificationResult, args: &Args) {
-    if args.format == OutputFormat::Json {
-        print_success_json(result);
+fn print_success(outcome: &Outcome, args: &Args) {
+    if args.format() == OutputFormat::Json {
+        print_success_json(outcome, args);
     } else {
-        print_success_text(result, args.verbose);
+        print_success_text(outcome, args);
     }
 }
 
-fn print_success_text(result: &VerificationResult, verbose: bool) {
+fn print_success_text(outcome: &Outcome, args: &Args) {
+    let result = &outcome.result;
+    let units = args.units;
+    let catalog = args.catalog();
     let reset = "\x1b[0m";
     let green = "\x1b[32m";
+    let yellow = "\x1b[33m";
     let bold = "\x1b[1m";
     let level_color = result.trust_level.color_code();
 
     println!();
     println!("{}PROOFAUDIO VERIFICATION SUMMARY{}", bold, reset);
     println!("===============================");
-    println!(
-        "Status:      {}{}VERIFIED{}",
-        bold, green, reset
-    );
+    if args.warnings_as_errors && !result.warnings.is_empty() {
+        println!("Status:      {}{}VERIFIED WITH WARNINGS{}", bold, yellow, reset);
+    } else {
+        println!(
+            "Status:      {}{}VERIFIED{}",
+            bold, green, reset
+        );
+    }
     println!(
         "Trust Level: {}{} ({}){}",
         level_color,
@@ -172,17 +1508,39 @@ fn print_success_text(result: &VerificationResult, verbose: bool) {
     println!("Captured:    {}", m.capture_start);
     println!("Duration:    {:.1}s", m.duration_seconds);
     println!("Format:      {} (M4A container)", m.audio_format.to_uppercase());
-    println!("Size:        {} bytes", m.audio_size_bytes);
+    println!("Size:        {}", format_size(m.audio_size_bytes, units));
+    if let Some(detected) = &result.audio_properties {
+        println!("Container:   {}", describe_audio_properties(detected));
+    }
 
-    if verbose {
+    if args.verbose {
         println!("Audio Hash:  {}", m.audio_hash);
+        println!("Manifest:    {}", hex_encode(&result.manifest_hash));
     }
 
     println!();
     println!("{}CRYPTOGRAPHIC IDENTITY{}", bold, reset);
     println!("----------------------");
-    println!("Device Key:  {}...", &m.device_key_id[..20.min(m.device_key_id.len())]);
+    if args.redact {
+        println!("Device Key:  {}", redact_key_id(&m.device_key_id));
+    } else {
+        println!("Device Key:  {}...", &m.device_key_id[..20.min(m.device_key_id.len())]);
+    }
     println!("App:         {} v{}", m.app_bundle_id, m.app_version);
+    for co_signer in &result.co_signers {
+        let status = match (co_signer.valid, co_signer.trusted) {
+            (true, true) => "verified",
+            (true, false) => "valid signature by an unpinned key",
+            (false, _) => "INVALID",
+        };
+        println!("Co-signer:   {} ({})", co_signer.role, status);
+    }
+    if let Some(sealing) = &result.sealing {
+        println!(
+            "Sealing:     {} ({} iterations, {} KB memory, parallelism {}), bundle v{}",
+            sealing.kdf_algorithm, sealing.iterations, sealing.memory_cost_kb, sealing.parallelism, sealing.bundle_version
+        );
+    }
 
     // Trust vectors
     println!();
@@ -191,8 +1549,24 @@ fn print_success_text(result: &VerificationResult, verbose: bool) {
 
     if let Some(loc) = &m.trust_vectors.location {
         println!("Location:");
-        println!("  Start:     {:.6}, {:.6} (±{:.0}m)", loc.start.lat, loc.start.lon, loc.start.accuracy);
-        println!("  End:       {:.6}, {:.6} (±{:.0}m)", loc.end.lat, loc.end.lon, loc.end.accuracy);
+        let accuracy = |meters| format_distance(meters, units);
+        let (precision, coordinate): (usize, fn(f64) -> f64) = if args.redact {
+            (REDACTED_COORDINATE_DECIMALS as usize, redact_coordinate)
+        } else {
+            (6, |value| value)
+        };
+        for (label, snapshot) in [("Start:", &loc.start), ("End:", &loc.end)] {
+            println!(
+                "  {:<10} {:.*}, {:.*} (±{})",
+                label,
+                precision,
+                coordinate(snapshot.lat),
+                precision,
+                coordinate(snapshot.lon),
+                accuracy(snapshot.accuracy)
+            );
+        }
+        println!("  Moved:     {}", format_distance(distance_meters(&loc.start, &loc.end), units));
     } else {
         println!("Location:    Not captured");
     }
@@ -222,7 +1596,13 @@ fn print_success_text(result: &VerificationResult, verbose: bool) {
         if !cont.interruption_events.is_empty() {
             println!("  Events:");
             for event in &cont.interruption_events {
-                println!("    - {}: {}", event.timestamp, event.reason);
+                match event.duration_seconds {
+                    Some(secs) => println!("    - {}: {} ({:.1}s)", event.timestamp, event.reason, secs),
+                    None => println!("    - {}: {}", event.timestamp, event.reason),
+                }
+            }
+            if let Some(total) = cont.total_interruption_seconds() {
+                println!("  Total:     {:.1}s", total);
             }
         }
     } else {
@@ -239,87 +1619,161 @@ fn print_success_text(result: &VerificationResult, verbose: bool) {
         println!("Clock:       Not captured");
     }
 
+    if !result.warnings.is_empty() {
+        println!();
+        println!("{}WARNINGS{}", bold, reset);
+        println!("--------");
+        for warning in &result.warnings {
+            println!("{}- {}{}", yellow, warning, reset);
+        }
+    }
+
     // Limitations
     println!();
-    println!("{}LIMITATIONS{}", bold, reset);
-    println!("-----------");
-    println!("This verification proves capture integrity, NOT:");
-    println!("- Who is speaking");
-    println!("- That statements are true");
-    println!("- Legal consent to record");
-    println!("- Absence of AI-generated audio");
+    let heading = catalog.get(MessageKey::LimitationsHeading);
+    println!("{}{}{}", bold, heading, reset);
+    println!("{}", "-".repeat(heading.chars().count()));
+    println!("{}", catalog.get(MessageKey::LimitationsIntro));
+    for limitation in catalog.limitations() {
+        println!("- {}", limitation);
+    }
     println!();
+
+    if let Some(receipt) = &outcome.checked_receipt {
+        println!("Receipt:     Matches, verified {} as {}", receipt.verified_at, receipt.trust_level);
+        println!();
+    }
+
+    if let Some(receipt) = &outcome.receipt {
+        println!("{}VERIFICATION RECEIPT{}", bold, reset);
+        println!("--------------------");
+        println!("{}", serde_json::to_string_pretty(receipt).unwrap());
+        println!();
+    }
+
+    println!("Verified in {:.2}s", outcome.elapsed.as_secs_f64());
 }
 
-fn print_success_json(result: &VerificationResult) {
-    let m = &result.manifest;
-    let json = serde_json::json!({
-        "status": "verified",
-        "trustLevel": result.trust_level.display_name(),
-        "trustLevelLabel": result.trust_level.label(),
-        "schemaVersion": m.schema_version,
-        "recording": {
-            "captureStart": m.capture_start,
-            "captureEnd": m.capture_end,
-            "durationSeconds": m.duration_seconds,
-            "audioFormat": m.audio_format,
-            "audioSizeBytes": m.audio_size_bytes,
-            "audioHash": m.audio_hash
-        },
-        "identity": {
-            "deviceKeyId": m.device_key_id,
-            "publicKey": m.public_key,
-            "appBundleId": m.app_bundle_id,
-            "appVersion": m.app_version
-        },
-        "trustVectors": {
-            "location": m.trust_vectors.location.as_ref().map(|l| serde_json::json!({
-                "start": {
-                    "lat": l.start.lat,
-                    "lon": l.start.lon,
-                    "accuracy": l.start.accuracy
-                },
-                "end": {
-                    "lat": l.end.lat,
-                    "lon": l.end.lon,
-                    "accuracy": l.end.accuracy
-                }
-            })),
-            "motion": m.trust_vectors.motion.as_ref().map(|mot| serde_json::json!({
-                "accelerationVariance": mot.acceleration_variance,
-                "rotationVariance": mot.rotation_variance,
-                "duration": mot.duration,
-                "sampleCount": mot.sample_count
-            })),
-            "continuity": m.trust_vectors.continuity.as_ref().map(|c| serde_json::json!({
-                "uninterrupted": c.uninterrupted,
-                "interruptionEvents": c.interruption_events.iter().map(|e| serde_json::json!({
-                    "timestamp": e.timestamp,
-                    "reason": e.reason
-                })).collect::<Vec<_>>()
-            })),
-            "clock": m.trust_vectors.clock.as_ref().map(|c| serde_json::json!({
-                "wallClockStart": c.wall_clock_start,
-                "wallClockEnd": c.wall_clock_end,
-                "monotonicDelta": c.monotonic_delta,
-                "timeZone": c.time_zone
-            }))
-        },
-        "signature": m.signature
-    });
+fn print_success_json(outcome: &Outcome, args: &Args) {
+    let json = success_json(outcome, args);
+    println!("{}", render_json(&json, args.compact(), args.json_case));
+}
+
+fn success_json(outcome: &Outcome, args: &Args) -> serde_json::Value {
+    let mut report = VerificationReport::from_result(&outcome.result);
+    if args.redact {
+        report.redact();
+    }
+    if args.include_raw_manifest {
+        report.raw_manifest_base64 = Some(BASE64.encode(&outcome.result.manifest_bytes));
+    }
+    report.receipt = outcome.receipt.clone();
+    report.checked_receipt = outcome.checked_receipt.clone();
+    report.extraction = outcome.extraction.clone();
+    report.verification_duration_ms = Some(outcome.elapsed.as_millis() as u64);
+    if args.warnings_as_errors && !report.warnings.is_empty() {
+        report.status = "verified_with_warnings".to_string();
+    }
+
+    // Go through Value so keys are emitted in sorted order
+    serde_json::to_value(&report).unwrap()
+}
+
+fn print_batch_entry(entry: &BatchEntry, args: &Args) {
+    let record = &entry.record;
+
+    if args.list_vectors {
+        let line = match &record.trust_level {
+            Some(level) => {
+                let present: Vec<TrustVector> = record.vectors.iter().filter_map(|v| v.parse().ok()).collect();
+                vectors_line(&entry.path, level.strip_prefix("Level ").unwrap_or(level), &present)
+            }
+            None => failed_line(&entry.path, record.error.as_deref().unwrap_or("")),
+        };
+        println!("{}", line);
+        return;
+    }
+
+    if args.format() == OutputFormat::Json {
+        // One JSON object per line, whatever --compact says
+        let json = serde_json::json!({
+            "path": entry.path.display().to_string(),
+            "status": record.status,
+            "trustLevel": record.trust_level,
+            "manifestHash": record.manifest_hash,
+            "error": record.error,
+            "exitCode": batch_exit_code(entry, args),
+            "warnings": record.warnings,
+            "bundleVersion": record.bundle_version,
+            "skipped": entry.skipped,
+        });
+        println!("{}", render_json(&json, true, args.json_case));
+        return;
+    }
+
+    let reset = "\x1b[0m";
+    let (color, status) = if record.is_verified() {
+        ("\x1b[32m", "VERIFIED")
+    } else {
+        ("\x1b[31m", "FAILED  ")
+    };
+    let detail = record
+        .trust_level
+        .clone()
+        .or_else(|| record.error.clone())
+        .unwrap_or_default();
+    let warnings = if record.warnings.is_empty() {
+        String::new()
+    } else {
+        format!(" [warnings: {}]", record.warnings.join(", "))
+    };
+    let skipped = if entry.skipped { " (from state file)" } else { "" };
+
+    println!(
+        "{}{}{}  {}  {}{}{}",
+        color,
+        status,
+        reset,
+        entry.path.display(),
+        detail,
+        warnings,
+        skipped
+    );
+}
+
+/// `--list-vectors` line for a verified bundle, e.g. `a.proofcapture: A [loc,motion,cont,clock]`.
+fn vectors_line(path: &Path, level: &str, present: &[TrustVector]) -> String {
+    format!("{}: {} {}", path.display(), level, vector_tag(present))
+}
+
+/// `--list-vectors` line for a bundle that failed verification.
+fn failed_line(path: &Path, error: &str) -> String {
+    format!("{}: FAILED ({})", path.display(), error)
+}
+
+fn print_batch_summary(entries: &[BatchEntry]) {
+    let verified = entries.iter().filter(|e| e.record.is_verified()).count();
+    let skipped = entries.iter().filter(|e| e.skipped).count();
 
-    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    println!();
+    println!(
+        "{} bundles: {} verified, {} failed, {} skipped",
+        entries.len(),
+        verified,
+        entries.len() - verified,
+        skipped
+    );
 }
 
 fn print_error(error: &VerifyError, args: &Args) {
-    if args.format == OutputFormat::Json {
-        print_error_json(error);
+    if args.format() == OutputFormat::Json {
+        print_error_json(error, args.compact(), args.json_case);
     } else {
-        print_error_text(error);
+        print_error_text(error, &args.catalog());
     }
 }
 
-fn print_error_text(error: &VerifyError) {
+fn print_error_text(error: &VerifyError, catalog: &Catalog) {
     let reset = "\x1b[0m";
     let red = "\x1b[31m";
     let bold = "\x1b[1m";
@@ -331,31 +1785,670 @@ fn print_error_text(error: &VerifyError) {
     eprintln!("Error:       {}", error);
     eprintln!();
 
-    match error {
-        VerifyError::HashMismatch => {
-            eprintln!("The audio file does not match the cryptographic hash");
-            eprintln!("recorded at capture time. This recording cannot be");
-            eprintln!("verified as authentic.");
-        }
-        VerifyError::SignatureInvalid => {
-            eprintln!("The digital signature is invalid. The manifest may have");
-            eprintln!("been tampered with or was not created by ProofCapture.");
-        }
-        VerifyError::DecryptionFailed => {
-            eprintln!("Could not decrypt the sealed proof. Please check your");
-            eprintln!("password and try again.");
-        }
-        _ => {}
+    let detail = match error {
+        VerifyError::HashMismatch => Some(MessageKey::HashMismatchDetail),
+        VerifyError::SignatureInvalid => Some(MessageKey::SignatureInvalidDetail),
+        VerifyError::DecryptionFailed => Some(MessageKey::DecryptionFailedDetail),
+        _ => None,
+    };
+    if let Some(key) = detail {
+        eprintln!("{}", catalog.get(key));
     }
     eprintln!();
 }
 
-fn print_error_json(error: &VerifyError) {
-    let json = serde_json::json!({
+fn print_error_json(error: &VerifyError, compact: bool, case: JsonCase) {
+    println!("{}", render_json(&error_json(error), compact, case));
+}
+
+fn error_json(error: &VerifyError) -> serde_json::Value {
+    serde_json::json!({
         "status": "failed",
         "error": error.to_string(),
         "exitCode": error.exit_code()
-    });
+    })
+}
+
+fn print_error_with_diagnosis(error: &VerifyError, diagnosis: &SignatureDiagnosis, args: &Args) {
+    if args.format() == OutputFormat::Json {
+        let mut json = error_json(error);
+        json["diagnosis"] = serde_json::json!({
+            "checks": diagnosis.checks,
+            "likelyCause": diagnosis.cause.code(),
+            "hint": diagnosis.cause.to_string(),
+        });
+        println!("{}", render_json(&json, args.compact(), args.json_case));
+        return;
+    }
+
+    print_error_text(error, &args.catalog());
+    eprintln!("Signature diagnostics:");
+    for check in &diagnosis.checks {
+        eprintln!("  {:<4} {} ({})", if check.passed { "yes" } else { "no" }, check.name, check.detail);
+    }
+    eprintln!();
+    eprintln!("Likely cause: {}", diagnosis.cause);
+}
+
+fn render_json(json: &serde_json::Value, compact: bool, case: JsonCase) -> String {
+    let json = case.apply(json.clone());
+    if compact {
+        serde_json::to_string(&json).unwrap()
+    } else {
+        serde_json::to_string_pretty(&json).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_json_compact_and_pretty() {
+        let json = serde_json::json!({"status": "verified", "recording": {"durationSeconds": 5.0}});
+
+        let compact = render_json(&json, true, JsonCase::Camel);
+        let pretty = render_json(&json, false, JsonCase::Camel);
+
+        assert_eq!(compact, r#"{"recording":{"durationSeconds":5.0},"status":"verified"}"#);
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains('\n'));
+        assert!(pretty.len() > compact.len());
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&pretty).unwrap(), json);
+
+        let snake = render_json(&json, true, JsonCase::Snake);
+        assert_eq!(snake, r#"{"recording":{"duration_seconds":5.0},"status":"verified"}"#);
+    }
+
+    #[test]
+    fn test_strip_line_ending_keeps_spaces() {
+        assert_eq!(strip_line_ending("correct horse battery\n"), "correct horse battery");
+        assert_eq!(strip_line_ending(" padded secret \r\n"), " padded secret ");
+        assert_eq!(strip_line_ending("no newline"), "no newline");
+        assert_eq!(strip_line_ending("   \n"), "   ");
+    }
+
+    #[test]
+    fn test_json_reports_verification_duration() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/minimal_bundle");
+        let args = Args::parse_from(["proofcapture-cli", fixture, "--format", "json"]);
+        let outcome = run(&args).unwrap();
+
+        let json = success_json(&outcome, &args);
+        let duration = json["verificationDurationMs"].as_u64().expect("duration present and non-negative");
+        assert_eq!(duration, outcome.elapsed.as_millis() as u64);
+    }
+
+    #[test]
+    fn test_verify_receipt_matching_and_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let operator = p256::ecdsa::SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let key_file = dir.path().join("operator.pub");
+        let public_key = proofcapture_cli::crypto::raw_public_key(operator.verifying_key());
+        fs::write(&key_file, BASE64.encode(public_key)).unwrap();
+
+        let result = proofcapture_cli::verify_standard_bundle(&fixtures.join("minimal_bundle")).unwrap();
+        let receipt = VerificationReceipt::issue_at(&result, &operator, "2026-02-01T12:00:00Z").unwrap();
+        let receipt_file = dir.path().join("receipt.json");
+        fs::write(&receipt_file, serde_json::to_vec(&receipt).unwrap()).unwrap();
+
+        let args_for = |bundle: &str| {
+            Args::parse_from([
+                "proofcapture-cli",
+                fixtures.join(bundle).to_str().unwrap(),
+                "--verify-receipt",
+                receipt_file.to_str().unwrap(),
+                "--receipt-key",
+                key_file.to_str().unwrap(),
+            ])
+        };
+        let outcome = run(&args_for("minimal_bundle")).unwrap();
+        assert_eq!(outcome.checked_receipt, Some(receipt));
+
+        let stale = run(&args_for("full_bundle")).err().unwrap();
+        assert!(matches!(stale, VerifyError::ReceiptStale { .. }));
+        assert_eq!(stale.exit_code(), 31);
+    }
+
+    #[test]
+    fn test_json_redaction() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/full_bundle");
+        let args = Args::parse_from(["proofcapture-cli", fixture, "--redact"]);
+        let outcome = run(&args).unwrap();
+        let public_key = &outcome.result.manifest.public_key;
+
+        let redacted = success_json(&outcome, &args).to_string();
+        assert!(!redacted.contains(public_key.as_str()));
+        assert!(!redacted.contains("37.775"));
+        let plain = Args::parse_from(["proofcapture-cli", fixture]);
+        assert!(success_json(&outcome, &plain).to_string().contains(public_key.as_str()));
+    }
+
+    #[test]
+    fn test_verify_inline_base64() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/minimal_bundle");
+        let audio = BASE64.encode(fs::read(dir.join("recording.m4a")).unwrap());
+        let manifest = BASE64.encode(fs::read(dir.join("manifest.json")).unwrap());
+
+        let args = Args::parse_from(["proofcapture-cli", "--audio-b64", &audio, "--manifest-b64", &manifest]);
+        assert_eq!(verify(&args).unwrap().trust_level.letter(), "C");
+        // Anything that reads PATH fails cleanly rather than panicking
+        assert!(matches!(args.path(), Err(VerifyError::PathRequired)));
+        assert!(matches!(run_batch(&args), Err(VerifyError::PathRequired)));
+
+        let args = Args::parse_from(["proofcapture-cli", "--audio-b64", "not base64!", "--manifest-b64", &manifest]);
+        assert!(matches!(verify(&args), Err(VerifyError::Base64(_))));
+        let args = Args::parse_from(["proofcapture-cli", "--audio-b64", &audio, "--manifest-b64", "not base64!"]);
+        assert!(matches!(verify(&args), Err(VerifyError::ManifestMalformed)));
+
+        assert!(Args::try_parse_from(["proofcapture-cli", "--audio-b64", &audio]).is_err());
+        assert!(Args::try_parse_from(["proofcapture-cli", "./bundle", "--audio-b64", &audio, "--manifest-b64", &manifest]).is_err());
+        // Nothing to watch without a PATH
+        let parsed = Args::try_parse_from(["proofcapture-cli", "--audio-b64", &audio, "--manifest-b64", &manifest, "--watch"]);
+        assert_eq!(parsed.unwrap_err().kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn test_explain_failure_with_inline_input() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/minimal_bundle");
+        let audio = BASE64.encode(fs::read(dir.join("recording.m4a")).unwrap());
+        let mut manifest: serde_json::Value = serde_json::from_slice(&fs::read(dir.join("manifest.json")).unwrap()).unwrap();
+        manifest["durationSeconds"] = 99.into();
+        let manifest = BASE64.encode(serde_json::to_vec(&manifest).unwrap());
+
+        let args = Args::parse_from(["proofcapture-cli", "--audio-b64", &audio, "--manifest-b64", &manifest, "--explain-failure"]);
+        let error = verify(&args).unwrap_err();
+        assert!(matches!(error, VerifyError::SignatureInvalid));
+        let diagnosis = explain_failure(&error, &args).unwrap();
+        assert_eq!(diagnosis.cause, proofcapture_cli::diagnose::FailureCause::ModifiedOrWrongKey);
+    }
+
+    #[test]
+    fn test_json_raw_manifest_round_trips() {
+        for manifest in ["full_bundle/manifest.json", "gzip_bundle/manifest.json.gz"] {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(manifest);
+            let bundle = path.parent().unwrap().to_str().unwrap();
+            let args = Args::parse_from(["proofcapture-cli", bundle, "--include-raw-manifest"]);
+            let json = success_json(&run(&args).unwrap(), &args);
+
+            let raw = BASE64.decode(json["rawManifestBase64"].as_str().unwrap()).unwrap();
+            assert_eq!(raw, fs::read(&path).unwrap(), "{manifest}");
+        }
+
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/minimal_bundle");
+        let args = Args::parse_from(["proofcapture-cli", fixture]);
+        assert!(success_json(&run(&args).unwrap(), &args).get("rawManifestBase64").is_none());
+    }
+
+    #[test]
+    fn test_json_reports_extracted_files() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/sealed_attachment_test.proofcapture");
+        let out = tempfile::tempdir().unwrap();
+        let out_dir = out.path().to_str().unwrap();
+        let args = Args::parse_from(["proofcapture-cli", fixture, "-p", "test-password-123", "-e", out_dir, "-f", "json"]);
+
+        let json = success_json(&run(&args).unwrap(), &args);
+        let files = json["extraction"]["files"].as_array().unwrap();
+        let audio_path = out.path().join("recording.m4a");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["path"], audio_path.display().to_string());
+        assert_eq!(files[0]["sizeBytes"], fs::metadata(&audio_path).unwrap().len());
+        assert_eq!(files[1]["path"], out.path().join("scene.jpg").display().to_string());
+
+        let plain_args = Args::parse_from(["proofcapture-cli", fixture, "-p", "test-password-123"]);
+        let plain = run(&plain_args).unwrap();
+        assert!(success_json(&plain, &plain_args).get("extraction").is_none());
+    }
+
+    #[test]
+    fn test_list_vectors_line_format() {
+        let full = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/full_bundle");
+        let result = verify(&Args::parse_from(["proofcapture-cli", full, "--list-vectors"])).unwrap();
+        let present = TrustVector::present(&result.manifest.trust_vectors);
+        assert_eq!(
+            vectors_line(Path::new("full_bundle"), result.trust_level.letter(), &present),
+            "full_bundle: A [loc,motion,cont,clock]"
+        );
+
+        assert_eq!(vectors_line(Path::new("minimal_bundle"), "C", &[]), "minimal_bundle: C []");
+        assert_eq!(
+            failed_line(Path::new("bad.proofcapture"), "Signature verification failed"),
+            "bad.proofcapture: FAILED (Signature verification failed)"
+        );
+    }
+
+    #[test]
+    fn test_benchmark_smoke() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/minimal_bundle");
+        let args = Args::parse_from(["proofcapture-cli", fixture, "--benchmark", "3"]);
+
+        let stats = benchmark(&args, args.benchmark.unwrap()).unwrap();
+        assert_eq!(stats.iterations, 3);
+        assert!(stats.median <= stats.p99);
+        assert!(stats.throughput > 0.0);
+    }
+
+    #[test]
+    fn test_benchmark_stats_percentiles() {
+        let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let stats = benchmark_stats(samples, Duration::from_secs(2));
+
+        assert_eq!(stats.mean, Duration::from_micros(50_500));
+        assert_eq!(stats.median, Duration::from_micros(50_500));
+        assert_eq!(stats.p99, Duration::from_millis(99));
+        assert_eq!(stats.throughput, 50.0);
+    }
+
+    #[test]
+    fn test_warnings_as_errors_with_skewed_fixture() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/skewed_bundle");
+
+        let lenient = Args::parse_from(["proofcapture-cli", fixture]);
+        let result = verify(&lenient).unwrap();
+        assert_eq!(result.warnings.len(), 2);
+        assert!(warnings_error(&result, &lenient).is_none());
+
+        let strict = Args::parse_from(["proofcapture-cli", fixture, "--warnings-as-errors"]);
+        let error = warnings_error(&result, &strict).unwrap();
+        assert!(matches!(error, VerifyError::WarningsPresent { count: 2 }));
+        assert_eq!(error.exit_code(), 16);
+    }
+
+    #[test]
+    fn test_password_for_unsealed_bundle_warns() {
+        use std::io::Write;
+
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/minimal_bundle");
+        let dir = tempfile::tempdir().unwrap();
+        let open = dir.path().join("evidence.proofbundle");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&open).unwrap());
+        for name in ["manifest.json", "recording.m4a"] {
+            zip.start_file(name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(&fs::read(Path::new(fixture).join(name)).unwrap()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        for path in [fixture, open.to_str().unwrap()] {
+            let args = Args::parse_from(["proofcapture-cli", path, "-p", "shared-secret", "--strict"]);
+            let result = run(&args).unwrap().result;
+            assert_eq!(result.warnings, [Warning::PasswordUnused]);
+            assert!(matches!(warnings_error(&result, &args), Some(VerifyError::WarningsPresent { count: 1 })));
+
+            let without = run(&Args::parse_from(["proofcapture-cli", path])).unwrap().result;
+            assert!(without.warnings.is_empty());
+        }
+
+        let sealed = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/sealed_test.proofcapture");
+        let args = Args::parse_from(["proofcapture-cli", sealed, "-p", "test-password-123"]);
+        assert!(!run(&args).unwrap().result.warnings.contains(&Warning::PasswordUnused));
+    }
+
+    #[test]
+    fn test_warnings_as_errors_ignores_clean_fixture() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/full_bundle");
+        let strict = Args::parse_from(["proofcapture-cli", fixture, "--warnings-as-errors"]);
+        assert!(warnings_error(&verify(&strict).unwrap(), &strict).is_none());
+    }
+
+    #[test]
+    fn test_format_metric_units() {
+        assert_eq!(format_distance(65.4, Units::Metric), "65 m");
+        assert_eq!(format_distance(1234.0, Units::Metric), "1.2 km");
+        assert_eq!(format_size(512, Units::Metric), "512 bytes");
+        assert_eq!(format_size(88_200, Units::Metric), "86.1 KiB (88200 bytes)");
+        assert_eq!(format_size(5 * 1024 * 1024, Units::Metric), "5.0 MiB (5242880 bytes)");
+    }
+
+    #[test]
+    fn test_format_imperial_units() {
+        assert_eq!(format_distance(65.0, Units::Imperial), "213 ft");
+        assert_eq!(format_distance(1609.344, Units::Imperial), "1.0 mi");
+        assert_eq!(format_size(999, Units::Imperial), "999 bytes");
+        assert_eq!(format_size(88_200, Units::Imperial), "88.2 KB (88200 bytes)");
+        assert_eq!(format_size(5_000_000, Units::Imperial), "5.0 MB (5000000 bytes)");
+
+        let args = Args::parse_from(["proofcapture-cli", "bundle", "--units", "imperial"]);
+        assert_eq!(args.units, Units::Imperial);
+    }
+
+    #[test]
+    fn test_check_overwrite_refuses_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("recording.m4a");
+        fs::write(&existing, b"earlier extraction").unwrap();
+        let destinations = [dir.path().join("photo.jpg"), existing.clone()];
+
+        let mut asked = Vec::new();
+        let error = check_overwrite(&destinations, false, |path| {
+            asked.push(path.to_path_buf());
+            false
+        })
+        .unwrap_err();
+        assert!(matches!(&error, VerifyError::OverwriteRefused { path } if *path == existing.display().to_string()));
+        assert_eq!(error.exit_code(), 20);
+        assert_eq!(asked, [existing]);
+
+        assert!(check_overwrite(&destinations, false, |_| true).is_ok());
+    }
+
+    #[test]
+    fn test_extract_with_assume_yes_overwrites() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/sealed_test.proofcapture");
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().to_str().unwrap();
+        let args = Args::parse_from(["proofcapture-cli", fixture, "-p", "test-password-123", "-e", out, "--assume-yes"]);
+
+        let (_, extraction) = verify_and_extract(&args).unwrap();
+        let audio = PathBuf::from(&extraction.unwrap().files[0].path);
+        let extracted = fs::read(&audio).unwrap();
+        fs::write(&audio, b"stale").unwrap();
+
+        verify_and_extract(&args).unwrap();
+        assert_eq!(fs::read(&audio).unwrap(), extracted);
+    }
+
+    #[test]
+    fn test_print_field_values() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/full_bundle");
+        let args = Args::parse_from(["proofcapture-cli", fixture, "--print", "trust-level"]);
+        let result = verify(&args).unwrap();
+
+        assert_eq!(args.print.unwrap().value(&result), "A");
+        assert_eq!(PrintField::AudioHash.value(&result), result.manifest.audio_hash);
+        assert_eq!(PrintField::Duration.value(&result), result.manifest.duration_seconds.to_string());
+    }
+
+    #[test]
+    fn test_print_rejects_unknown_field() {
+        let parsed = Args::try_parse_from(["proofcapture-cli", "bundle", "--print", "gps"]);
+        assert!(parsed.is_err());
+    }
 
-    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    #[test]
+    fn test_manifest_from_stdin() {
+        let bundle = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/full_bundle");
+        let manifest = fs::read(format!("{}/manifest.json", bundle)).unwrap();
+        let options = VerifyOptions::default();
+
+        // PATH as the audio file, and as the bundle directory
+        let audio = format!("{}/recording.m4a", bundle);
+        let args = Args::parse_from(["proofcapture-cli", &audio, "--manifest-stdin"]);
+        let result = verify_piped_manifest(&args, &manifest[..], &options).unwrap();
+        assert_eq!(result.trust_level.letter(), "A");
+        let args = Args::parse_from(["proofcapture-cli", bundle, "--manifest-stdin"]);
+        assert!(verify_piped_manifest(&args, &manifest[..], &options).is_ok());
+
+        let garbage = verify_piped_manifest(&args, &b"not json"[..], &options);
+        assert!(matches!(garbage, Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_manifest_stdin_conflicts_with_manifest() {
+        let parsed = Args::try_parse_from(["proofcapture-cli", "a.m4a", "--manifest", "m.json", "--manifest-stdin"]);
+        assert_eq!(parsed.unwrap_err().kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn test_now_pins_the_clock() {
+        let args = Args::parse_from(["proofcapture-cli", "bundle", "--now", "2026-02-01T12:00:00+02:00"]);
+        assert_eq!(args.clock().now().unix_timestamp(), 1_769_940_000);
+
+        let parsed = Args::try_parse_from(["proofcapture-cli", "bundle", "--now", "yesterday"]);
+        assert!(parsed.is_err());
+    }
+
+    // The signal path itself exits the process, so it's checked by hand: run
+    // `proofcapture-cli fixtures/sealed_test.proofcapture` in a terminal,
+    // press Ctrl-C at the prompt, and confirm that the shell prompt starts on
+    // a new line, typed text echoes, and `echo $?` prints 130.
+    #[cfg(unix)]
+    #[test]
+    fn test_prompt_interrupt_guard_restores_handler() {
+        fn current_handler() -> libc::sighandler_t {
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                libc::sigaction(libc::SIGINT, std::ptr::null(), &mut action);
+                action.sa_sigaction
+            }
+        }
+
+        let before = current_handler();
+        let guard = PromptInterruptGuard::install();
+        assert_ne!(current_handler(), before);
+        drop(guard);
+        assert_eq!(current_handler(), before);
+    }
+
+    #[test]
+    fn test_inspect_reads_sealed_metadata_without_password() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/sealed_test.proofcapture");
+        let args = Args::parse_from(["proofcapture-cli", fixture, "--inspect"]);
+        assert!(args.password.is_none());
+
+        let bundle = inspect(&args).unwrap();
+        assert_eq!(bundle.created_at, "2026-01-27T02:57:16Z");
+        assert_eq!(bundle.sealing_info().iterations, 600_000);
+    }
+
+    #[test]
+    fn test_mixed_directory_follows_prefer() {
+        let mixed = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/mixed_bundle");
+        let sealing = |extra: &[&str]| {
+            let args = Args::parse_from(["proofcapture-cli", mixed, "-p", "test-password-123"].iter().chain(extra));
+            verify(&args.with_bundle_resolved()).unwrap().sealing
+        };
+
+        assert!(sealing(&[]).is_some());
+        assert!(sealing(&["--prefer", "sealed"]).is_some());
+        assert!(sealing(&["--prefer", "standard"]).is_none());
+        assert!(sealing(&["--manifest", concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/mixed_bundle/manifest.json")]).is_none());
+    }
+
+    #[test]
+    fn test_profile_presets_options_and_explicit_flags_win() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("proofcapture.toml");
+        fs::write(
+            &config,
+            "[profiles.strict]\nrequire-vectors = [\"location\", \"clock\"]\nmax-age = \"30d\"\nmax-interruptions = 2\nwarnings-as-errors = true\n",
+        )
+        .unwrap();
+        let argv = |extra: &[&str]| {
+            let mut argv: Vec<OsString> = ["proofcapture-cli", "bundle", "--profile", "strict", "--config"].map(OsString::from).into();
+            argv.push(config.clone().into());
+            argv.extend(extra.iter().map(OsString::from));
+            argv
+        };
+
+        let args = argv(&["--max-age", "7d"]);
+        let parsed = Args::parse_from(&args);
+        let args = with_profile(args, &parsed, "strict").unwrap();
+        assert_eq!(args.require_vectors, [TrustVector::Location, TrustVector::Clock]);
+        assert!(args.warnings_as_errors);
+        let options = verify_options(&args);
+        assert_eq!(options.max_age(), parse_duration("7d"));
+        assert_eq!(options.max_interruptions(), Some(2));
+
+        fs::write(&config, "[profiles.strict]\nmax-agee = \"30d\"\n").unwrap();
+        let error = with_profile(argv(&[]), &parsed, "strict").unwrap_err();
+        assert!(matches!(error, VerifyError::ConfigInvalid { .. }), "{error}");
+        assert!(matches!(with_profile(argv(&[]), &parsed, "lenient"), Err(VerifyError::ConfigInvalid { .. })));
+    }
+
+    #[test]
+    fn test_manifest_only_json_matches_fixture() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/full_bundle");
+        let bytes = fs::read(dir.join("manifest.json")).unwrap();
+        let fixture: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        let args = Args::parse_from(["proofcapture-cli", dir.to_str().unwrap(), "--manifest-only-json"]);
+        let dumped: serde_json::Value = serde_json::from_str(&manifest_only_json(&args).unwrap()).unwrap();
+        // Same fields and values, though whole-number floats print as 5.0
+        assert_eq!(dumped, serde_json::to_value(SignedAudioManifest::from_json(&bytes).unwrap()).unwrap());
+        let keys = |value: &serde_json::Value| value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(&dumped), keys(&fixture));
+        assert_eq!(dumped["signature"], fixture["signature"]);
+        assert_eq!(dumped["trustVectors"]["location"]["start"]["lat"], fixture["trustVectors"]["location"]["start"]["lat"]);
+    }
+
+    #[test]
+    fn test_print_public_key_matches_fixture() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&fs::read(dir.join("full_bundle/manifest.json")).unwrap()).unwrap();
+
+        let args = Args::parse_from(["proofcapture-cli", dir.join("full_bundle").to_str().unwrap(), "--print-public-key"]);
+        let (public_key, fingerprint) = public_key_info(&args).unwrap();
+        assert_eq!(public_key, manifest["publicKey"].as_str().unwrap());
+        // The fixture's deviceKeyId is the same fingerprint in base64
+        let device_key_id = decode_base64(manifest["deviceKeyId"].as_str().unwrap()).unwrap();
+        assert_eq!(fingerprint, hex_encode(&device_key_id));
+
+        let sealed = dir.join("sealed_test.proofcapture");
+        let args = Args::parse_from(["proofcapture-cli", sealed.to_str().unwrap(), "--print-public-key", "-p", "test-password-123"]);
+        assert_eq!(public_key_info(&args).unwrap().1.len(), 64);
+    }
+
+    #[test]
+    fn test_max_age_uses_the_pinned_clock() {
+        // minimal_bundle ended at 2026-01-27T02:57:17Z
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/minimal_bundle");
+        let check = |now: &str| verify(&Args::parse_from(["proofcapture-cli", fixture, "--max-age", "30d", "--now", now]));
+
+        assert!(check("2026-02-20T00:00:00Z").is_ok());
+        assert!(matches!(check("2026-03-01T00:00:00Z"), Err(VerifyError::RecordingTooOld { .. })));
+        assert!(Args::try_parse_from(["proofcapture-cli", fixture, "--max-age", "30"]).is_err());
+    }
+
+    #[test]
+    fn test_batch_audit_log_hashes_bundles_without_a_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let batch = dir.path().join("batch");
+        fs::create_dir(&batch).unwrap();
+        let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("sealed_test.proofcapture");
+        fs::copy(&fixture, batch.join("sealed_test.proofcapture")).unwrap();
+        let log = dir.path().join("audit.jsonl");
+
+        let args = Args::parse_from([
+            "proofcapture-cli", batch.to_str().unwrap(), "--batch", "-p", "test-password-123", "--audit-log", log.to_str().unwrap(),
+        ]);
+        run_batch(&args).unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&fs::read(&log).unwrap()).unwrap();
+        assert_eq!(line["contentHash"], hex_encode(&proofcapture_cli::crypto::sha256_bytes(&fs::read(&fixture).unwrap())));
+    }
+
+    #[test]
+    fn test_max_interruption_seconds_must_be_non_negative() {
+        let parse = |secs: &str| Args::try_parse_from(["proofcapture-cli", "./bundle", &format!("--max-interruption-seconds={}", secs)]);
+
+        assert_eq!(parse("2.5").unwrap().max_interruption_seconds, Some(2.5));
+        assert_eq!(parse("0").unwrap().max_interruption_seconds, Some(0.0));
+        for secs in ["-1", "NaN", "inf", "two"] {
+            assert!(parse(secs).is_err(), "{}", secs);
+        }
+    }
+
+    #[test]
+    fn test_certificate_written_for_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("minimal_bundle");
+        let bundle = dir.path().join("bundle");
+        fs::create_dir(&bundle).unwrap();
+        let manifest = fs::read(fixture.join("manifest.json")).unwrap();
+        fs::write(bundle.join("manifest.json"), &manifest).unwrap();
+        let mut audio = fs::read(fixture.join("recording.m4a")).unwrap();
+        audio[0] ^= 0xFF;
+        fs::write(bundle.join("recording.m4a"), audio).unwrap();
+
+        let certificate = dir.path().join("certificate.html");
+        let args = Args::parse_from([
+            "proofcapture-cli",
+            bundle.to_str().unwrap(),
+            "--certificate",
+            certificate.to_str().unwrap(),
+        ]);
+        assert!(matches!(run(&args), Err(VerifyError::HashMismatch)));
+
+        let html = fs::read_to_string(certificate).unwrap();
+        let capture_start = SignedAudioManifest::from_json(&manifest).unwrap().capture_start;
+        assert!(html.contains("Verification failed"));
+        assert!(html.contains(capture_start.as_str()));
+    }
+
+    #[test]
+    fn test_now_does_not_backdate_receipts() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("operator.key");
+        fs::write(&key_file, BASE64.encode([0x42; 32])).unwrap();
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/minimal_bundle");
+        let args = Args::parse_from([
+            "proofcapture-cli",
+            fixture,
+            "--sign-receipt",
+            key_file.to_str().unwrap(),
+            "--now",
+            "2020-01-01T00:00:00Z",
+        ]);
+
+        let receipt = run(&args).unwrap().receipt.unwrap();
+        assert!(!receipt.verified_at.starts_with("2020-"), "{}", receipt.verified_at);
+    }
+
+    #[test]
+    fn test_output_dir_requires_batch() {
+        assert!(Args::try_parse_from(["proofcapture-cli", "./evidence", "--batch", "--output-dir", "out", "-y"]).is_ok());
+        assert!(Args::try_parse_from(["proofcapture-cli", "a.proofcapture", "--output-dir", "out"]).is_err());
+        assert!(Args::try_parse_from(["proofcapture-cli", "a.proofcapture", "-y"]).is_err());
+    }
+
+    #[test]
+    fn test_count_requires_batch() {
+        assert!(Args::try_parse_from(["proofcapture-cli", "./evidence", "--batch", "--count"]).is_ok());
+        let parsed = Args::try_parse_from(["proofcapture-cli", "./evidence", "--count"]);
+        assert_eq!(parsed.unwrap_err().kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_password_env_fills_password() {
+        let env = |var: &str| (var == "INTAKE_PASSWORD").then(|| "test-password-123".to_string());
+        let args = Args::parse_from(["proofcapture-cli", "./intake", "--watch", "--password-env", "INTAKE_PASSWORD"]);
+        assert_eq!(args.with_password_from_env(env).unwrap().password.as_deref(), Some("test-password-123"));
+
+        let unset = Args::parse_from(["proofcapture-cli", "./intake", "--password-env", "UNSET_PASSWORD"]);
+        assert!(matches!(unset.with_password_from_env(env), Err(VerifyError::PasswordRequired)));
+        let parsed = Args::try_parse_from(["proofcapture-cli", "./intake", "--settle-seconds", "5"]);
+        assert_eq!(parsed.unwrap_err().kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_format_values() {
+        let args = Args::parse_from(["proofcapture-cli", "bundle", "--format", "JSON"]);
+        assert_eq!(args.format, OutputFormat::Json);
+
+        let error = Args::try_parse_from(["proofcapture-cli", "bundle", "--format", "xml"]).unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::InvalidValue);
+        assert!(error.to_string().contains("[possible values: auto, text, json]"));
+    }
+
+    #[test]
+    fn test_format_auto_follows_terminal() {
+        let mut args = Args::parse_from(["proofcapture-cli", "bundle"]);
+        assert_eq!(args.format, OutputFormat::Auto);
+
+        args.stdout_terminal = Some(true);
+        assert_eq!((args.format(), args.compact()), (OutputFormat::Text, false));
+        args.stdout_terminal = Some(false);
+        assert_eq!((args.format(), args.compact()), (OutputFormat::Json, true));
+
+        // An explicit format wins either way
+        for terminal in [true, false] {
+            let mut text = Args::parse_from(["proofcapture-cli", "bundle", "--format", "text"]);
+            text.stdout_terminal = Some(terminal);
+            assert_eq!(text.format(), OutputFormat::Text);
+            let mut json = Args::parse_from(["proofcapture-cli", "bundle", "-f", "json"]);
+            json.stdout_terminal = Some(terminal);
+            assert_eq!((json.format(), json.compact()), (OutputFormat::Json, false));
+        }
+    }
 }
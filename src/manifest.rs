@@ -3,6 +3,11 @@
 //! Handles parsing of SignedAudioManifest from iOS and
 //! canonicalization for signature verification.
 
+use std::borrow::Cow;
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -12,6 +17,21 @@ use crate::error::{Result, VerifyError};
 /// Current supported schema version.
 pub const CURRENT_SCHEMA_VERSION: i32 = 1;
 
+/// `audioFormat` values a bundle can carry; anything else (e.g. `mp3`) means
+/// the manifest doesn't describe the packaged audio.
+pub const KNOWN_AUDIO_FORMATS: [&str; 4] = ["aac", "m4a", "mp4", "wav"];
+
+/// Magic bytes at the start of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Default limit on the size of the manifest JSON a verification will parse,
+/// whether stored plain or gzipped. Real manifests are a few KiB.
+pub const DEFAULT_MAX_MANIFEST_BYTES: usize = 4 * 1024 * 1024;
+
+/// Deepest nesting of objects and arrays canonicalization accepts. Real
+/// manifests nest four levels deep.
+pub const MAX_MANIFEST_DEPTH: usize = 32;
+
 /// The signed audio manifest structure from iOS.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -19,6 +39,13 @@ pub struct SignedAudioManifest {
     pub schema_version: i32,
     pub audio_hash: String,
     pub audio_format: String,
+    /// Name of the audio file in the bundle, when the producer recorded it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_filename: Option<String>,
+    /// Where to fetch the audio when it isn't stored alongside the manifest:
+    /// a local path (relative to the manifest's directory) or an http(s):// URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_ref: Option<String>,
     pub audio_size_bytes: i64,
     pub capture_start: String,
     pub capture_end: String,
@@ -27,12 +54,69 @@ pub struct SignedAudioManifest {
     pub app_bundle_id: String,
     pub device_key_id: String,
     pub public_key: String,
+    /// Absent from minimal manifests, which carry no trust vectors at all.
+    #[serde(default)]
     pub trust_vectors: TrustVectors,
+    /// How the signature was produced; absent means [`SignatureMode::Digest`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_mode: Option<SignatureMode>,
+    /// How the manifest is canonicalized for hashing; absent means
+    /// [`Canonicalization::IosLegacy`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonicalization: Option<Canonicalization>,
+    /// Base64 signature; absent when the signature is detached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Further signatures over the same canonical manifest, e.g. by a notary
+    /// service. Excluded from the canonical JSON, like `signature`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<CoSignature>,
+}
+
+/// A co-signature by a party other than the capturing device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoSignature {
+    /// Base64 raw 64-byte P-256 public key of the co-signer.
+    pub public_key: String,
+    /// Base64 raw 64-byte r||s signature, made as the manifest's
+    /// `signatureMode` says.
     pub signature: String,
+    /// What the co-signer attests to, e.g. `"notary"`.
+    pub role: String,
+}
+
+/// How the signer passed the canonical manifest to ECDSA.
+///
+/// Like every other field, `signatureMode` is covered by the signature, so it
+/// can't be changed to steer verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SignatureMode {
+    /// The 32-byte SHA-256 of the canonical JSON was signed as the message,
+    /// so ECDSA hashed it a second time. Used by iOS 1.x.
+    Digest,
+    /// The canonical JSON bytes were signed directly (hashed once by ECDSA).
+    Message,
+}
+
+/// How manifest JSON is turned into the canonical bytes that are hashed.
+///
+/// Declared by the signed `canonicalization` field, so like `signatureMode`
+/// it can't be changed without breaking the signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Canonicalization {
+    /// What iOS 1.x's `JSONEncoder` produces: forward slashes escaped,
+    /// whole floats keeping their `.0`, keys sorted by UTF-8 bytes.
+    #[default]
+    IosLegacy,
+    /// RFC 8785 JSON Canonicalization Scheme.
+    Jcs,
 }
 
 /// Trust vectors container.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TrustVectors {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<LocationVector>,
@@ -44,15 +128,44 @@ pub struct TrustVectors {
     pub clock: Option<ClockVector>,
 }
 
+impl TrustVectors {
+    /// Combine another fragment's vectors into these, e.g. when a multi-part
+    /// capture delivers its vectors in separate manifests.
+    ///
+    /// Absent vectors are filled in from `other`. Where both have a location,
+    /// motion or clock vector, `other`'s wins (later fragments supersede
+    /// earlier ones). Continuity vectors are combined: interruption events are
+    /// concatenated, and the capture is uninterrupted only if both were.
+    pub fn merge(&mut self, other: &TrustVectors) {
+        if other.location.is_some() {
+            self.location = other.location.clone();
+        }
+        if other.motion.is_some() {
+            self.motion = other.motion.clone();
+        }
+        if other.clock.is_some() {
+            self.clock = other.clock.clone();
+        }
+        match (&mut self.continuity, &other.continuity) {
+            (Some(mine), Some(theirs)) => {
+                mine.uninterrupted &= theirs.uninterrupted;
+                mine.interruption_events.extend(theirs.interruption_events.iter().cloned());
+            }
+            (None, Some(theirs)) => self.continuity = Some(theirs.clone()),
+            (_, None) => {}
+        }
+    }
+}
+
 /// Location trust vector.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LocationVector {
     pub start: LocationSnapshot,
     pub end: LocationSnapshot,
 }
 
 /// Location snapshot at a point in time.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LocationSnapshot {
     pub lat: f64,
     pub lon: f64,
@@ -60,7 +173,7 @@ pub struct LocationSnapshot {
 }
 
 /// Motion trust vector.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MotionVector {
     pub acceleration_variance: f64,
@@ -70,22 +183,33 @@ pub struct MotionVector {
 }
 
 /// Continuity trust vector.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ContinuityVector {
     pub uninterrupted: bool,
     pub interruption_events: Vec<InterruptionEvent>,
 }
 
+impl ContinuityVector {
+    /// Total seconds the recording was interrupted, or `None` if any event
+    /// doesn't say how long it lasted.
+    pub fn total_interruption_seconds(&self) -> Option<f64> {
+        self.interruption_events.iter().map(|e| e.duration_seconds).sum()
+    }
+}
+
 /// An interruption event during recording.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InterruptionEvent {
     pub timestamp: String,
     pub reason: String,
+    /// How long the interruption lasted, if the app measured it.
+    #[serde(rename = "durationSeconds", default, skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<f64>,
 }
 
 /// Clock trust vector.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ClockVector {
     pub wall_clock_start: String,
@@ -95,14 +219,21 @@ pub struct ClockVector {
 }
 
 impl SignedAudioManifest {
-    /// Parse manifest from JSON bytes.
+    /// Parse manifest from JSON bytes (gzip-compressed input is decompressed first).
     pub fn from_json(json_bytes: &[u8]) -> Result<Self> {
-        serde_json::from_slice(json_bytes).map_err(|_| VerifyError::ManifestMalformed)
+        let json_bytes = decompress_manifest(json_bytes)?;
+        serde_json::from_slice(&json_bytes).map_err(|_| VerifyError::ManifestMalformed)
     }
 
     /// Validate schema version is supported.
     pub fn validate_schema(&self) -> Result<()> {
-        if self.schema_version > CURRENT_SCHEMA_VERSION {
+        self.validate_schema_up_to(CURRENT_SCHEMA_VERSION)
+    }
+
+    /// Validate schema version is at most `max`, which may exceed
+    /// [`CURRENT_SCHEMA_VERSION`] when testing draft schemas.
+    pub fn validate_schema_up_to(&self, max: i32) -> Result<()> {
+        if self.schema_version > max {
             return Err(VerifyError::SchemaUnsupported {
                 version: self.schema_version,
             });
@@ -110,63 +241,374 @@ impl SignedAudioManifest {
         Ok(())
     }
 
-    /// Compute the canonical hash for signature verification.
+    /// Whether `audioFormat` is one of [`KNOWN_AUDIO_FORMATS`], ignoring case.
+    pub fn has_known_audio_format(&self) -> bool {
+        KNOWN_AUDIO_FORMATS.contains(&self.audio_format.to_lowercase().as_str())
+    }
+
+    /// Compute the canonical hash of this manifest, as
+    /// [`compute_canonical_hash_from_bytes`] does for its JSON: under the
+    /// declared canonicalization, without `signature` or `signatures`.
     ///
-    /// This must exactly match iOS's HashingService.computeManifestHash():
-    /// - Exclude the "signature" field
-    /// - Sort keys alphabetically (recursive)
-    /// - Compact JSON (no whitespace)
-    /// - UTF-8 encoding
+    /// The manifest is serialized first, so numbers are in serde_json's
+    /// formatting rather than the signer's; prefer hashing the original bytes
+    /// when they're available.
     pub fn compute_canonical_hash(&self) -> Result<[u8; 32]> {
-        // Parse to generic Value to manipulate
-        let json_str = serde_json::to_string(self).map_err(|_| VerifyError::ManifestMalformed)?;
-        let mut value: Value =
-            serde_json::from_str(&json_str).map_err(|_| VerifyError::ManifestMalformed)?;
+        let json = serde_json::to_vec(self).map_err(|_| VerifyError::ManifestMalformed)?;
+        canonical_hash(&json, true)
+    }
+}
 
-        // Remove signature field
-        if let Value::Object(ref mut map) = value {
-            map.remove("signature");
+/// Validate that trust vector values are physically sensible.
+///
+/// Variances, durations and interruption durations must be non-negative,
+/// GPS accuracy must be positive, and sample counts must be non-negative.
+/// Violations indicate a corrupted or synthetic manifest and are reported as
+/// `ManifestMalformed`.
+pub fn validate_trust_vectors(vectors: &TrustVectors) -> Result<()> {
+    // Also rejects NaN and infinity, which JSON can't encode but a
+    // programmatically built manifest can hold
+    fn non_negative(value: f64) -> bool {
+        value.is_finite() && value >= 0.0
+    }
+
+    if let Some(location) = &vectors.location {
+        for snapshot in [&location.start, &location.end] {
+            if snapshot.accuracy <= 0.0 {
+                return Err(VerifyError::ManifestMalformed);
+            }
+        }
+    }
+
+    if let Some(motion) = &vectors.motion {
+        if !non_negative(motion.acceleration_variance)
+            || !non_negative(motion.rotation_variance)
+            || !non_negative(motion.duration)
+            || motion.sample_count < 0
+        {
+            return Err(VerifyError::ManifestMalformed);
         }
+    }
+
+    if let Some(continuity) = &vectors.continuity {
+        // A negative duration would let other interruptions slip under --max-interruption-seconds
+        if continuity.interruption_events.iter().filter_map(|e| e.duration_seconds).any(|d| !non_negative(d)) {
+            return Err(VerifyError::ManifestMalformed);
+        }
+    }
+
+    Ok(())
+}
 
-        // Serialize with sorted keys (serde_json sorts by default for BTreeMap)
-        // We need to ensure keys are sorted - convert to sorted representation
-        let canonical = canonicalize_json(&value)?;
+/// Returns the manifest JSON, decompressing it if it is gzip-compressed.
+///
+/// Some bundle variants store large manifests gzipped. The signature always
+/// covers the canonical form of the decompressed JSON, so callers must hash
+/// the bytes returned here rather than the compressed input.
+///
+/// JSON decompressing to more than [`DEFAULT_MAX_MANIFEST_BYTES`] is
+/// malformed; see [`decompress_manifest_with_limit`].
+pub fn decompress_manifest(manifest_bytes: &[u8]) -> Result<Cow<'_, [u8]>> {
+    decompress_manifest_with_limit(manifest_bytes, DEFAULT_MAX_MANIFEST_BYTES)
+}
+
+/// [`decompress_manifest`], failing with `ManifestMalformed` once the JSON
+/// passes `max_bytes`, so a gzip bomb is never fully inflated.
+pub fn decompress_manifest_with_limit(manifest_bytes: &[u8], max_bytes: usize) -> Result<Cow<'_, [u8]>> {
+    if !manifest_bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(Cow::Borrowed(manifest_bytes));
+    }
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(manifest_bytes)
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|_| VerifyError::ManifestMalformed)?;
 
-        Ok(sha256_bytes(canonical.as_bytes()))
+    if decompressed.len() > max_bytes {
+        return Err(VerifyError::ManifestMalformed);
     }
+
+    Ok(Cow::Owned(decompressed))
 }
 
 /// Compute canonical hash directly from JSON bytes (preserves original formatting).
 /// This is the preferred method as it preserves the original number formatting.
+/// Gzip-compressed input is decompressed before canonicalization.
 pub fn compute_canonical_hash_from_bytes(json_bytes: &[u8]) -> Result<[u8; 32]> {
+    canonical_hash(json_bytes, true)
+}
+
+/// Compute the canonical hash of a manifest whose signature is detached.
+///
+/// Every field but `signatures` is covered, including any `signature`.
+pub fn compute_canonical_hash_detached(json_bytes: &[u8]) -> Result<[u8; 32]> {
+    canonical_hash(json_bytes, false)
+}
+
+fn canonical_hash(json_bytes: &[u8], strip_signature: bool) -> Result<[u8; 32]> {
+    let json_bytes = decompress_manifest(json_bytes)?;
+
     // Parse to generic Value
     let mut value: Value =
-        serde_json::from_slice(json_bytes).map_err(|_| VerifyError::ManifestMalformed)?;
+        serde_json::from_slice(&json_bytes).map_err(|_| VerifyError::ManifestMalformed)?;
+
+    let mode = declared_canonicalization(&value)?;
 
-    // Remove signature field
+    // Remove embedded signature field; co-signatures can never cover themselves
     if let Value::Object(ref mut map) = value {
-        map.remove("signature");
+        if strip_signature {
+            map.remove("signature");
+        }
+        map.remove("signatures");
     }
 
     // Canonicalize (sort keys, compact)
-    let canonical = canonicalize_json(&value)?;
+    let canonical = canonicalize_at_depth(&value, 0, mode)?;
 
     Ok(sha256_bytes(canonical.as_bytes()))
 }
 
+/// The canonicalization a manifest declares; an unknown one is malformed.
+fn declared_canonicalization(value: &Value) -> Result<Canonicalization> {
+    match value.get("canonicalization") {
+        None => Ok(Canonicalization::IosLegacy),
+        Some(declared) => {
+            Canonicalization::deserialize(declared).map_err(|_| VerifyError::ManifestMalformed)
+        }
+    }
+}
+
+/// Diagnostic summary of a manifest's canonical form.
+///
+/// Describes what the signature covers without exposing the content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanonicalSummary {
+    /// Top-level keys in canonical order, `signature` and `signatures` excluded.
+    pub keys: Vec<String>,
+    /// Length in bytes of the canonical JSON that is hashed.
+    pub length: usize,
+}
+
+/// Summarize the canonical form of manifest JSON bytes.
+pub fn canonical_summary(json_bytes: &[u8]) -> Result<CanonicalSummary> {
+    let json_bytes = decompress_manifest(json_bytes)?;
+
+    let mut value: Value =
+        serde_json::from_slice(&json_bytes).map_err(|_| VerifyError::ManifestMalformed)?;
+    let mode = declared_canonicalization(&value)?;
+    let map = value.as_object_mut().ok_or(VerifyError::ManifestMalformed)?;
+    map.remove("signature");
+    map.remove("signatures");
+
+    let mut keys: Vec<String> = map.keys().cloned().collect();
+    sort_keys(&mut keys, mode);
+    let length = canonicalize_at_depth(&value, 0, mode)?.len();
+
+    Ok(CanonicalSummary { keys, length })
+}
+
+/// A canonicalization test vector: manifest JSON, the canonical bytes its
+/// signature covers, and their SHA-256.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalTestVector {
+    pub name: &'static str,
+    /// Canonicalization the vector is for.
+    pub mode: Canonicalization,
+    /// Manifest JSON as written, possibly with an embedded `signature`.
+    pub input: &'static str,
+    /// Canonical UTF-8 JSON of `input`, `signature` and `signatures` excluded.
+    pub canonical: &'static str,
+    /// Lowercase hex SHA-256 of `canonical`.
+    pub sha256: &'static str,
+}
+
+/// Vectors pinning the canonicalization rules (spec section 5) byte for
+/// byte, for other implementations to test against. Each input appears once
+/// per mode, canonicalized with [`canonical_json_with_mode`]. `IosLegacy`
+/// inputs also hash to `sha256` with [`compute_canonical_hash_from_bytes`];
+/// a real JCS manifest declares `"canonicalization": "jcs"`, which is then
+/// part of its canonical bytes.
+pub const CANONICAL_TEST_VECTORS: &[CanonicalTestVector] = &[
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "sorted-keys",
+        input: r#"{"b":2,"a":1,"c":{"z":true,"y":[3,{"k":null,"j":false}]}}"#,
+        canonical: r#"{"a":1,"b":2,"c":{"y":[3,{"j":false,"k":null}],"z":true}}"#,
+        sha256: "d767e226785e8345db5d0f29917f46a9c999d9e20a81c8574e6b85b9989f2da0",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "signature-excluded",
+        input: r#"{"schemaVersion":1,"signature":"MEUCIQ==","audioHash":"47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="}"#,
+        canonical: r#"{"audioHash":"47DEQpj8HBSa+\/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=","schemaVersion":1}"#,
+        sha256: "80ff02189c225c89f90318747728e2c1a9eeceb7c746660720ecc661d574d302",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "co-signatures-excluded",
+        input: r#"{"schemaVersion":1,"signature":"MEUCIQ==","signatures":[{"publicKey":"AAAA","signature":"BBBB","role":"notary"}]}"#,
+        canonical: r#"{"schemaVersion":1}"#,
+        sha256: "0e9561cfb83d50990a103b3896fe249a11fe27fa28985448187f93ec12116d72",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "escaping",
+        input: r#"{"appBundleId":"com.bestdaylabs/proofcapture","note":"a \"quoted\" back\\slash\nnew\tline\u0001"}"#,
+        canonical: r#"{"appBundleId":"com.bestdaylabs\/proofcapture","note":"a \"quoted\" back\\slash\nnew\tline\u0001"}"#,
+        sha256: "6f4be517476748f402bece1c1f7c30980e391c5b79081156000955e684385a10",
+    },
+    CanonicalTestVector {
+        // An escaped combining accent is written raw, and not normalized to NFC
+        mode: Canonicalization::IosLegacy,
+        name: "unicode-unnormalized",
+        input: "{\"reason\":\"Cafe\\u0301\",\"place\":\"Caf\u{e9}\",\"emoji\":\"\u{1f399}\"}",
+        canonical: "{\"emoji\":\"\u{1f399}\",\"place\":\"Caf\u{e9}\",\"reason\":\"Cafe\u{301}\"}",
+        sha256: "0956d1229da46c5261170f0dcbfdb2feab8345d75205ffd52008c33d2ad36cda",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "numbers",
+        input: r#"{"int":42,"negative":-7,"float":1.5,"zero":0,"small":0.000125}"#,
+        canonical: r#"{"float":1.5,"int":42,"negative":-7,"small":0.000125,"zero":0}"#,
+        sha256: "31f8eca6ba6238676e726c00d05e8792aaadeafa79733fc4d8043a9a6fa33d87",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "empty-containers",
+        input: r#"{"emptyObject":{},"emptyArray":[],"nested":[[],[{}]]}"#,
+        canonical: r#"{"emptyArray":[],"emptyObject":{},"nested":[[],[{}]]}"#,
+        sha256: "67ce61a9c2a04e5ee51a7f66d78c78739daf46631b1828b780184693bfc33a26",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "number-formatting",
+        input: r#"{"whole":5.0,"big":1e21,"tiny":1E-7,"negZero":-0.0,"huge":12345678901234567890}"#,
+        canonical: r#"{"big":1e+21,"huge":12345678901234567890,"negZero":-0.0,"tiny":1e-7,"whole":5.0}"#,
+        sha256: "29bed4f2702946715a44c976aec12a242eee700fe2b3364c235a14b662d0c94b",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::IosLegacy,
+        name: "control-characters",
+        input: r#"{"note":"bell\u0007back\bform\fdel\u007f","path":"a/b"}"#,
+        canonical: r#"{"note":"bell\u0007back\u0008form\u000cdel\u007f","path":"a\/b"}"#,
+        sha256: "476cefbf049f34c7d662b412e785bb87dcc747d2e8de5c08fd357ba97a6da682",
+    },
+    CanonicalTestVector {
+        // Sorted by UTF-8 bytes: U+E000 (EE 80 80) before U+1F399 (F0 9F 8E 99)
+        mode: Canonicalization::IosLegacy,
+        name: "key-order",
+        input: "{\"\u{e000}\":1,\"\u{1f399}\":2}",
+        canonical: "{\"\u{e000}\":1,\"\u{1f399}\":2}",
+        sha256: "cfda7ca07c9c56dd6a217d1adcc0fd31fe538b7a2b9d64eb69b50506befc3487",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "sorted-keys",
+        input: r#"{"b":2,"a":1,"c":{"z":true,"y":[3,{"k":null,"j":false}]}}"#,
+        canonical: r#"{"a":1,"b":2,"c":{"y":[3,{"j":false,"k":null}],"z":true}}"#,
+        sha256: "d767e226785e8345db5d0f29917f46a9c999d9e20a81c8574e6b85b9989f2da0",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "signature-excluded",
+        input: r#"{"schemaVersion":1,"signature":"MEUCIQ==","audioHash":"47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="}"#,
+        canonical: r#"{"audioHash":"47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=","schemaVersion":1}"#,
+        sha256: "341ad62b1e723e35e0fe8809cf4614b717843b9382dfeb64467d3753b5064962",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "co-signatures-excluded",
+        input: r#"{"schemaVersion":1,"signature":"MEUCIQ==","signatures":[{"publicKey":"AAAA","signature":"BBBB","role":"notary"}]}"#,
+        canonical: r#"{"schemaVersion":1}"#,
+        sha256: "0e9561cfb83d50990a103b3896fe249a11fe27fa28985448187f93ec12116d72",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "escaping",
+        input: r#"{"appBundleId":"com.bestdaylabs/proofcapture","note":"a \"quoted\" back\\slash\nnew\tline\u0001"}"#,
+        canonical: r#"{"appBundleId":"com.bestdaylabs/proofcapture","note":"a \"quoted\" back\\slash\nnew\tline\u0001"}"#,
+        sha256: "a70f3e3180b54f2d97a21722686f0d24fe6a85c7dd37a3600d69ab25ea31d79c",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "unicode-unnormalized",
+        input: "{\"reason\":\"Cafe\\u0301\",\"place\":\"Caf\u{e9}\",\"emoji\":\"\u{1f399}\"}",
+        canonical: "{\"emoji\":\"\u{1f399}\",\"place\":\"Caf\u{e9}\",\"reason\":\"Cafe\u{301}\"}",
+        sha256: "0956d1229da46c5261170f0dcbfdb2feab8345d75205ffd52008c33d2ad36cda",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "numbers",
+        input: r#"{"int":42,"negative":-7,"float":1.5,"zero":0,"small":0.000125}"#,
+        canonical: r#"{"float":1.5,"int":42,"negative":-7,"small":0.000125,"zero":0}"#,
+        sha256: "31f8eca6ba6238676e726c00d05e8792aaadeafa79733fc4d8043a9a6fa33d87",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "empty-containers",
+        input: r#"{"emptyObject":{},"emptyArray":[],"nested":[[],[{}]]}"#,
+        canonical: r#"{"emptyArray":[],"emptyObject":{},"nested":[[],[{}]]}"#,
+        sha256: "67ce61a9c2a04e5ee51a7f66d78c78739daf46631b1828b780184693bfc33a26",
+    },
+    CanonicalTestVector {
+        // Every number is a double, written as ECMAScript would
+        mode: Canonicalization::Jcs,
+        name: "number-formatting",
+        input: r#"{"whole":5.0,"big":1e21,"tiny":1E-7,"negZero":-0.0,"huge":12345678901234567890}"#,
+        canonical: r#"{"big":1e+21,"huge":12345678901234567000,"negZero":0,"tiny":1e-7,"whole":5}"#,
+        sha256: "c562aa29783cc9684d55f410ddc50ffbb0c73a8514c562240d8930ef10c884df",
+    },
+    CanonicalTestVector {
+        mode: Canonicalization::Jcs,
+        name: "control-characters",
+        input: r#"{"note":"bell\u0007back\bform\fdel\u007f","path":"a/b"}"#,
+        canonical: "{\"note\":\"bell\\u0007back\\bform\\fdel\u{7f}\",\"path\":\"a/b\"}",
+        sha256: "ab980e20ef7f46df4d095cca225e711fe9aa38f5f15eee91a75d26cd314d381b",
+    },
+    CanonicalTestVector {
+        // Sorted by UTF-16 code units: U+1F399 (D83C DF99) before U+E000
+        mode: Canonicalization::Jcs,
+        name: "key-order",
+        input: "{\"\u{e000}\":1,\"\u{1f399}\":2}",
+        canonical: "{\"\u{1f399}\":2,\"\u{e000}\":1}",
+        sha256: "e35e4f27930b9f0349348c66e3eca0980cefe57106473ba82f1f63f686b6e91d",
+    },
+];
+
+/// Canonical JSON of `value` under `mode`, whatever the value declares.
+pub fn canonical_json_with_mode(value: &Value, mode: Canonicalization) -> Result<String> {
+    canonicalize_at_depth(value, 0, mode)
+}
+
 /// Recursively sort JSON object keys and produce compact output.
+///
+/// Structures nested deeper than [`MAX_MANIFEST_DEPTH`] are rejected as
+/// `ManifestMalformed` rather than canonicalized.
+#[cfg(test)]
 fn canonicalize_json(value: &Value) -> Result<String> {
+    canonicalize_at_depth(value, 0, Canonicalization::IosLegacy)
+}
+
+fn canonicalize_at_depth(value: &Value, depth: usize, mode: Canonicalization) -> Result<String> {
+    if depth > MAX_MANIFEST_DEPTH {
+        return Err(VerifyError::ManifestMalformed);
+    }
     match value {
         Value::Object(map) => {
             // Sort keys and recursively canonicalize values
-            let mut sorted: Vec<_> = map.iter().collect();
-            sorted.sort_by(|a, b| a.0.cmp(b.0));
+            let mut keys: Vec<&String> = map.keys().collect();
+            sort_keys(&mut keys, mode);
 
-            let pairs: Vec<String> = sorted
+            let pairs: Vec<String> = keys
                 .into_iter()
-                .map(|(k, v)| {
-                    let canonical_v = canonicalize_json(v)?;
-                    Ok(format!("\"{}\":{}", k, canonical_v))
+                .map(|k| {
+                    let canonical_v = canonicalize_at_depth(&map[k], depth + 1, mode)?;
+                    Ok(match mode {
+                        // iOS writes keys as they are; manifest keys never need escaping
+                        Canonicalization::IosLegacy => format!("\"{}\":{}", k, canonical_v),
+                        Canonicalization::Jcs => format!("\"{}\":{}", escape_jcs_string(k), canonical_v),
+                    })
                 })
                 .collect::<Result<Vec<_>>>()?;
 
@@ -175,19 +617,40 @@ fn canonicalize_json(value: &Value) -> Result<String> {
         Value::Array(arr) => {
             let items: Vec<String> = arr
                 .iter()
-                .map(canonicalize_json)
+                .map(|item| canonicalize_at_depth(item, depth + 1, mode))
                 .collect::<Result<Vec<_>>>()?;
             Ok(format!("[{}]", items.join(",")))
         }
-        Value::String(s) => Ok(format!("\"{}\"", escape_json_string(s))),
-        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) => Ok(match mode {
+            Canonicalization::IosLegacy => format!("\"{}\"", escape_json_string(s)),
+            Canonicalization::Jcs => format!("\"{}\"", escape_jcs_string(s)),
+        }),
+        Value::Number(n) => match mode {
+            Canonicalization::IosLegacy => Ok(n.to_string()),
+            Canonicalization::Jcs => n.as_f64().map(jcs_number).ok_or(VerifyError::ManifestMalformed),
+        },
         Value::Bool(b) => Ok(if *b { "true" } else { "false" }.to_string()),
         Value::Null => Ok("null".to_string()),
     }
 }
 
+/// Sort object keys: by UTF-8 bytes for iOS, by UTF-16 code units for JCS.
+///
+/// The two orders differ only between keys with characters above U+FFFF
+/// and keys with characters in U+E000..=U+FFFF.
+fn sort_keys<K: AsRef<str>>(keys: &mut [K], mode: Canonicalization) {
+    match mode {
+        Canonicalization::IosLegacy => keys.sort_by(|a, b| a.as_ref().cmp(b.as_ref())),
+        Canonicalization::Jcs => keys.sort_by(|a, b| a.as_ref().encode_utf16().cmp(b.as_ref().encode_utf16())),
+    }
+}
+
 /// Escape special characters in JSON strings.
 /// Note: iOS JSONEncoder escapes forward slashes, so we must too for compatibility.
+///
+/// No Unicode normalization is applied. JSONEncoder writes a Swift String's
+/// scalars exactly as stored (NFC and NFD input stay distinct), so the
+/// canonical form must preserve code points byte-for-byte to match.
 fn escape_json_string(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     for c in s.chars() {
@@ -207,9 +670,348 @@ fn escape_json_string(s: &str) -> String {
     result
 }
 
+/// Escape a string as RFC 8785 section 3.2.2.2 requires: only `"`, `\` and
+/// U+0000..=U+001F are escaped, with the short forms where JSON has one.
+fn escape_jcs_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\u{8}' => result.push_str("\\b"),
+            '\u{c}' => result.push_str("\\f"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                result.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Format a number as ECMAScript's `Number.prototype.toString` does, which
+/// RFC 8785 section 3.2.2.3 requires. Every number is treated as an IEEE 754
+/// double, so integers beyond 2^53 lose precision.
+fn jcs_number(n: f64) -> String {
+    if n == 0.0 {
+        // Also -0
+        return "0".to_string();
+    }
+    // Rust's `{:e}` gives the shortest digits that round-trip, as ECMAScript needs
+    let formatted = format!("{:e}", n.abs());
+    let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let digits: String = mantissa.chars().filter(|c| c.is_ascii_digit()).collect();
+    let k = digits.len() as i32;
+    // The decimal point sits after the first `point` digits
+    let point = exponent.parse::<i32>().unwrap_or(0) + 1;
+
+    let mut result = String::new();
+    if n < 0.0 {
+        result.push('-');
+    }
+    if k <= point && point <= 21 {
+        result.push_str(&digits);
+        result.push_str(&"0".repeat((point - k) as usize));
+    } else if 0 < point && point <= 21 {
+        result.push_str(&digits[..point as usize]);
+        result.push('.');
+        result.push_str(&digits[point as usize..]);
+    } else if -6 < point && point <= 0 {
+        result.push_str("0.");
+        result.push_str(&"0".repeat(-point as usize));
+        result.push_str(&digits);
+    } else {
+        result.push_str(&digits[..1]);
+        if k > 1 {
+            result.push('.');
+            result.push_str(&digits[1..]);
+        }
+        let exponent = point - 1;
+        result.push_str(&format!("e{}{}", if exponent < 0 { '-' } else { '+' }, exponent.abs()));
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::hex_encode;
+
+    #[test]
+    fn test_canonical_summary_of_minimal_fixture() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/minimal_bundle/manifest.json");
+        let bytes = std::fs::read(path).unwrap();
+        let summary = canonical_summary(&bytes).unwrap();
+
+        assert_eq!(
+            summary.keys,
+            [
+                "appBundleId", "appVersion", "audioFormat", "audioHash", "audioSizeBytes", "captureEnd",
+                "captureStart", "deviceKeyId", "durationSeconds", "publicKey", "schemaVersion", "trustVectors",
+            ]
+        );
+
+        let mut value: Value = serde_json::from_slice(&bytes).unwrap();
+        value.as_object_mut().unwrap().remove("signature");
+        assert_eq!(summary.length, canonicalize_json(&value).unwrap().len());
+    }
+
+    fn valid_vectors() -> TrustVectors {
+        let snapshot = LocationSnapshot {
+            lat: 37.775,
+            lon: -122.418,
+            accuracy: 65.0,
+        };
+        TrustVectors {
+            location: Some(LocationVector {
+                start: snapshot.clone(),
+                end: snapshot,
+            }),
+            motion: Some(MotionVector {
+                acceleration_variance: 0.002,
+                rotation_variance: 0.001,
+                duration: 5.0,
+                sample_count: 500,
+            }),
+            continuity: None,
+            clock: None,
+        }
+    }
+
+    fn continuity(events: &[&str]) -> ContinuityVector {
+        ContinuityVector {
+            uninterrupted: events.is_empty(),
+            interruption_events: events
+                .iter()
+                .map(|reason| InterruptionEvent {
+                    timestamp: "2026-01-27T02:56:00Z".to_string(),
+                    reason: reason.to_string(),
+                    duration_seconds: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge_disjoint_vectors() {
+        let mut merged = valid_vectors();
+        let fragment = TrustVectors {
+            location: None,
+            motion: None,
+            continuity: Some(continuity(&[])),
+            clock: Some(ClockVector {
+                wall_clock_start: "2026-01-27T02:55:00Z".to_string(),
+                wall_clock_end: "2026-01-27T02:56:00Z".to_string(),
+                monotonic_delta: 60.0,
+                time_zone: "UTC".to_string(),
+            }),
+        };
+        merged.merge(&fragment);
+
+        assert!(merged.location.is_some() && merged.motion.is_some());
+        assert!(merged.continuity.unwrap().uninterrupted);
+        assert_eq!(merged.clock.unwrap().monotonic_delta, 60.0);
+    }
+
+    #[test]
+    fn test_merge_overlapping_vectors() {
+        let mut merged = valid_vectors();
+        merged.continuity = Some(continuity(&["phoneCall"]));
+
+        let mut fragment = valid_vectors();
+        fragment.motion.as_mut().unwrap().sample_count = 900;
+        fragment.continuity = Some(continuity(&["siri"]));
+        merged.merge(&fragment);
+
+        assert_eq!(merged.motion.unwrap().sample_count, 900);
+        let continuity = merged.continuity.unwrap();
+        assert!(!continuity.uninterrupted);
+        let reasons: Vec<&str> = continuity.interruption_events.iter().map(|e| e.reason.as_str()).collect();
+        assert_eq!(reasons, ["phoneCall", "siri"]);
+    }
+
+    #[test]
+    fn test_validate_trust_vectors_accepts_sensible_values() {
+        assert!(validate_trust_vectors(&valid_vectors()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_trust_vectors_accepts_zero_variance() {
+        let mut vectors = valid_vectors();
+        let motion = vectors.motion.as_mut().unwrap();
+        motion.acceleration_variance = 0.0;
+        motion.rotation_variance = 0.0;
+        motion.duration = 0.0;
+        motion.sample_count = 0;
+        assert!(validate_trust_vectors(&vectors).is_ok());
+    }
+
+    #[test]
+    fn test_validate_trust_vectors_rejects_negative_variance() {
+        let mut vectors = valid_vectors();
+        vectors.motion.as_mut().unwrap().acceleration_variance = -0.1;
+        assert!(matches!(validate_trust_vectors(&vectors), Err(VerifyError::ManifestMalformed)));
+
+        let mut vectors = valid_vectors();
+        vectors.motion.as_mut().unwrap().rotation_variance = -0.1;
+        assert!(matches!(validate_trust_vectors(&vectors), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_validate_trust_vectors_rejects_negative_duration_and_samples() {
+        let mut vectors = valid_vectors();
+        vectors.motion.as_mut().unwrap().duration = -5.0;
+        assert!(matches!(validate_trust_vectors(&vectors), Err(VerifyError::ManifestMalformed)));
+
+        let mut vectors = valid_vectors();
+        vectors.motion.as_mut().unwrap().sample_count = -1;
+        assert!(matches!(validate_trust_vectors(&vectors), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_validate_trust_vectors_rejects_bad_interruption_durations() {
+        for duration in [-1.0, f64::NAN, f64::INFINITY] {
+            let mut vectors = valid_vectors();
+            let mut continuity = continuity(&["phoneCall", "siri"]);
+            continuity.interruption_events[0].duration_seconds = Some(2.0);
+            continuity.interruption_events[1].duration_seconds = Some(duration);
+            vectors.continuity = Some(continuity);
+            assert!(matches!(validate_trust_vectors(&vectors), Err(VerifyError::ManifestMalformed)), "{}", duration);
+        }
+
+        // Zero and unmeasured durations are fine
+        let mut vectors = valid_vectors();
+        let mut continuity = continuity(&["phoneCall", "siri"]);
+        continuity.interruption_events[0].duration_seconds = Some(0.0);
+        vectors.continuity = Some(continuity);
+        assert!(validate_trust_vectors(&vectors).is_ok());
+    }
+
+    #[test]
+    fn test_validate_trust_vectors_rejects_non_positive_accuracy() {
+        let mut vectors = valid_vectors();
+        vectors.location.as_mut().unwrap().start.accuracy = 0.0;
+        assert!(matches!(validate_trust_vectors(&vectors), Err(VerifyError::ManifestMalformed)));
+
+        let mut vectors = valid_vectors();
+        vectors.location.as_mut().unwrap().end.accuracy = -10.0;
+        assert!(matches!(validate_trust_vectors(&vectors), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_decompress_manifest_passes_plain_json_through() {
+        let json = br#"{"a":1}"#;
+        let result = decompress_manifest(json).unwrap();
+        assert!(matches!(result, Cow::Borrowed(_)));
+        assert_eq!(&*result, json);
+    }
+
+    #[test]
+    fn test_decompress_manifest_inflates_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let json = br#"{"b":2,"a":1}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(&*decompress_manifest(&compressed).unwrap(), json);
+        assert_eq!(
+            compute_canonical_hash_from_bytes(&compressed).unwrap(),
+            compute_canonical_hash_from_bytes(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decompress_manifest_rejects_corrupt_gzip() {
+        let corrupt = [0x1f, 0x8b, 0x08, 0x00, 0xde, 0xad];
+        assert!(matches!(decompress_manifest(&corrupt), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_decompress_manifest_stops_at_limit() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let json = vec![b' '; 1000];
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&json).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(decompress_manifest_with_limit(&compressed, 1000).unwrap().len(), 1000);
+        assert!(matches!(decompress_manifest_with_limit(&compressed, 999), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_canonicalize_preserves_precomposed_characters() {
+        // "Café" with precomposed U+00E9
+        let json: Value = serde_json::json!({ "reason": "Caf\u{e9}" });
+        let canonical = canonicalize_json(&json).unwrap();
+        assert_eq!(canonical.as_bytes(), "{\"reason\":\"Caf\u{e9}\"}".as_bytes());
+    }
+
+    #[test]
+    fn test_canonicalize_preserves_combining_characters() {
+        // "Café" as "e" followed by combining acute accent U+0301
+        let json: Value = serde_json::json!({ "reason": "Cafe\u{301}" });
+        let canonical = canonicalize_json(&json).unwrap();
+        assert_eq!(canonical.as_bytes(), "{\"reason\":\"Cafe\u{301}\"}".as_bytes());
+    }
+
+    #[test]
+    fn test_canonical_hash_distinguishes_nfc_and_nfd() {
+        let nfc = "{\"timeZone\":\"Caf\u{e9}\",\"signature\":\"x\"}";
+        let nfd = "{\"timeZone\":\"Cafe\u{301}\",\"signature\":\"x\"}";
+        assert_ne!(
+            compute_canonical_hash_from_bytes(nfc.as_bytes()).unwrap(),
+            compute_canonical_hash_from_bytes(nfd.as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_struct_canonical_hash_matches_bytes() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/cosigned_bundle/manifest.json");
+        let mut manifest = SignedAudioManifest::from_json(&std::fs::read(path).unwrap()).unwrap();
+        let hash_of_serialized = |m: &SignedAudioManifest| compute_canonical_hash_from_bytes(&serde_json::to_vec(m).unwrap()).unwrap();
+
+        // Co-signatures aren't covered
+        assert!(!manifest.signatures.is_empty());
+        let hash = manifest.compute_canonical_hash().unwrap();
+        assert_eq!(hash, hash_of_serialized(&manifest));
+        let without_cosignatures = SignedAudioManifest { signatures: Vec::new(), ..manifest.clone() };
+        assert_eq!(without_cosignatures.compute_canonical_hash().unwrap(), hash);
+
+        // The declared canonicalization is used
+        manifest.canonicalization = Some(Canonicalization::Jcs);
+        assert_eq!(manifest.compute_canonical_hash().unwrap(), hash_of_serialized(&manifest));
+    }
+
+    #[test]
+    fn test_detached_canonical_hash_keeps_signature_field() {
+        let json = br#"{"b":1,"a":2,"signature":"x"}"#;
+        assert_ne!(
+            compute_canonical_hash_detached(json).unwrap(),
+            compute_canonical_hash_from_bytes(json).unwrap()
+        );
+        assert_eq!(
+            compute_canonical_hash_detached(br#"{"b":1,"a":2}"#).unwrap(),
+            compute_canonical_hash_from_bytes(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_decodes_unicode_escapes_to_raw_utf8() {
+        let escaped = br#"{"reason":"Cafe\u0301"}"#;
+        let raw = "{\"reason\":\"Cafe\u{301}\"}";
+        assert_eq!(
+            compute_canonical_hash_from_bytes(escaped).unwrap(),
+            compute_canonical_hash_from_bytes(raw.as_bytes()).unwrap()
+        );
+    }
 
     #[test]
     fn test_canonicalize_simple_object() {
@@ -230,4 +1032,47 @@ mod tests {
         let canonical = canonicalize_json(&json).unwrap();
         assert_eq!(canonical, r#"{"a":"test","z":{"a":1,"b":2}}"#);
     }
+
+    #[test]
+    fn test_canonical_test_vectors() {
+        for vector in CANONICAL_TEST_VECTORS {
+            let mut value: Value = serde_json::from_str(vector.input).unwrap();
+            let map = value.as_object_mut().unwrap();
+            map.remove("signature");
+            map.remove("signatures");
+            assert_eq!(canonical_json_with_mode(&value, vector.mode).unwrap(), vector.canonical, "{}", vector.name);
+            assert_eq!(hex_encode(&sha256_bytes(vector.canonical.as_bytes())), vector.sha256, "{}", vector.name);
+            if vector.mode == Canonicalization::IosLegacy {
+                let hash = compute_canonical_hash_from_bytes(vector.input.as_bytes()).unwrap();
+                assert_eq!(hex_encode(&hash), vector.sha256, "{}", vector.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_manifest_selects_canonicalization() {
+        let input = r#"{"canonicalization":"jcs","path":"a/b","whole":5.0}"#;
+        let hash = compute_canonical_hash_from_bytes(input.as_bytes()).unwrap();
+        assert_eq!(hash, sha256_bytes(br#"{"canonicalization":"jcs","path":"a/b","whole":5}"#));
+
+        let legacy = r#"{"canonicalization":"ios-legacy","path":"a/b","whole":5.0}"#;
+        let hash = compute_canonical_hash_from_bytes(legacy.as_bytes()).unwrap();
+        assert_eq!(hash, sha256_bytes(br#"{"canonicalization":"ios-legacy","path":"a\/b","whole":5.0}"#));
+
+        let unknown = r#"{"canonicalization":"jcs-2","path":"a/b"}"#;
+        assert!(matches!(compute_canonical_hash_from_bytes(unknown.as_bytes()), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_canonicalize_rejects_deep_nesting() {
+        // The innermost value sits `depth` levels below the top-level object
+        let nested = |depth: usize| format!(r#"{{"a":1,"extra":{}1{}}}"#, "[".repeat(depth - 1), "]".repeat(depth - 1));
+
+        assert!(compute_canonical_hash_from_bytes(nested(MAX_MANIFEST_DEPTH).as_bytes()).is_ok());
+        assert!(compute_canonical_hash_from_bytes(nested(MAX_MANIFEST_DEPTH + 1).as_bytes()).is_err());
+        // Shallow enough for serde_json's own recursion limit of 128
+        let deep = nested(100);
+        assert!(serde_json::from_str::<Value>(&deep).is_ok());
+        assert!(matches!(compute_canonical_hash_from_bytes(deep.as_bytes()), Err(VerifyError::ManifestMalformed)));
+    }
 }
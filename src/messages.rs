@@ -0,0 +1,146 @@
+//! Message catalog for user-facing text.
+//!
+//! The explanatory paragraphs and the limitations section of the text output
+//! are looked up by [`MessageKey`] in a [`Catalog`], so they can be
+//! translated. English is built in. To add a language, write a table of
+//! `(MessageKey, &str)` pairs and list it in `LANGUAGES`; keys the table
+//! leaves out fall back to English.
+//!
+//! Error messages from `VerifyError`'s `Display` stay in English.
+
+/// Identifies one piece of user-facing text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKey {
+    /// Explanation shown after a hash mismatch.
+    HashMismatchDetail,
+    /// Explanation shown after an invalid signature.
+    SignatureInvalidDetail,
+    /// Explanation shown after a decryption failure.
+    DecryptionFailedDetail,
+    /// Heading of the limitations section.
+    LimitationsHeading,
+    /// Sentence introducing the limitations list.
+    LimitationsIntro,
+    LimitationSpeaker,
+    LimitationTruth,
+    LimitationConsent,
+    LimitationSynthetic,
+}
+
+/// The limitations disclaimer, in display order. Shared by the text
+/// section and the `limitations` array of JSON reports.
+pub const LIMITATIONS: [MessageKey; 4] = [
+    MessageKey::LimitationSpeaker,
+    MessageKey::LimitationTruth,
+    MessageKey::LimitationConsent,
+    MessageKey::LimitationSynthetic,
+];
+
+/// A language's messages, as `(key, text)` pairs.
+pub type MessageTable = &'static [(MessageKey, &'static str)];
+
+const ENGLISH: MessageTable = &[
+    (
+        MessageKey::HashMismatchDetail,
+        "The audio file does not match the cryptographic hash\n\
+         recorded at capture time. This recording cannot be\n\
+         verified as authentic.",
+    ),
+    (
+        MessageKey::SignatureInvalidDetail,
+        "The digital signature is invalid. The manifest may have\n\
+         been tampered with or was not created by ProofCapture.",
+    ),
+    (
+        MessageKey::DecryptionFailedDetail,
+        "Could not decrypt the sealed proof. Please check your\n\
+         password and try again.",
+    ),
+    (MessageKey::LimitationsHeading, "LIMITATIONS"),
+    (MessageKey::LimitationsIntro, "This verification proves capture integrity, NOT:"),
+    (MessageKey::LimitationSpeaker, "Who is speaking"),
+    (MessageKey::LimitationTruth, "That statements are true"),
+    (MessageKey::LimitationConsent, "Legal consent to record"),
+    (MessageKey::LimitationSynthetic, "Absence of AI-generated audio"),
+];
+
+/// Built-in languages by ISO 639-1 code.
+const LANGUAGES: &[(&str, MessageTable)] = &[("en", ENGLISH)];
+
+/// Messages for one language, falling back to English.
+#[derive(Debug, Clone, Copy)]
+pub struct Catalog {
+    messages: MessageTable,
+}
+
+impl Catalog {
+    /// The built-in English catalog.
+    pub fn english() -> Self {
+        Catalog { messages: ENGLISH }
+    }
+
+    /// A catalog backed by `messages`, e.g. one defined outside this crate.
+    pub fn new(messages: MessageTable) -> Self {
+        Catalog { messages }
+    }
+
+    /// The catalog for a language code such as `de` or a locale such as
+    /// `de_DE.UTF-8`. Unknown languages get English.
+    pub fn for_language(code: &str) -> Self {
+        let language = code.split(['_', '-', '.']).next().unwrap_or("").to_lowercase();
+        LANGUAGES
+            .iter()
+            .find(|(name, _)| *name == language)
+            .map_or_else(Self::english, |(_, messages)| Self::new(messages))
+    }
+
+    /// The limitations disclaimer in this catalog's language.
+    pub fn limitations(&self) -> Vec<&'static str> {
+        LIMITATIONS.iter().map(|&key| self.get(key)).collect()
+    }
+
+    /// The text for `key`, in English if this catalog lacks it.
+    pub fn get(&self, key: MessageKey) -> &'static str {
+        lookup(self.messages, key)
+            .or_else(|| lookup(ENGLISH, key))
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+fn lookup(messages: MessageTable, key: MessageKey) -> Option<&'static str> {
+    messages.iter().find(|(k, _)| *k == key).map(|(_, text)| *text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A partial "language" standing in for a real translation.
+    const STUB: MessageTable = &[
+        (MessageKey::LimitationsHeading, "GRENZEN"),
+        (MessageKey::LimitationSpeaker, "Wer spricht"),
+    ];
+
+    #[test]
+    fn test_stub_catalog_overrides_and_falls_back() {
+        let catalog = Catalog::new(STUB);
+        assert_eq!(catalog.get(MessageKey::LimitationsHeading), "GRENZEN");
+        assert_eq!(catalog.get(MessageKey::LimitationSpeaker), "Wer spricht");
+        assert_eq!(catalog.get(MessageKey::LimitationTruth), "That statements are true");
+    }
+
+    #[test]
+    fn test_for_language_parses_locales() {
+        let english = Catalog::english().get(MessageKey::LimitationsIntro);
+        assert_eq!(Catalog::for_language("en_US.UTF-8").get(MessageKey::LimitationsIntro), english);
+        // Not translated yet, and POSIX locales, fall back to English
+        assert_eq!(Catalog::for_language("fr").get(MessageKey::LimitationsIntro), english);
+        assert_eq!(Catalog::for_language("C").get(MessageKey::LimitationsIntro), english);
+    }
+}
@@ -0,0 +1,241 @@
+//! Memory-mapped hashing of large audio files.
+//!
+//! Available with the `mmap` cargo feature. Standard-bundle audio of at
+//! least [`MMAP_THRESHOLD_BYTES`] is verified through a read-only memory map
+//! instead of being read into memory first. Smaller files, files that can't
+//! be mapped and platforms other than Linux are read as before.
+//!
+//! Truncating a mapped file under the verifier would normally kill it with
+//! `SIGBUS` on the next access past the new end. While a file is mapped, a
+//! `SIGBUS` handler instead maps a zeroed page over the missing one and
+//! marks the mapping as faulted, and the verification then fails with
+//! `AudioFileCorrupt`. The handler stays installed once the first file is
+//! mapped, and hands faults outside the mappings to the previous handler.
+
+use std::path::Path;
+
+use crate::error::{Result, VerifyError};
+
+/// Files at least this large are mapped (16 MiB).
+pub const MMAP_THRESHOLD_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Run `verify` over the mapped contents of the file at `path`.
+///
+/// Returns `None` without calling `verify` if the file is smaller than
+/// [`MMAP_THRESHOLD_BYTES`] or can't be mapped, for the caller to read it
+/// instead. A truncation while `verify` runs gives `AudioFileCorrupt`,
+/// whatever `verify` returned.
+pub(crate) fn with_mapped<T>(path: &Path, verify: impl FnOnce(&[u8]) -> Result<T>) -> Option<Result<T>> {
+    imp::with_mapped(path, verify)
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::fs::File;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::OnceLock;
+
+    use memmap2::Mmap;
+
+    use super::*;
+
+    /// At most this many files are mapped at once; beyond it they're read.
+    const SLOTS: usize = 64;
+
+    /// An address range the `SIGBUS` handler may repair; `start` is zero
+    /// while the slot is unused.
+    struct Watched {
+        claimed: AtomicBool,
+        start: AtomicUsize,
+        end: AtomicUsize,
+        faulted: AtomicBool,
+    }
+
+    impl Watched {
+        const fn new() -> Self {
+            Watched {
+                claimed: AtomicBool::new(false),
+                start: AtomicUsize::new(0),
+                end: AtomicUsize::new(0),
+                faulted: AtomicBool::new(false),
+            }
+        }
+    }
+
+    static WATCHED: [Watched; SLOTS] = [const { Watched::new() }; SLOTS];
+
+    static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+    /// The `SIGBUS` action before ours, or `None` if ours couldn't be installed.
+    static PREVIOUS: OnceLock<Option<libc::sigaction>> = OnceLock::new();
+
+    pub(super) fn with_mapped<T>(path: &Path, verify: impl FnOnce(&[u8]) -> Result<T>) -> Option<Result<T>> {
+        let file = File::open(path).ok()?;
+        if file.metadata().ok()?.len() < MMAP_THRESHOLD_BYTES || !install_handler() {
+            return None;
+        }
+        // SAFETY: another process may change the file while it's mapped.
+        // Truncation faults are caught by on_sigbus and reported below; other
+        // writes only change the bytes verified, as they would mid-read.
+        let map = unsafe { Mmap::map(&file) }.ok()?;
+        // Declared after the map so it stops watching before the unmap
+        let slot = Slot::claim(&map)?;
+
+        let outcome = verify(&map);
+        Some(if slot.faulted() { Err(VerifyError::AudioFileCorrupt) } else { outcome })
+    }
+
+    /// A claimed [`Watched`] slot, released on drop.
+    struct Slot(&'static Watched);
+
+    impl Slot {
+        fn claim(map: &Mmap) -> Option<Slot> {
+            let watched = WATCHED
+                .iter()
+                .find(|w| w.claimed.compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed).is_ok())?;
+            let start = map.as_ptr() as usize;
+            watched.faulted.store(false, Ordering::Release);
+            watched.end.store(start + map.len(), Ordering::Release);
+            watched.start.store(start, Ordering::Release);
+            Some(Slot(watched))
+        }
+
+        fn faulted(&self) -> bool {
+            self.0.faulted.load(Ordering::Acquire)
+        }
+    }
+
+    impl Drop for Slot {
+        fn drop(&mut self) {
+            self.0.start.store(0, Ordering::Release);
+            self.0.claimed.store(false, Ordering::Release);
+        }
+    }
+
+    fn install_handler() -> bool {
+        let previous = PREVIOUS.get_or_init(|| {
+            // SAFETY: sigaction is plain data, and on_sigbus only makes
+            // async-signal-safe calls
+            unsafe {
+                PAGE_SIZE.store(libc::sysconf(libc::_SC_PAGESIZE) as usize, Ordering::Relaxed);
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_sigbus as extern "C" fn(_, _, _) as libc::sighandler_t;
+                action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut previous: libc::sigaction = std::mem::zeroed();
+                (libc::sigaction(libc::SIGBUS, &action, &mut previous) == 0).then_some(previous)
+            }
+        });
+        previous.is_some()
+    }
+
+    extern "C" fn on_sigbus(signal: libc::c_int, info: *mut libc::siginfo_t, context: *mut libc::c_void) {
+        // Only async-signal-safe calls from here on
+        let address = unsafe { (*info).si_addr() } as usize;
+        let page_size = PAGE_SIZE.load(Ordering::Relaxed);
+        for watched in &WATCHED {
+            let start = watched.start.load(Ordering::Acquire);
+            if start == 0 || !(start..watched.end.load(Ordering::Acquire)).contains(&address) {
+                continue;
+            }
+            // Replace the page past the end of file with zeros and retry the access
+            let page = address & !(page_size - 1);
+            let replaced = unsafe {
+                libc::mmap(
+                    page as *mut libc::c_void,
+                    page_size,
+                    libc::PROT_READ,
+                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_FIXED,
+                    -1,
+                    0,
+                )
+            };
+            if replaced != libc::MAP_FAILED {
+                watched.faulted.store(true, Ordering::Release);
+                return;
+            }
+        }
+        forward(signal, info, context);
+    }
+
+    /// Hand a fault that isn't ours to the previous `SIGBUS` action.
+    fn forward(signal: libc::c_int, info: *mut libc::siginfo_t, context: *mut libc::c_void) {
+        let Some(Some(previous)) = PREVIOUS.get() else {
+            return;
+        };
+        // SAFETY: the previous action's handler has the signature its flags declare
+        unsafe {
+            match previous.sa_sigaction {
+                // Restore it; the faulting access repeats and gets the default action
+                libc::SIG_DFL | libc::SIG_IGN => {
+                    libc::sigaction(libc::SIGBUS, previous, std::ptr::null_mut());
+                }
+                handler if previous.sa_flags & libc::SA_SIGINFO != 0 => {
+                    let handler: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                        std::mem::transmute(handler);
+                    handler(signal, info, context);
+                }
+                handler => {
+                    let handler: extern "C" fn(libc::c_int) = std::mem::transmute(handler);
+                    handler(signal);
+                }
+            }
+        }
+    }
+}
+
+/// Other platforms always read the file.
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    use super::*;
+
+    pub(super) fn with_mapped<T>(_path: &Path, _verify: impl FnOnce(&[u8]) -> Result<T>) -> Option<Result<T>> {
+        None
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+    use std::fs;
+
+    /// A file just over the threshold, with bytes that differ page to page.
+    fn large_file(dir: &Path) -> std::path::PathBuf {
+        let path = dir.join("recording.m4a");
+        let data: Vec<u8> = (0..MMAP_THRESHOLD_BYTES + 4096).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_small_files_are_read_instead() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small.m4a");
+        fs::write(&path, [1u8; 1024]).unwrap();
+        assert!(with_mapped(&path, |_| -> Result<()> { panic!("mapped a small file") }).is_none());
+    }
+
+    #[test]
+    fn test_large_file_hashes_as_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = large_file(dir.path());
+        let mapped = with_mapped(&path, |bytes| Ok(Sha256::digest(bytes))).unwrap().unwrap();
+        assert_eq!(mapped, Sha256::digest(fs::read(&path).unwrap()));
+    }
+
+    #[test]
+    fn test_truncation_while_mapped_is_audio_file_corrupt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = large_file(dir.path());
+        let outcome = with_mapped(&path, |bytes| {
+            fs::OpenOptions::new().write(true).open(&path).unwrap().set_len(4096).unwrap();
+            Ok(Sha256::digest(bytes))
+        });
+        assert!(matches!(outcome, Some(Err(VerifyError::AudioFileCorrupt))));
+
+        // The handler stays installed and later mappings are unaffected
+        let path = large_file(dir.path());
+        assert!(with_mapped(&path, |bytes| Ok(bytes.len())).unwrap().is_ok());
+    }
+}
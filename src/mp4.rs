@@ -0,0 +1,312 @@
+//! MP4/M4A container metadata: embedded manifests and audio properties.
+//!
+//! Embedded manifests are experimental (spec section 6.3): no ProofCapture
+//! app writes them and `pcmf` is not a registered box type, so the layout may
+//! change. A pipeline can make a recording self-contained by injecting the
+//! manifest JSON into the audio file as a `moov/udta/pcmf` box. The manifest's
+//! `audioHash` still covers the file as it was *before* injection, so the
+//! original bytes are recovered by removing the `pcmf` box and shrinking the
+//! size fields of its `udta` and `moov` ancestors by the same amount.
+//!
+//! Injection must not move media data: an injector that patches chunk
+//! offsets (`stco`/`co64`) produces a file whose original bytes can't be
+//! recovered this way.
+//!
+//! Audio properties are read from the `mvhd` and sound track `stsd` boxes,
+//! to compare with what the manifest declares.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::error::{Result, VerifyError};
+
+/// Box type holding the embedded manifest JSON.
+pub const EMBEDDED_MANIFEST_BOX: [u8; 4] = *b"pcmf";
+
+/// Manifest and pre-injection audio recovered from an MP4 file.
+#[derive(Debug)]
+pub struct EmbeddedManifest {
+    /// The manifest JSON (possibly gzip-compressed).
+    pub manifest: Vec<u8>,
+    /// The file with the manifest box removed: the bytes `audioHash` covers.
+    pub audio: Vec<u8>,
+}
+
+/// Audio properties recorded by an MP4 container, each if present.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioProperties {
+    /// Movie duration from `mvhd`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<f64>,
+    /// Sample entry type of the sound track, e.g. `"mp4a"` for AAC.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate_hz: Option<u32>,
+}
+
+/// How a box header encodes its size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SizeField {
+    /// 32-bit size at offset 0.
+    Compact,
+    /// 64-bit size at offset 8, after a 32-bit size of 1.
+    Large,
+    /// A size of 0: the box extends to the end of the file.
+    ToEnd,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BoxHeader {
+    kind: [u8; 4],
+    start: usize,
+    header_len: usize,
+    end: usize,
+    size_field: SizeField,
+}
+
+/// Find an embedded manifest in MP4 bytes, or `None` if there isn't one.
+///
+/// Malformed box structure is reported as `AudioFileCorrupt`.
+pub fn extract_embedded_manifest(data: &[u8]) -> Result<Option<EmbeddedManifest>> {
+    let Some(moov) = find_box(data, 0, data.len(), b"moov")? else {
+        return Ok(None);
+    };
+    let Some(udta) = find_box(data, moov.start + moov.header_len, moov.end, b"udta")? else {
+        return Ok(None);
+    };
+    let Some(pcmf) = find_box(data, udta.start + udta.header_len, udta.end, &EMBEDDED_MANIFEST_BOX)? else {
+        return Ok(None);
+    };
+
+    let removed = (pcmf.end - pcmf.start) as u64;
+    let mut audio = Vec::with_capacity(data.len() - removed as usize);
+    audio.extend_from_slice(&data[..pcmf.start]);
+    audio.extend_from_slice(&data[pcmf.end..]);
+    for ancestor in [moov, udta] {
+        shrink_size_field(&mut audio, ancestor, removed);
+    }
+
+    Ok(Some(EmbeddedManifest {
+        manifest: data[pcmf.start + pcmf.header_len..pcmf.end].to_vec(),
+        audio,
+    }))
+}
+
+/// Read the audio properties of MP4 bytes, or `None` if they aren't an MP4
+/// file with a `moov` box. Malformed boxes are treated as absent.
+pub fn read_audio_properties(data: &[u8]) -> Option<AudioProperties> {
+    let moov = find_box(data, 0, data.len(), b"moov").ok()??;
+    let sample_entry = sound_sample_entry(data, moov).ok().flatten();
+    let entry = sample_entry.map(|entry| payload(data, entry)).unwrap_or_default();
+    Some(AudioProperties {
+        duration_seconds: movie_duration(data, moov).ok().flatten(),
+        codec: sample_entry.map(|entry| String::from_utf8_lossy(&entry.kind).into_owned()),
+        channels: u16_at(entry, 16),
+        // 16.16 fixed point
+        sample_rate_hz: u32_at(entry, 24).map(|rate| rate >> 16),
+    })
+}
+
+/// Duration from `moov/mvhd`: its duration field over its timescale.
+fn movie_duration(data: &[u8], moov: BoxHeader) -> Result<Option<f64>> {
+    let Some(mvhd) = child(data, moov, b"mvhd")? else {
+        return Ok(None);
+    };
+    let mvhd = payload(data, mvhd);
+    // Version 1 widens the creation, modification and duration fields to 64 bits
+    let (timescale, duration) = match mvhd.first() {
+        Some(1) => (u32_at(mvhd, 20), u64_at(mvhd, 24)),
+        _ => (u32_at(mvhd, 12), u32_at(mvhd, 16).filter(|&d| d != u32::MAX).map(u64::from)),
+    };
+    Ok(match (timescale, duration) {
+        // All ones means the duration isn't known
+        (Some(timescale), Some(duration)) if timescale > 0 && duration != u64::MAX => {
+            Some(duration as f64 / timescale as f64)
+        }
+        _ => None,
+    })
+}
+
+/// The first sample entry in the `stsd` of the first track whose handler is `soun`.
+fn sound_sample_entry(data: &[u8], moov: BoxHeader) -> Result<Option<BoxHeader>> {
+    let mut offset = moov.start + moov.header_len;
+    while offset < moov.end {
+        let trak = read_header(data, offset, moov.end)?;
+        offset = trak.end;
+        if &trak.kind != b"trak" {
+            continue;
+        }
+        let Some(mdia) = child(data, trak, b"mdia")? else {
+            continue;
+        };
+        // hdlr: version and flags, pre_defined, then the handler type
+        let handler = child(data, mdia, b"hdlr")?.and_then(|hdlr| payload(data, hdlr).get(8..12));
+        if handler != Some(b"soun") {
+            continue;
+        }
+        let mut stsd = Some(mdia);
+        for kind in [b"minf", b"stbl", b"stsd"] {
+            stsd = match stsd {
+                Some(parent) => child(data, parent, kind)?,
+                None => None,
+            };
+        }
+        // stsd: version and flags, entry count, then the entries
+        return match stsd {
+            Some(stsd) if stsd.start + stsd.header_len + 8 < stsd.end => {
+                read_header(data, stsd.start + stsd.header_len + 8, stsd.end).map(Some)
+            }
+            _ => Ok(None),
+        };
+    }
+    Ok(None)
+}
+
+/// The first child box of `parent` of type `kind`.
+fn child(data: &[u8], parent: BoxHeader, kind: &[u8; 4]) -> Result<Option<BoxHeader>> {
+    find_box(data, parent.start + parent.header_len, parent.end, kind)
+}
+
+/// The bytes of `header`'s box after its header.
+fn payload(data: &[u8], header: BoxHeader) -> &[u8] {
+    &data[header.start + header.header_len..header.end]
+}
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_at(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u64_at(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// The first box of type `kind` among the sibling boxes in `data[start..end]`.
+fn find_box(data: &[u8], start: usize, end: usize, kind: &[u8; 4]) -> Result<Option<BoxHeader>> {
+    let mut offset = start;
+    while offset < end {
+        let header = read_header(data, offset, end)?;
+        if &header.kind == kind {
+            return Ok(Some(header));
+        }
+        offset = header.end;
+    }
+    Ok(None)
+}
+
+fn read_header(data: &[u8], start: usize, parent_end: usize) -> Result<BoxHeader> {
+    let field = |at: usize, len: usize| data.get(at..at + len).filter(|_| at + len <= parent_end);
+    let corrupt = || VerifyError::AudioFileCorrupt;
+
+    let size32 = u32::from_be_bytes(field(start, 4).ok_or_else(corrupt)?.try_into().unwrap());
+    let kind: [u8; 4] = field(start + 4, 4).ok_or_else(corrupt)?.try_into().unwrap();
+
+    let (size, header_len, size_field) = match size32 {
+        0 => ((parent_end - start) as u64, 8, SizeField::ToEnd),
+        1 => {
+            let size64 = u64::from_be_bytes(field(start + 8, 8).ok_or_else(corrupt)?.try_into().unwrap());
+            (size64, 16, SizeField::Large)
+        }
+        n => (n as u64, 8, SizeField::Compact),
+    };
+
+    let end = usize::try_from(size)
+        .ok()
+        .and_then(|size| start.checked_add(size))
+        .filter(|&end| end <= parent_end && end >= start + header_len)
+        .ok_or_else(corrupt)?;
+
+    Ok(BoxHeader { kind, start, header_len, end, size_field })
+}
+
+/// Reduce the recorded size of `header` (which precedes the removed range,
+/// so its offset is unchanged) by `removed` bytes.
+fn shrink_size_field(data: &mut [u8], header: BoxHeader, removed: u64) {
+    let size = (header.end - header.start) as u64 - removed;
+    match header.size_field {
+        SizeField::Compact => {
+            data[header.start..header.start + 4].copy_from_slice(&(size as u32).to_be_bytes())
+        }
+        SizeField::Large => data[header.start + 8..header.start + 16].copy_from_slice(&size.to_be_bytes()),
+        SizeField::ToEnd => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut out = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn sample_file(udta_payload: &[u8]) -> Vec<u8> {
+        let moov = mp4_box(b"moov", &[mp4_box(b"mvhd", &[0; 12]), mp4_box(b"udta", udta_payload)].concat());
+        [mp4_box(b"ftyp", b"M4A \0\0\0\0"), mp4_box(b"mdat", &[7; 32]), moov].concat()
+    }
+
+    #[test]
+    fn test_extract_restores_pre_injection_bytes() {
+        let name = mp4_box(b"name", b"take");
+        let original = sample_file(&name);
+        let injected = sample_file(&[name.clone(), mp4_box(b"pcmf", b"{\"a\":1}")].concat());
+
+        let embedded = extract_embedded_manifest(&injected).unwrap().unwrap();
+        assert_eq!(embedded.manifest, b"{\"a\":1}");
+        assert_eq!(embedded.audio, original);
+    }
+
+    #[test]
+    fn test_extract_without_manifest_box() {
+        assert!(extract_embedded_manifest(&sample_file(&[])).unwrap().is_none());
+        assert!(extract_embedded_manifest(&[]).unwrap().is_none());
+    }
+
+    /// An AAC file of `duration` units at `timescale` with one stereo 44.1 kHz sound track.
+    fn aac_file(timescale: u32, duration: u32) -> Vec<u8> {
+        let mvhd = [[0; 12].as_slice(), &timescale.to_be_bytes(), &duration.to_be_bytes(), &[0; 80]].concat();
+        let hdlr = [[0; 8].as_slice(), b"soun", &[0; 13]].concat();
+        let entry = [[0; 16].as_slice(), &2u16.to_be_bytes(), &[0; 6], &(44_100u32 << 16).to_be_bytes()].concat();
+        let stsd = [[0, 0, 0, 0, 0, 0, 0, 1].as_slice(), &mp4_box(b"mp4a", &entry)].concat();
+        let stbl = mp4_box(b"stbl", &mp4_box(b"stsd", &stsd));
+        let mdia = mp4_box(b"mdia", &[mp4_box(b"hdlr", &hdlr), mp4_box(b"minf", &stbl)].concat());
+        let moov = mp4_box(b"moov", &[mp4_box(b"mvhd", &mvhd), mp4_box(b"trak", &mdia)].concat());
+        [mp4_box(b"ftyp", b"M4A \0\0\0\0"), moov, mp4_box(b"mdat", &[7; 32])].concat()
+    }
+
+    #[test]
+    fn test_read_audio_properties() {
+        let properties = read_audio_properties(&aac_file(44_100, 529_200)).unwrap();
+        assert_eq!(
+            properties,
+            AudioProperties {
+                duration_seconds: Some(12.0),
+                codec: Some("mp4a".to_string()),
+                channels: Some(2),
+                sample_rate_hz: Some(44_100),
+            }
+        );
+
+        // No sound track, and an unknown duration
+        let properties = read_audio_properties(&sample_file(&[])).unwrap();
+        assert_eq!(properties, AudioProperties::default());
+        assert_eq!(read_audio_properties(&[0; 64]), None);
+    }
+
+    #[test]
+    fn test_extract_rejects_overlong_box() {
+        let mut file = sample_file(&mp4_box(b"pcmf", b"{}"));
+        file[0..4].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(extract_embedded_manifest(&file), Err(VerifyError::AudioFileCorrupt)));
+    }
+}
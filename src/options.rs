@@ -0,0 +1,342 @@
+//! Verification options.
+//!
+//! Opt-in behaviour for library callers, configured through a builder.
+//! `VerifyOptions::default()` matches the behaviour of the plain
+//! `verify_*` functions.
+
+use std::sync::Arc;
+
+use time::Duration;
+
+use crate::cache::ResultCache;
+use crate::clock::{Clock, SystemClock};
+use crate::crypto::{hex_encode, sha256_bytes, KdfCache};
+use crate::manifest::{CURRENT_SCHEMA_VERSION, DEFAULT_MAX_MANIFEST_BYTES};
+use crate::registry::HashRegistry;
+use crate::sealed::NonceTracker;
+use crate::trust::{TrustPolicy, TrustVector};
+use crate::warnings::CheckFn;
+
+/// Options controlling how verification is performed.
+///
+/// Cloning is cheap; clones share any caches they hold.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use proofcapture_cli::verify::verify_sealed_bundle_with_options;
+/// use proofcapture_cli::VerifyOptions;
+///
+/// let options = VerifyOptions::new().with_kdf_cache(16);
+///
+/// for path in ["a.proofcapture", "b.proofcapture"] {
+///     let result = verify_sealed_bundle_with_options(Path::new(path), "team-password", &options);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    kdf_cache: Option<Arc<KdfCache>>,
+    result_cache: Option<Arc<ResultCache>>,
+    required_vectors: Vec<TrustVector>,
+    require_full_context: bool,
+    max_interruptions: Option<usize>,
+    hash_registry: Option<HashRegistry>,
+    reject_duplicates: bool,
+    follow_audio_refs: bool,
+    clock: Option<Arc<dyn Clock>>,
+    nonce_tracker: Option<Arc<NonceTracker>>,
+    checks: Vec<CheckFn>,
+    allowed_schema_version: Option<i32>,
+    trust_policy: TrustPolicy,
+    max_age: Option<Duration>,
+    max_manifest_bytes: Option<usize>,
+    read_retries: u32,
+    trusted_cosigners: Vec<String>,
+}
+
+impl VerifyOptions {
+    /// Creates options with every opt-in behaviour disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables a bounded cache of PBKDF2-derived keys holding up to `capacity` entries.
+    ///
+    /// This trades memory safety of key material for speed; see [`KdfCache`]
+    /// before enabling it.
+    pub fn with_kdf_cache(self, capacity: usize) -> Self {
+        self.with_shared_kdf_cache(Arc::new(KdfCache::new(capacity)))
+    }
+
+    /// Uses an existing KDF cache, e.g. one shared between several option sets.
+    pub fn with_shared_kdf_cache(mut self, cache: Arc<KdfCache>) -> Self {
+        self.kdf_cache = Some(cache);
+        self
+    }
+
+    /// The KDF cache, if enabled.
+    pub fn kdf_cache(&self) -> Option<&KdfCache> {
+        self.kdf_cache.as_deref()
+    }
+
+    /// Enables a bounded cache of up to `capacity` authenticated sealed and
+    /// open bundles, keyed by the SHA-256 of the bundle bytes.
+    ///
+    /// A repeat of a byte-identical bundle skips decryption and the hash and
+    /// signature checks, but is still judged under these options; see
+    /// [`ResultCache`] for what runs again.
+    pub fn with_result_cache(self, capacity: usize) -> Self {
+        self.with_shared_result_cache(Arc::new(ResultCache::new(capacity)))
+    }
+
+    /// Uses an existing result cache, e.g. one shared between threads.
+    pub fn with_shared_result_cache(mut self, cache: Arc<ResultCache>) -> Self {
+        self.result_cache = Some(cache);
+        self
+    }
+
+    /// The result cache, if enabled.
+    pub fn result_cache(&self) -> Option<&ResultCache> {
+        self.result_cache.as_deref()
+    }
+
+    /// Requires the given trust vectors to be present, regardless of trust level.
+    ///
+    /// Checked after signature verification.
+    pub fn with_required_vectors(mut self, vectors: impl IntoIterator<Item = TrustVector>) -> Self {
+        self.required_vectors = vectors.into_iter().collect();
+        self
+    }
+
+    /// Trust vectors that must be present.
+    pub fn required_vectors(&self) -> &[TrustVector] {
+        &self.required_vectors
+    }
+
+    /// Requires all four trust vectors to be present.
+    ///
+    /// Checked after signature verification, like required vectors.
+    pub fn with_full_context_required(mut self, required: bool) -> Self {
+        self.require_full_context = required;
+        self
+    }
+
+    /// Whether all four trust vectors must be present.
+    pub fn requires_full_context(&self) -> bool {
+        self.require_full_context
+    }
+
+    /// Fails recordings with more than `max` continuity interruption events.
+    ///
+    /// Checked after signature verification, like required vectors.
+    pub fn with_max_interruptions(mut self, max: usize) -> Self {
+        self.max_interruptions = Some(max);
+        self
+    }
+
+    /// The most interruption events allowed, if limited.
+    pub fn max_interruptions(&self) -> Option<usize> {
+        self.max_interruptions
+    }
+
+    /// Fails recordings whose `captureEnd` is more than `max_age` before the
+    /// current time, as read from [`clock`](Self::clock).
+    ///
+    /// Checked after signature verification, like required vectors.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// The oldest a recording may be, if limited.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Computes the trust level under `policy`, e.g. to tolerate brief
+    /// interruptions at Level A.
+    pub fn with_trust_policy(mut self, policy: TrustPolicy) -> Self {
+        self.trust_policy = policy;
+        self
+    }
+
+    /// The trust level policy.
+    pub fn trust_policy(&self) -> &TrustPolicy {
+        &self.trust_policy
+    }
+
+    /// Records each verified audio hash in `registry`, warning with
+    /// `DuplicateRecording` when it was already there.
+    pub fn with_hash_registry(mut self, registry: HashRegistry) -> Self {
+        self.hash_registry = Some(registry);
+        self
+    }
+
+    /// The seen-hashes registry, if enabled.
+    pub fn hash_registry(&self) -> Option<&HashRegistry> {
+        self.hash_registry.as_ref()
+    }
+
+    /// These options without the hash registry, for checking it later.
+    pub(crate) fn without_hash_registry(mut self) -> Self {
+        self.hash_registry = None;
+        self
+    }
+
+    /// Fails duplicates found in the hash registry instead of warning.
+    pub fn with_duplicates_rejected(mut self, reject: bool) -> Self {
+        self.reject_duplicates = reject;
+        self
+    }
+
+    /// Whether duplicates fail verification.
+    pub fn rejects_duplicates(&self) -> bool {
+        self.reject_duplicates
+    }
+
+    /// Fetches the audio from the manifest's `audioRef` when no local audio
+    /// is found.
+    ///
+    /// The reference is followed before the signature is checked, so an
+    /// untrusted manifest can make the verifier read any local file or
+    /// request any URL. The audio hash check still rejects wrong content.
+    pub fn with_audio_refs_followed(mut self, follow: bool) -> Self {
+        self.follow_audio_refs = follow;
+        self
+    }
+
+    /// Whether `audioRef` is followed.
+    pub fn follows_audio_refs(&self) -> bool {
+        self.follow_audio_refs
+    }
+
+    /// Trusts co-signatures made by `public_key` (base64 raw P-256 key, as in
+    /// the manifest's `signatures` array).
+    ///
+    /// A co-signature is only reported as trusted when it verifies and its
+    /// key is pinned here; anyone can add a valid co-signature with a key of
+    /// their own.
+    pub fn with_trusted_cosigner(mut self, public_key: impl Into<String>) -> Self {
+        self.trusted_cosigners.push(public_key.into());
+        self
+    }
+
+    /// The pinned co-signer keys.
+    pub fn trusted_cosigners(&self) -> &[String] {
+        &self.trusted_cosigners
+    }
+
+    /// Records each sealed payload's nonce in `tracker`, warning with
+    /// `NonceReused` when a bundle with the same salt already used it.
+    pub fn with_nonce_tracker(mut self, tracker: Arc<NonceTracker>) -> Self {
+        self.nonce_tracker = Some(tracker);
+        self
+    }
+
+    /// The nonce tracker, if enabled.
+    pub fn nonce_tracker(&self) -> Option<&NonceTracker> {
+        self.nonce_tracker.as_deref()
+    }
+
+    /// Rejects manifests whose JSON (after decompression) exceeds `max`
+    /// bytes as `ManifestMalformed`, before parsing it.
+    pub fn with_max_manifest_bytes(mut self, max: usize) -> Self {
+        self.max_manifest_bytes = Some(max);
+        self
+    }
+
+    /// The largest manifest parsed; [`DEFAULT_MAX_MANIFEST_BYTES`] unless set.
+    pub fn max_manifest_bytes(&self) -> usize {
+        self.max_manifest_bytes.unwrap_or(DEFAULT_MAX_MANIFEST_BYTES)
+    }
+
+    /// Retries reading a bundle file up to `retries` more times, with
+    /// exponential backoff from 50 ms, when it fails with an IO error that
+    /// may be transient, e.g. on network filesystems. Verification failures
+    /// are never retried.
+    pub fn with_read_retries(mut self, retries: u32) -> Self {
+        self.read_retries = retries;
+        self
+    }
+
+    /// How many times a failed read is retried; 0 unless set.
+    pub fn read_retries(&self) -> u32 {
+        self.read_retries
+    }
+
+    /// Accepts manifests with a `schemaVersion` up to `version`, for testing
+    /// draft schemas before this release supports them.
+    ///
+    /// Results for versions above [`CURRENT_SCHEMA_VERSION`] are experimental:
+    /// fields a newer schema adds are ignored. Versions already supported
+    /// are always accepted.
+    pub fn with_allowed_schema_version(mut self, version: i32) -> Self {
+        self.allowed_schema_version = Some(version);
+        self
+    }
+
+    /// The highest `schemaVersion` accepted.
+    pub fn max_schema_version(&self) -> i32 {
+        self.allowed_schema_version.map_or(CURRENT_SCHEMA_VERSION, |v| v.max(CURRENT_SCHEMA_VERSION))
+    }
+
+    /// Adds a custom check, e.g. an organisation's own geofence policy.
+    ///
+    /// Checks run only once the recording has verified, after the built-in
+    /// warnings and duplicate detection, in the order they were added. They
+    /// can only add warnings (typically [`Warning::Custom`]); they can't
+    /// fail or pass a recording, though `--strict`-style callers may treat
+    /// any warning as failure.
+    ///
+    /// [`Warning::Custom`]: crate::warnings::Warning::Custom
+    pub fn with_check(mut self, check: CheckFn) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Custom checks, in evaluation order.
+    pub fn checks(&self) -> &[CheckFn] {
+        &self.checks
+    }
+
+    /// Hex SHA-256 over the options that decide a verification's outcome, to
+    /// tell an outcome recorded under other options from one under these.
+    ///
+    /// Caches, the clock, read retries and the nonce tracker leave outcomes
+    /// unchanged and aren't included. Nor is the maximum age, which depends
+    /// on when it's checked, or custom checks, which can't be compared.
+    pub(crate) fn policy_fingerprint(&self) -> String {
+        let mut required_vectors: Vec<&str> = self.required_vectors.iter().map(|v| v.name()).collect();
+        required_vectors.sort_unstable();
+        let mut trusted_cosigners = self.trusted_cosigners.clone();
+        trusted_cosigners.sort_unstable();
+        let policy = serde_json::json!({
+            "requiredVectors": required_vectors,
+            "requireFullContext": self.require_full_context,
+            "maxInterruptions": self.max_interruptions,
+            "maxInterruptionSeconds": self.trust_policy.max_interruption_seconds,
+            "hashRegistry": self.hash_registry.as_ref().map(|r| r.path().to_string_lossy()),
+            "rejectDuplicates": self.reject_duplicates,
+            "followAudioRefs": self.follow_audio_refs,
+            "trustedCosigners": trusted_cosigners,
+            "maxSchemaVersion": self.max_schema_version(),
+            "maxManifestBytes": self.max_manifest_bytes(),
+        });
+        hex_encode(&sha256_bytes(policy.to_string().as_bytes()))
+    }
+
+    /// Reads the current time from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// The clock to read the current time from.
+    pub fn clock(&self) -> &dyn Clock {
+        match &self.clock {
+            Some(clock) => clock.as_ref(),
+            None => &SystemClock,
+        }
+    }
+}
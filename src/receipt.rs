@@ -0,0 +1,254 @@
+//! Signed verification receipts.
+//!
+//! A receipt attests "this verifier checked bundle X at time T with result R".
+//! It is signed with a P-256 key controlled by the verifier operator, using
+//! the same scheme as manifests: the signature covers the SHA-256 of the
+//! canonical JSON of every field except `signature`.
+//!
+//! Operator keys are stored like iOS device keys: the private key file holds
+//! the base64-encoded raw 32-byte scalar, and public keys are the base64 raw
+//! 64-byte x||y coordinates.
+
+use std::fs;
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use p256::ecdsa::{signature::Signer, Signature, SigningKey, VerifyingKey};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+
+use crate::clock::Clock;
+use crate::crypto::{decode_base64, hex_encode, parse_public_key, parse_signature, raw_public_key, verify_signature};
+use crate::error::{Result, VerifyError};
+use crate::manifest::compute_canonical_hash_from_bytes;
+use crate::verify::VerificationResult;
+
+/// Current receipt format version.
+pub const CURRENT_RECEIPT_VERSION: i32 = 1;
+
+/// A signed, tamper-evident record of a successful verification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReceipt {
+    pub receipt_version: i32,
+    /// Base64 SHA-256 of the audio, as recorded in the manifest.
+    pub audio_hash: String,
+    /// Hex SHA-256 of the canonical manifest (the signed content).
+    pub manifest_hash: String,
+    pub trust_level: String,
+    /// ISO-8601 timestamp of the verification.
+    pub verified_at: String,
+    /// Base64 raw 64-byte operator public key.
+    pub operator_public_key: String,
+    /// Base64 raw 64-byte r||s signature over the other fields.
+    pub signature: String,
+}
+
+impl VerificationReceipt {
+    /// Issue a receipt for a verification result, timestamped with `clock`'s current time.
+    pub fn issue(result: &VerificationResult, signing_key: &SigningKey, clock: &dyn Clock) -> Result<Self> {
+        let verified_at = clock
+            .now()
+            .format(&Rfc3339)
+            .map_err(|_| VerifyError::ReceiptInvalid)?;
+        Self::issue_at(result, signing_key, &verified_at)
+    }
+
+    /// Issue a receipt for a verification result with an explicit timestamp.
+    pub fn issue_at(result: &VerificationResult, signing_key: &SigningKey, verified_at: &str) -> Result<Self> {
+        let mut receipt = VerificationReceipt {
+            receipt_version: CURRENT_RECEIPT_VERSION,
+            audio_hash: result.manifest.audio_hash.clone(),
+            manifest_hash: hex_encode(&result.manifest_hash),
+            trust_level: result.trust_level.display_name().to_string(),
+            verified_at: verified_at.to_string(),
+            operator_public_key: encode_public_key(signing_key.verifying_key()),
+            signature: String::new(),
+        };
+
+        let hash = receipt.canonical_hash()?;
+        let signature: Signature = signing_key.sign(&hash);
+        receipt.signature = BASE64.encode(signature.to_bytes());
+
+        Ok(receipt)
+    }
+
+    /// Parse a receipt from JSON bytes.
+    pub fn from_json(json_bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(json_bytes).map_err(|_| VerifyError::ReceiptInvalid)
+    }
+
+    /// Load a receipt from a sidecar JSON file.
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::from_json(&fs::read(path)?)
+    }
+
+    /// Check the receipt was signed by `operator_key`.
+    pub fn verify(&self, operator_key: &VerifyingKey) -> Result<()> {
+        if self.operator_public_key != encode_public_key(operator_key) {
+            return Err(VerifyError::ReceiptInvalid);
+        }
+
+        let signature_bytes = decode_base64(&self.signature).map_err(|_| VerifyError::ReceiptInvalid)?;
+        let signature = parse_signature(&signature_bytes).map_err(|_| VerifyError::ReceiptInvalid)?;
+
+        if !verify_signature(operator_key, &self.canonical_hash()?, &signature) {
+            return Err(VerifyError::ReceiptInvalid);
+        }
+        Ok(())
+    }
+
+    /// Check the receipt is for the bundle behind `result`: its audio and
+    /// manifest hashes must equal the freshly computed ones.
+    ///
+    /// Doesn't check the signature; see [`verify_for`](Self::verify_for).
+    pub fn check_matches(&self, result: &VerificationResult) -> Result<()> {
+        if self.audio_hash != result.manifest.audio_hash {
+            return Err(VerifyError::ReceiptStale { field: "audioHash".to_string() });
+        }
+        if self.manifest_hash != hex_encode(&result.manifest_hash) {
+            return Err(VerifyError::ReceiptStale { field: "manifestHash".to_string() });
+        }
+        Ok(())
+    }
+
+    /// Check the receipt was signed by `operator_key` and is for the bundle
+    /// behind `result`.
+    pub fn verify_for(&self, operator_key: &VerifyingKey, result: &VerificationResult) -> Result<()> {
+        self.verify(operator_key)?;
+        self.check_matches(result)
+    }
+
+    /// SHA-256 of the canonical JSON of every field except `signature`.
+    fn canonical_hash(&self) -> Result<[u8; 32]> {
+        let json = serde_json::to_vec(self).map_err(|_| VerifyError::ReceiptInvalid)?;
+        compute_canonical_hash_from_bytes(&json)
+    }
+}
+
+/// Parse an operator signing key from base64 of the raw 32-byte scalar.
+pub fn parse_signing_key(encoded: &str) -> Result<SigningKey> {
+    let bytes = decode_base64(encoded.trim()).map_err(|_| VerifyError::ReceiptKeyInvalid)?;
+    SigningKey::from_slice(&bytes).map_err(|_| VerifyError::ReceiptKeyInvalid)
+}
+
+/// Load an operator signing key from a key file.
+pub fn load_signing_key(path: &Path) -> Result<SigningKey> {
+    let encoded = fs::read_to_string(path).map_err(|_| VerifyError::ReceiptKeyInvalid)?;
+    parse_signing_key(&encoded)
+}
+
+/// Parse an operator public key from base64 of the raw 64-byte x||y coordinates.
+pub fn parse_operator_public_key(encoded: &str) -> Result<VerifyingKey> {
+    let bytes = decode_base64(encoded.trim()).map_err(|_| VerifyError::ReceiptKeyInvalid)?;
+    parse_public_key(&bytes).map_err(|_| VerifyError::ReceiptKeyInvalid)
+}
+
+/// Load an operator public key from a key file.
+pub fn load_operator_public_key(path: &Path) -> Result<VerifyingKey> {
+    let encoded = fs::read_to_string(path).map_err(|_| VerifyError::ReceiptKeyInvalid)?;
+    parse_operator_public_key(&encoded)
+}
+
+/// Encode a public key as base64 raw x||y, matching the manifest format.
+fn encode_public_key(key: &VerifyingKey) -> String {
+    BASE64.encode(raw_public_key(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::verify::verify_standard_bundle;
+    use std::path::PathBuf;
+    use time::OffsetDateTime;
+
+    fn operator_key() -> SigningKey {
+        SigningKey::from_slice(&[0x42; 32]).unwrap()
+    }
+
+    fn minimal_result() -> VerificationResult {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("minimal_bundle");
+        verify_standard_bundle(&path).unwrap()
+    }
+
+    #[test]
+    fn test_receipt_reverifies_with_operator_key() {
+        let key = operator_key();
+        let result = minimal_result();
+        let receipt = VerificationReceipt::issue_at(&result, &key, "2026-02-01T12:00:00Z").unwrap();
+
+        assert_eq!(receipt.audio_hash, result.manifest.audio_hash);
+        assert_eq!(receipt.manifest_hash, hex_encode(&result.manifest_hash));
+        assert_eq!(receipt.trust_level, "Level C");
+        assert!(receipt.verify(key.verifying_key()).is_ok());
+    }
+
+    #[test]
+    fn test_receipt_survives_json_round_trip() {
+        let key = operator_key();
+        let clock = FixedClock::new(OffsetDateTime::parse("2026-02-01T12:00:00Z", &Rfc3339).unwrap());
+        let receipt = VerificationReceipt::issue(&minimal_result(), &key, &clock).unwrap();
+        assert_eq!(receipt.verified_at, "2026-02-01T12:00:00Z");
+
+        let json = serde_json::to_vec_pretty(&receipt).unwrap();
+        let parsed = VerificationReceipt::from_json(&json).unwrap();
+
+        assert_eq!(parsed, receipt);
+        assert!(parsed.verify(key.verifying_key()).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_receipt_fails() {
+        let key = operator_key();
+        let mut receipt = VerificationReceipt::issue_at(&minimal_result(), &key, "2026-02-01T12:00:00Z").unwrap();
+        receipt.trust_level = "Level A".to_string();
+
+        assert!(matches!(receipt.verify(key.verifying_key()), Err(VerifyError::ReceiptInvalid)));
+    }
+
+    #[test]
+    fn test_receipt_fails_with_other_operator_key() {
+        let receipt = VerificationReceipt::issue_at(&minimal_result(), &operator_key(), "2026-02-01T12:00:00Z").unwrap();
+        let other = SigningKey::from_slice(&[0x24; 32]).unwrap();
+
+        assert!(matches!(receipt.verify(other.verifying_key()), Err(VerifyError::ReceiptInvalid)));
+    }
+
+    #[test]
+    fn test_receipt_matches_only_its_own_bundle() {
+        let key = operator_key();
+        let receipt = VerificationReceipt::issue_at(&minimal_result(), &key, "2026-02-01T12:00:00Z").unwrap();
+        assert!(receipt.verify_for(key.verifying_key(), &minimal_result()).is_ok());
+
+        // Authentic, but issued for a different bundle
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("full_bundle");
+        let other = verify_standard_bundle(&path).unwrap();
+        assert!(matches!(
+            receipt.verify_for(key.verifying_key(), &other),
+            Err(VerifyError::ReceiptStale { field }) if field == "audioHash"
+        ));
+
+        // Same audio, re-signed manifest
+        let mut resigned = minimal_result();
+        resigned.manifest_hash[0] ^= 1;
+        assert!(matches!(
+            receipt.check_matches(&resigned),
+            Err(VerifyError::ReceiptStale { field }) if field == "manifestHash"
+        ));
+    }
+
+    #[test]
+    fn test_parse_signing_key() {
+        let encoded = format!("{}\n", BASE64.encode([0x42; 32]));
+        let key = parse_signing_key(&encoded).unwrap();
+        assert_eq!(key.to_bytes()[..], [0x42; 32]);
+
+        let public = parse_operator_public_key(&encode_public_key(key.verifying_key())).unwrap();
+        assert_eq!(&public, key.verifying_key());
+
+        assert!(matches!(parse_signing_key("not base64!"), Err(VerifyError::ReceiptKeyInvalid)));
+        assert!(matches!(parse_signing_key(&BASE64.encode([0u8; 32])), Err(VerifyError::ReceiptKeyInvalid)));
+    }
+}
@@ -0,0 +1,65 @@
+//! Registry of previously verified audio hashes.
+//!
+//! Catches the same recording being submitted more than once, possibly under
+//! different names. The registry is a text file with one base64 audio hash
+//! per line. Checking and recording a hash happen under one exclusive lock,
+//! so parallel runs sharing a registry can't both treat a hash as new.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+/// A file of audio hashes already seen.
+#[derive(Debug, Clone)]
+pub struct HashRegistry {
+    path: PathBuf,
+}
+
+impl HashRegistry {
+    /// Use the registry at `path`; it's created on the first registration.
+    pub fn new(path: &Path) -> Self {
+        HashRegistry { path: path.to_path_buf() }
+    }
+
+    /// The registry file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record `audio_hash`, returning whether it was already registered.
+    pub fn register(&self, audio_hash: &str) -> Result<bool> {
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(&self.path)?;
+        file.lock()?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        if contents.lines().any(|line| line.trim() == audio_hash) {
+            return Ok(true);
+        }
+
+        // Keep the new hash on its own line even if the last write was cut short
+        let separator = if contents.is_empty() || contents.ends_with('\n') { "" } else { "\n" };
+        file.write_all(format!("{}{}\n", separator, audio_hash).as_bytes())?;
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_reports_first_seen_and_duplicate() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = HashRegistry::new(&dir.path().join("seen.txt"));
+
+        assert!(!registry.register("qPnjva3YtGcUFJ6issPTOgMi2omJ+ORprEIyCa0eVMY=").unwrap());
+        assert!(registry.register("qPnjva3YtGcUFJ6issPTOgMi2omJ+ORprEIyCa0eVMY=").unwrap());
+        assert!(!registry.register("47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=").unwrap());
+
+        let contents = std::fs::read_to_string(dir.path().join("seen.txt")).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+    }
+}
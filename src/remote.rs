@@ -0,0 +1,231 @@
+//! Verification of bundles referenced by URL.
+//!
+//! Available with the `remote` cargo feature. Downloads are held in memory
+//! and never written to disk, and are capped at a maximum size.
+//!
+//! Both `http://` and `https://` URLs are supported; TLS is provided by
+//! rustls with the Mozilla root store bundled at build time, so no system
+//! certificate configuration is needed.
+
+use std::io::Read;
+use std::time::Duration;
+
+use crate::error::{Result, VerifyError};
+use crate::options::VerifyOptions;
+use crate::verify::{verify_open_bytes, verify_sealed_bytes, VerificationResult};
+
+/// Default cap on downloaded bundle size (256 MiB).
+pub const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Time allowed to establish the connection, including the TLS handshake.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time allowed for any single read from the server to make progress.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Returns true if `s` looks like a URL rather than a filesystem path.
+pub fn is_url(s: &str) -> bool {
+    s.starts_with("http://") || s.starts_with("https://")
+}
+
+/// Returns true if the URL points at a sealed (.proofcapture) bundle.
+pub fn is_sealed_url(url: &str) -> bool {
+    url_path_extension(url) == Some("proofcapture")
+}
+
+/// Download a bundle and verify it, dispatching on the URL's extension.
+///
+/// `.proofcapture` URLs require a password and fail with `PasswordRequired`
+/// without one; `.proofbundle` URLs are verified
+/// as open bundles. Standard bundles are directories and can't be fetched.
+pub fn verify_url(
+    url: &str,
+    password: Option<&str>,
+    max_bytes: u64,
+    options: &VerifyOptions,
+) -> Result<VerificationResult> {
+    match url_path_extension(url) {
+        Some("proofcapture") => {
+            let password = password.ok_or(VerifyError::PasswordRequired)?;
+            let bytes = fetch(url, max_bytes)?;
+            verify_sealed_bytes(&bytes, password, options)
+        }
+        Some("proofbundle") => {
+            let bytes = fetch(url, max_bytes)?;
+            verify_open_bytes(&bytes, options)
+        }
+        _ => Err(VerifyError::Network(
+            "URL must point to a .proofcapture or .proofbundle file".to_string(),
+        )),
+    }
+}
+
+/// Fetch the body of `url` into memory, failing if it exceeds `max_bytes`.
+pub fn fetch(url: &str, max_bytes: u64) -> Result<Vec<u8>> {
+    fetch_with_timeouts(url, max_bytes, CONNECT_TIMEOUT, READ_TIMEOUT)
+}
+
+fn fetch_with_timeouts(url: &str, max_bytes: u64, connect: Duration, read: Duration) -> Result<Vec<u8>> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(connect)
+        .timeout_read(read)
+        .user_agent(concat!("proofcapture-cli/", env!("CARGO_PKG_VERSION")))
+        .build();
+
+    let response = agent.get(url).call().map_err(|e| match e {
+        ureq::Error::Status(status, _) => VerifyError::Network(format!("Server responded with HTTP {}", status)),
+        ureq::Error::Transport(transport) => VerifyError::Network(transport.to_string()),
+    })?;
+
+    let content_length = response
+        .header("Content-Length")
+        .and_then(|len| len.parse::<u64>().ok());
+    if content_length.is_some_and(|len| len > max_bytes) {
+        return Err(too_large(max_bytes));
+    }
+
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(max_bytes + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| VerifyError::Network(e.to_string()))?;
+    if body.len() as u64 > max_bytes {
+        return Err(too_large(max_bytes));
+    }
+    if content_length.is_some_and(|len| body.len() as u64 != len) {
+        return Err(VerifyError::Network("Download was truncated".to_string()));
+    }
+
+    Ok(body)
+}
+
+/// Extension of the URL's path, ignoring any query string or fragment.
+fn url_path_extension(url: &str) -> Option<&str> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let filename = path.rsplit('/').next()?;
+    filename.rsplit_once('.').map(|(_, ext)| ext)
+}
+
+fn too_large(max_bytes: u64) -> VerifyError {
+    VerifyError::Network(format!("Download exceeds maximum size of {} bytes", max_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread;
+
+    /// Serve a single canned HTTP response on a local port and return the base URL.
+    fn serve_once(response: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            // Drain the request headers
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            stream.write_all(&response).unwrap();
+        });
+        format!("http://{}", address)
+    }
+
+    fn ok_response(body: &[u8]) -> Vec<u8> {
+        let mut response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len()).into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("http://example.com/a.proofcapture"));
+        assert!(is_url("https://example.com/a.proofcapture"));
+        assert!(!is_url("./evidence.proofcapture"));
+    }
+
+    #[test]
+    fn test_url_path_extension_ignores_query() {
+        assert_eq!(url_path_extension("http://h/e.proofcapture?token=abc"), Some("proofcapture"));
+        assert_eq!(url_path_extension("http://h/dir/e.proofbundle#x"), Some("proofbundle"));
+        assert_eq!(url_path_extension("http://h/dir/"), None);
+    }
+
+    #[test]
+    fn test_fetch_content_length_body() {
+        let url = serve_once(ok_response(b"hello"));
+        assert_eq!(fetch(&format!("{}/file", url), 1024).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_fetch_chunked_body() {
+        let response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nhel\r\n2\r\nlo\r\n0\r\n\r\n".to_vec();
+        let url = serve_once(response);
+        assert_eq!(fetch(&format!("{}/file", url), 1024).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_fetch_http_error_status() {
+        let url = serve_once(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_vec());
+        let err = fetch(&format!("{}/missing", url), 1024).unwrap_err();
+        assert!(matches!(err, VerifyError::Network(ref msg) if msg.contains("404")));
+    }
+
+    #[test]
+    fn test_fetch_enforces_max_size() {
+        let url = serve_once(ok_response(&[0u8; 64]));
+        let err = fetch(&format!("{}/big", url), 16).unwrap_err();
+        assert!(matches!(err, VerifyError::Network(ref msg) if msg.contains("maximum size")));
+    }
+
+    #[test]
+    fn test_fetch_times_out_on_silent_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/slow", listener.local_addr().unwrap());
+        // Accept the connection and hold it open without ever responding
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            thread::sleep(Duration::from_secs(2));
+            drop(stream);
+        });
+
+        let err = fetch_with_timeouts(&url, 1024, CONNECT_TIMEOUT, Duration::from_millis(200)).unwrap_err();
+        assert!(matches!(err, VerifyError::Network(_)));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_verify_sealed_url_without_password() {
+        let err = verify_url(
+            "http://127.0.0.1:9/evidence.proofcapture",
+            None,
+            DEFAULT_MAX_DOWNLOAD_BYTES,
+            &VerifyOptions::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VerifyError::PasswordRequired));
+    }
+
+    #[test]
+    fn test_verify_url_sealed_bundle() {
+        let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("sealed_test.proofcapture");
+        let url = serve_once(ok_response(&std::fs::read(fixture).unwrap()));
+
+        let result = verify_url(
+            &format!("{}/evidence.proofcapture", url),
+            Some("test-password-123"),
+            DEFAULT_MAX_DOWNLOAD_BYTES,
+            &VerifyOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(result.manifest.app_bundle_id, "com.bestdaylabs.proofcapture");
+    }
+}
@@ -0,0 +1,742 @@
+//! Serializable verification reports.
+//!
+//! `VerificationReport` is the JSON shape emitted by `--format json`.
+//! [`verify_bundle_to_report`] produces one for any bundle, failed or not.
+//! Its JSON Schema (draft 2020-12), from [`verification_report_schema`], is
+//! derived from the report types themselves.
+//!
+//! Reports serialize with camelCase keys; [`JsonCase`] renames them after
+//! serialization for consumers that expect snake_case.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use schemars::transform::{RecursiveTransform, Transform};
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::crypto::hex_encode;
+use crate::error::VerifyError;
+use crate::messages::Catalog;
+use crate::manifest::{
+    compute_canonical_hash_from_bytes, ClockVector, ContinuityVector, LocationVector, MotionVector,
+    SignedAudioManifest,
+};
+use crate::mp4::AudioProperties;
+use crate::options::VerifyOptions;
+use crate::receipt::VerificationReceipt;
+use crate::sealed::SealingInfo;
+use crate::verify::{load_manifest_bytes, verify_bundle, verify_sealed_bytes_keeping_manifest, CoSignerResult, VerificationResult};
+
+/// Decimal places kept in redacted coordinates (about 1 km).
+pub const REDACTED_COORDINATE_DECIMALS: i32 = 2;
+
+/// Characters of `deviceKeyId` kept when redacting.
+pub const REDACTED_KEY_ID_PREFIX: usize = 8;
+
+/// `$id` of the report schema.
+const SCHEMA_ID: &str = "https://github.com/BestDayLabs/ProofCapture_CLI/verification-report.schema.json";
+
+/// Result of a ProofCapture verification.
+///
+/// Failed reports (`status: "failed"`) carry `error` and `exitCode`, and
+/// describe the unverified manifest when it could be read.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReport {
+    #[schemars(extend("enum" = ["verified", "verified_with_warnings", "failed"]))]
+    pub status: String,
+    #[schemars(extend("enum" = ["Level A", "Level B", "Level C", "Unverified"]))]
+    pub trust_level: String,
+    pub trust_level_label: String,
+    pub schema_version: i32,
+    pub recording: RecordingReport,
+    pub identity: IdentityReport,
+    pub trust_vectors: TrustVectorsReport,
+    pub signature: String,
+    /// Hex SHA-256 of the canonical manifest, i.e. the signed content.
+    #[schemars(regex(pattern = r"^([0-9a-f]{64})?$"))]
+    pub manifest_hash: String,
+    pub warnings: Vec<WarningReport>,
+    /// Outcome of each co-signature, for manifests that carry `signatures`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub co_signers: Vec<CoSignerResult>,
+    /// What a successful verification does not prove, in English.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub limitations: Vec<String>,
+    /// Bundle version and KDF settings, for sealed bundles only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sealing: Option<SealingInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<VerificationReceipt>,
+    /// An earlier receipt, given with `--verify-receipt`, that was checked
+    /// against this verification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_receipt: Option<VerificationReceipt>,
+    /// Wall-clock time the verification took, if measured by the caller.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_duration_ms: Option<u64>,
+    /// The manifest bytes exactly as verified, base64-encoded, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_manifest_base64: Option<String>,
+    /// Files written by `--extract`, if extraction was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extraction: Option<ExtractionReport>,
+    /// Error message when `status` is `"failed"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// CLI exit code when `status` is `"failed"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1))]
+    pub exit_code: Option<i32>,
+}
+
+/// Recording details.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingReport {
+    pub capture_start: String,
+    pub capture_end: String,
+    pub duration_seconds: f64,
+    pub audio_format: String,
+    pub audio_size_bytes: i64,
+    pub audio_hash: String,
+    /// Summed interruption durations, when every event records one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_interruption_seconds: Option<f64>,
+    /// What the audio's MP4 container records, to compare with the above.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detected: Option<AudioProperties>,
+}
+
+/// Cryptographic identity of the capturing device and app.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityReport {
+    pub device_key_id: String,
+    pub public_key: String,
+    pub app_bundle_id: String,
+    pub app_version: String,
+}
+
+/// A non-fatal warning.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WarningReport {
+    #[schemars(
+        regex(pattern = r"^[a-z][a-z0-9_]*$"),
+        extend("examples" = ["device_key_id_mismatch", "unknown_audio_format", "duration_mismatch", "audio_duration_mismatch", "audio_format_mismatch", "clock_skew", "implausible_monotonic_delta", "implausible_sample_rate", "motion_duration_mismatch", "location_drift", "interruption_outside_capture", "duplicate_recording", "nonce_reused", "co_signature_invalid", "unknown_time_zone", "password_unused"])
+    )]
+    pub code: String,
+    pub message: String,
+}
+
+/// Files written when extracting a sealed bundle.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ExtractionReport {
+    pub files: Vec<ExtractedFileReport>,
+}
+
+/// One extracted file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractedFileReport {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Trust vectors, with absent vectors reported as `null`.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct TrustVectorsReport {
+    pub location: Option<LocationVector>,
+    pub motion: Option<MotionVector>,
+    pub continuity: Option<ContinuityVector>,
+    pub clock: Option<ClockVector>,
+}
+
+impl VerificationReport {
+    /// Build the report for a successful verification.
+    pub fn from_result(result: &VerificationResult) -> Self {
+        let mut report = VerificationReport {
+            status: "verified".to_string(),
+            trust_level: result.trust_level.display_name().to_string(),
+            trust_level_label: result.trust_level.label().to_string(),
+            signature: result.signature.clone(),
+            manifest_hash: hex_encode(&result.manifest_hash),
+            warnings: result
+                .warnings
+                .iter()
+                .map(|w| WarningReport {
+                    code: w.code().to_string(),
+                    message: w.to_string(),
+                })
+                .collect(),
+            co_signers: result.co_signers.clone(),
+            limitations: Catalog::english().limitations().into_iter().map(String::from).collect(),
+            sealing: result.sealing.clone(),
+            ..Self::describing(&result.manifest)
+        };
+        report.recording.detected = result.audio_properties.clone();
+        report
+    }
+
+    /// Build the report for a failed verification, describing the
+    /// (unverified) manifest in `manifest_bytes` if it parses.
+    pub fn from_error(error: &VerifyError, manifest_bytes: Option<&[u8]>) -> Self {
+        let manifest = manifest_bytes.and_then(|bytes| {
+            let manifest = SignedAudioManifest::from_json(bytes).ok()?;
+            Some((manifest, compute_canonical_hash_from_bytes(bytes).ok()?))
+        });
+        let mut report = match manifest {
+            Some((manifest, manifest_hash)) => VerificationReport {
+                manifest_hash: hex_encode(&manifest_hash),
+                ..Self::describing(&manifest)
+            },
+            None => Self::empty(),
+        };
+        report.status = "failed".to_string();
+        report.trust_level = "Unverified".to_string();
+        report.trust_level_label = "Verification failed".to_string();
+        report.error = Some(error.to_string());
+        report.exit_code = Some(error.exit_code());
+        report
+    }
+
+    /// Hide identifying detail for sharing outside the verifying team:
+    /// coordinates are rounded, the public key is masked and `deviceKeyId`
+    /// is cut to a short prefix. The verification outcome is unchanged.
+    pub fn redact(&mut self) {
+        if let Some(location) = &mut self.trust_vectors.location {
+            for snapshot in [&mut location.start, &mut location.end] {
+                snapshot.lat = redact_coordinate(snapshot.lat);
+                snapshot.lon = redact_coordinate(snapshot.lon);
+            }
+        }
+        self.identity.public_key = "[redacted]".to_string();
+        for co_signer in &mut self.co_signers {
+            co_signer.public_key = "[redacted]".to_string();
+        }
+        self.identity.device_key_id = redact_key_id(&self.identity.device_key_id);
+    }
+
+    /// The manifest's fields, with verification outcome fields left empty.
+    fn describing(m: &SignedAudioManifest) -> Self {
+        VerificationReport {
+            schema_version: m.schema_version,
+            recording: RecordingReport {
+                capture_start: m.capture_start.clone(),
+                capture_end: m.capture_end.clone(),
+                duration_seconds: m.duration_seconds,
+                audio_format: m.audio_format.clone(),
+                audio_size_bytes: m.audio_size_bytes,
+                audio_hash: m.audio_hash.clone(),
+                total_interruption_seconds: m
+                    .trust_vectors
+                    .continuity
+                    .as_ref()
+                    .filter(|c| !c.interruption_events.is_empty())
+                    .and_then(|c| c.total_interruption_seconds()),
+                detected: None,
+            },
+            identity: IdentityReport {
+                device_key_id: m.device_key_id.clone(),
+                public_key: m.public_key.clone(),
+                app_bundle_id: m.app_bundle_id.clone(),
+                app_version: m.app_version.clone(),
+            },
+            trust_vectors: TrustVectorsReport {
+                location: m.trust_vectors.location.clone(),
+                motion: m.trust_vectors.motion.clone(),
+                continuity: m.trust_vectors.continuity.clone(),
+                clock: m.trust_vectors.clock.clone(),
+            },
+            signature: m.signature.clone().unwrap_or_default(),
+            ..Self::empty()
+        }
+    }
+
+    fn empty() -> Self {
+        VerificationReport {
+            status: String::new(),
+            trust_level: String::new(),
+            trust_level_label: String::new(),
+            schema_version: 0,
+            recording: RecordingReport::default(),
+            identity: IdentityReport::default(),
+            trust_vectors: TrustVectorsReport::default(),
+            signature: String::new(),
+            manifest_hash: String::new(),
+            warnings: Vec::new(),
+            co_signers: Vec::new(),
+            limitations: Vec::new(),
+            sealing: None,
+            receipt: None,
+            checked_receipt: None,
+            verification_duration_ms: None,
+            raw_manifest_base64: None,
+            extraction: None,
+            error: None,
+            exit_code: None,
+        }
+    }
+}
+
+/// `value` rounded to [`REDACTED_COORDINATE_DECIMALS`] places.
+pub fn redact_coordinate(value: f64) -> f64 {
+    let scale = 10f64.powi(REDACTED_COORDINATE_DECIMALS);
+    (value * scale).round() / scale
+}
+
+/// The first [`REDACTED_KEY_ID_PREFIX`] characters of `key_id`, then `...`.
+pub fn redact_key_id(key_id: &str) -> String {
+    format!("{}...", key_id.chars().take(REDACTED_KEY_ID_PREFIX).collect::<String>())
+}
+
+/// Verify any bundle and build its report, whatever the outcome.
+///
+/// Both variants carry a complete report; `Err` has `status: "failed"`.
+/// Sealed bundles need `password`.
+// Both variants are the same type, so boxing only the error buys nothing
+#[allow(clippy::result_large_err)]
+pub fn verify_bundle_to_report(
+    path: &Path,
+    password: Option<&str>,
+) -> std::result::Result<VerificationReport, VerificationReport> {
+    match verify_bundle(path, password, &VerifyOptions::default()) {
+        Ok(result) => Ok(VerificationReport::from_result(&result)),
+        Err(error) => {
+            // Describe the manifest when it's readable, e.g. for a signature failure
+            let manifest_bytes = load_manifest_bytes(path, password).ok();
+            Err(VerificationReport::from_error(&error, manifest_bytes.as_deref()))
+        }
+    }
+}
+
+/// Verify sealed bundle bytes held in memory and build the report, whatever
+/// the outcome, as [`verify_bundle_to_report`] does for a path.
+///
+/// This is the entry point for embeddings without a filesystem, such as a
+/// browser evidence viewer compiled to WebAssembly.
+#[allow(clippy::result_large_err)]
+pub fn verify_sealed_bytes_to_report(
+    bundle: &[u8],
+    password: &str,
+) -> std::result::Result<VerificationReport, VerificationReport> {
+    match verify_sealed_bytes_keeping_manifest(bundle, password, &VerifyOptions::default()) {
+        (Ok(result), _) => Ok(VerificationReport::from_result(&result)),
+        (Err(error), manifest_bytes) => Err(VerificationReport::from_error(&error, manifest_bytes.as_deref())),
+    }
+}
+
+/// JSON Schema (draft 2020-12) describing [`VerificationReport`].
+///
+/// Generated from the report types as they serialize, with every nested
+/// type inlined.
+pub fn verification_report_schema() -> Value {
+    let settings = SchemaSettings::draft2020_12().for_serialize().with(|s| s.inline_subschemas = true);
+    let mut schema = settings.into_generator().into_root_schema_for::<VerificationReport>();
+    // Reports never carry keys beyond their fields, and leave out absent
+    // optional ones rather than writing null
+    RecursiveTransform(|schema: &mut Schema| {
+        let Some(object) = schema.as_object_mut() else { return };
+        let required: Vec<Value> = object.get("required").and_then(Value::as_array).cloned().unwrap_or_default();
+        let Some(Value::Object(properties)) = object.get_mut("properties") else { return };
+        for (key, property) in properties.iter_mut() {
+            if required.contains(&json!(key)) {
+                continue;
+            }
+            if let Some(Value::Array(types)) = property.get_mut("type") {
+                types.retain(|t| t != "null");
+                if let [only] = types.as_slice() {
+                    property["type"] = only.clone();
+                }
+            }
+        }
+        object.insert("additionalProperties".to_string(), Value::Bool(false));
+    })
+    .transform(&mut schema);
+    schema.insert("$id".to_string(), json!(SCHEMA_ID));
+    schema.to_value()
+}
+
+/// Key naming convention for emitted JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonCase {
+    /// `trustLevel`, as serialized. The schema describes this casing.
+    #[default]
+    Camel,
+    /// `trust_level`.
+    Snake,
+}
+
+impl JsonCase {
+    /// Rename every object key in `value`, recursively. Values are untouched.
+    pub fn apply(self, value: Value) -> Value {
+        match (self, value) {
+            (JsonCase::Camel, value) => value,
+            (JsonCase::Snake, Value::Object(map)) => Value::Object(
+                map.into_iter()
+                    .map(|(key, child)| (snake_case(&key), self.apply(child)))
+                    .collect(),
+            ),
+            (JsonCase::Snake, Value::Array(items)) => Value::Array(items.into_iter().map(|v| self.apply(v)).collect()),
+            (JsonCase::Snake, value) => value,
+        }
+    }
+}
+
+impl FromStr for JsonCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "camel" => Ok(JsonCase::Camel),
+            "snake" => Ok(JsonCase::Snake),
+            _ => Err(format!("Unknown JSON case: {}. Use 'camel' or 'snake'", s)),
+        }
+    }
+}
+
+fn snake_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for (i, c) in key.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::compute_canonical_hash_from_bytes;
+    use crate::verify::verify_standard_bundle;
+    use p256::ecdsa::SigningKey;
+    use std::path::PathBuf;
+
+    fn fixture_report(name: &str) -> VerificationReport {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name);
+        VerificationReport::from_result(&verify_standard_bundle(&path).unwrap())
+    }
+
+    /// Validates `value` against the subset of JSON Schema used above.
+    fn validate(value: &Value, schema: &Value, path: &str) -> std::result::Result<(), String> {
+        if let Some(expected) = schema.get("const") {
+            if value != expected {
+                return Err(format!("{}: expected {}", path, expected));
+            }
+        }
+        if let Some(Value::Array(options)) = schema.get("enum") {
+            if !options.contains(value) {
+                return Err(format!("{}: {} not in enum", path, value));
+            }
+        }
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => vec![],
+            };
+            let matches = types.iter().any(|t| match *t {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "number" => value.is_number(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "boolean" => value.is_boolean(),
+                "null" => value.is_null(),
+                _ => false,
+            });
+            if !matches {
+                return Err(format!("{}: {} is not of type {:?}", path, value, types));
+            }
+        }
+        if let (Some(pattern), Some(text)) = (schema.get("pattern").and_then(Value::as_str), value.as_str()) {
+            if !regex::Regex::new(pattern).unwrap().is_match(text) {
+                return Err(format!("{}: {:?} doesn't match {}", path, text, pattern));
+            }
+        }
+        if let (Some(minimum), Some(n)) = (schema.get("minimum").and_then(Value::as_f64), value.as_f64()) {
+            if n < minimum {
+                return Err(format!("{}: {} is below minimum {}", path, n, minimum));
+            }
+        }
+        if let Value::Object(map) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        return Err(format!("{}: missing required {}", path, key));
+                    }
+                }
+            }
+            for (key, child) in map {
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => validate(child, child_schema, &format!("{}.{}", path, key))?,
+                    None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                        return Err(format!("{}: unexpected property {}", path, key));
+                    }
+                    None => {}
+                }
+            }
+        }
+        if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+            for (i, item) in items.iter().enumerate() {
+                validate(item, item_schema, &format!("{}[{}]", path, i))?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_case_camel_and_snake() {
+        let report = serde_json::to_value(fixture_report("full_bundle")).unwrap();
+
+        let camel = JsonCase::Camel.apply(report.clone());
+        assert_eq!(camel, report);
+        assert!(camel["identity"].get("deviceKeyId").is_some());
+
+        let snake = JsonCase::Snake.apply(report.clone());
+        assert_eq!(snake["trust_level"], report["trustLevel"]);
+        assert_eq!(snake["identity"]["device_key_id"], report["identity"]["deviceKeyId"]);
+        assert_eq!(snake["trust_vectors"]["clock"]["wall_clock_start"], report["trustVectors"]["clock"]["wallClockStart"]);
+        assert!(snake.get("trustLevel").is_none());
+
+        fn keys(value: &Value, out: &mut Vec<String>) {
+            match value {
+                Value::Object(map) => map.iter().for_each(|(k, v)| {
+                    out.push(k.clone());
+                    keys(v, out);
+                }),
+                Value::Array(items) => items.iter().for_each(|v| keys(v, out)),
+                _ => {}
+            }
+        }
+        let mut all = Vec::new();
+        keys(&snake, &mut all);
+        assert!(all.iter().all(|k| !k.chars().any(|c| c.is_ascii_uppercase())), "{:?}", all);
+    }
+
+    #[test]
+    fn test_full_report_validates_against_schema() {
+        let report = serde_json::to_value(fixture_report("full_bundle")).unwrap();
+        validate(&report, &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_report_lists_limitations() {
+        let report = serde_json::to_value(fixture_report("minimal_bundle")).unwrap();
+        let limitations = report["limitations"].as_array().unwrap();
+        assert_eq!(limitations.len(), crate::messages::LIMITATIONS.len());
+        assert_eq!(limitations[0], "Who is speaking");
+    }
+
+    #[test]
+    fn test_minimal_report_validates_against_schema() {
+        let report = serde_json::to_value(fixture_report("minimal_bundle")).unwrap();
+        assert!(report["trustVectors"]["location"].is_null());
+        validate(&report, &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_report_with_receipt_validates_against_schema() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("minimal_bundle");
+        let result = verify_standard_bundle(&path).unwrap();
+        let key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+
+        let mut report = VerificationReport::from_result(&result);
+        report.receipt = Some(VerificationReceipt::issue(&result, &key, &crate::clock::SystemClock).unwrap());
+        report.verification_duration_ms = Some(12);
+        report.extraction = Some(ExtractionReport {
+            files: vec![ExtractedFileReport { path: "out/recording.m4a".to_string(), size_bytes: 88200 }],
+        });
+
+        validate(&serde_json::to_value(report).unwrap(), &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_report_with_every_option_set_validates_against_schema() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("full_bundle");
+        let result = verify_standard_bundle(&path).unwrap();
+        let key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let receipt = VerificationReceipt::issue(&result, &key, &crate::clock::SystemClock).unwrap();
+
+        let mut report = VerificationReport::from_result(&result);
+        report.recording.total_interruption_seconds = Some(1.5);
+        report.recording.detected = Some(AudioProperties {
+            duration_seconds: Some(30.0),
+            codec: Some("mp4a".to_string()),
+            channels: Some(1),
+            sample_rate_hz: Some(44100),
+        });
+        if let Some(continuity) = &mut report.trust_vectors.continuity {
+            continuity.interruption_events = vec![crate::manifest::InterruptionEvent {
+                timestamp: "2024-01-01T00:00:10Z".to_string(),
+                reason: "phone_call".to_string(),
+                duration_seconds: Some(1.5),
+            }];
+        }
+        report.co_signers = vec![CoSignerResult {
+            role: "witness".to_string(),
+            public_key: "AAAA".to_string(),
+            valid: true,
+            trusted: false,
+        }];
+        report.sealing = Some(SealingInfo {
+            bundle_version: 1,
+            kdf_algorithm: "pbkdf2".to_string(),
+            iterations: 600000,
+            memory_cost_kb: 0,
+            parallelism: 1,
+        });
+        report.receipt = Some(receipt.clone());
+        report.checked_receipt = Some(receipt);
+        report.verification_duration_ms = Some(12);
+        report.raw_manifest_base64 = Some("e30=".to_string());
+        report.extraction = Some(ExtractionReport {
+            files: vec![ExtractedFileReport { path: "out/recording.m4a".to_string(), size_bytes: 88200 }],
+        });
+        report.error = Some("Audio file has been modified".to_string());
+        report.exit_code = Some(2);
+
+        let schema = verification_report_schema();
+        let value = serde_json::to_value(report).unwrap();
+        for key in schema["properties"].as_object().unwrap().keys() {
+            assert!(value.get(key).is_some_and(|v| !v.is_null()), "{} not set", key);
+        }
+        validate(&value, &schema, "$").unwrap();
+    }
+
+    #[test]
+    fn test_sealed_report_includes_kdf_parameters() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("sealed_test.proofcapture");
+        let report = serde_json::to_value(verify_bundle_to_report(&path, Some("test-password-123")).unwrap()).unwrap();
+
+        assert_eq!(
+            report["sealing"],
+            json!({"bundleVersion": 1, "kdfAlgorithm": "pbkdf2", "iterations": 600000, "memoryCostKb": 0, "parallelism": 1})
+        );
+        validate(&report, &verification_report_schema(), "$").unwrap();
+        assert!(serde_json::to_value(fixture_report("full_bundle")).unwrap().get("sealing").is_none());
+    }
+
+    #[test]
+    fn test_redacted_report_hides_precise_fields() {
+        let mut report = fixture_report("full_bundle");
+        let public_key = report.identity.public_key.clone();
+        let device_key_id = report.identity.device_key_id.clone();
+        report.redact();
+
+        let location = report.trust_vectors.location.as_ref().unwrap();
+        for value in [location.start.lat, location.start.lon, location.end.lat, location.end.lon] {
+            assert_eq!(value, redact_coordinate(value));
+        }
+        assert_eq!(location.start.lat, 37.78);
+        assert_eq!(report.identity.device_key_id, format!("{}...", &device_key_id[..8]));
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains(&public_key) && !json.contains(&device_key_id));
+        assert!(!json.contains("37.775") && !json.contains("122.418"));
+        assert_eq!(report.status, "verified");
+        validate(&serde_json::to_value(report).unwrap(), &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_verify_bundle_to_report_success() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("full_bundle");
+        let report = verify_bundle_to_report(&path, None).unwrap();
+
+        assert_eq!(report.status, "verified");
+        assert_eq!(report.exit_code, None);
+        validate(&serde_json::to_value(report).unwrap(), &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_verify_bundle_to_report_failure() {
+        // Tampered audio: the manifest is still described, unverified
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("full_bundle");
+        std::fs::copy(fixture.join("manifest.json"), dir.path().join("manifest.json")).unwrap();
+        std::fs::write(dir.path().join("recording.m4a"), b"tampered").unwrap();
+
+        let report = verify_bundle_to_report(dir.path(), None).unwrap_err();
+        assert_eq!(report.status, "failed");
+        assert_eq!(report.exit_code, Some(VerifyError::HashMismatch.exit_code()));
+        assert_eq!(report.identity.app_bundle_id, "com.bestdaylabs.proofcapture");
+        assert_eq!(report.manifest_hash.len(), 64);
+        validate(&serde_json::to_value(report).unwrap(), &verification_report_schema(), "$").unwrap();
+
+        // Missing bundle: nothing to describe
+        let report = verify_bundle_to_report(&dir.path().join("missing"), None).unwrap_err();
+        assert_eq!(report.error.as_deref(), Some(VerifyError::AudioFileMissing.to_string().as_str()));
+        assert_eq!(report.manifest_hash, "");
+        validate(&serde_json::to_value(report).unwrap(), &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_verify_sealed_bytes_to_report() {
+        let bundle = std::fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("sealed_test.proofcapture")).unwrap();
+
+        let report = verify_sealed_bytes_to_report(&bundle, "test-password-123").unwrap();
+        assert_eq!(report.status, "verified");
+        assert!(report.sealing.is_some());
+
+        let report = verify_sealed_bytes_to_report(&bundle, "wrong-password").unwrap_err();
+        assert_eq!(report.exit_code, Some(VerifyError::DecryptionFailed.exit_code()));
+        validate(&serde_json::to_value(report).unwrap(), &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_every_fixture_report_validates_against_schema() {
+        let schema = verification_report_schema();
+        for entry in std::fs::read_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")).unwrap() {
+            let path = entry.unwrap().path();
+            let report = match verify_bundle_to_report(&path, Some("test-password-123")) {
+                Ok(report) | Err(report) => report,
+            };
+            let value = serde_json::to_value(&report).unwrap();
+            if let Err(e) = validate(&value, &schema, "$") {
+                panic!("{}: {}", path.display(), e);
+            }
+        }
+
+        // Warnings from custom checks have codes outside the built-in list
+        let mut report = fixture_report("full_bundle");
+        report.warnings.push(WarningReport { code: "outside_geofence".to_string(), message: "Outside".to_string() });
+        validate(&serde_json::to_value(report).unwrap(), &schema, "$").unwrap();
+    }
+
+    #[test]
+    fn test_report_with_warnings_validates_against_schema() {
+        let report = serde_json::to_value(fixture_report("skewed_bundle")).unwrap();
+        assert_eq!(report["warnings"][0]["code"], "clock_skew");
+        validate(&report, &verification_report_schema(), "$").unwrap();
+    }
+
+    #[test]
+    fn test_report_manifest_hash_matches_recomputation() {
+        let manifest_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("full_bundle")
+            .join("manifest.json");
+        let expected = compute_canonical_hash_from_bytes(&std::fs::read(manifest_path).unwrap()).unwrap();
+
+        let report = fixture_report("full_bundle");
+        assert_eq!(report.manifest_hash, hex_encode(&expected));
+        assert_eq!(report.manifest_hash.len(), 64);
+    }
+
+    #[test]
+    fn test_schema_rejects_unknown_fields() {
+        let mut report = serde_json::to_value(fixture_report("minimal_bundle")).unwrap();
+        report["unexpected"] = json!(true);
+        assert!(validate(&report, &verification_report_schema(), "$").is_err());
+    }
+}
@@ -2,14 +2,28 @@
 //!
 //! Handles decryption of password-protected .proofcapture bundles.
 
-use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Mutex;
 
-use crate::crypto::{decode_base64, decrypt_aes_gcm, derive_key_pbkdf2};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{decode_base64, decrypt_aes_gcm, derive_key_pbkdf2, sha256_matches, AES_GCM_NONCE_LEN, AES_GCM_TAG_LEN};
 use crate::error::{Result, VerifyError};
+use crate::options::VerifyOptions;
 
 /// Current supported bundle version.
 pub const CURRENT_BUNDLE_VERSION: i32 = 1;
 
+/// Shortest KDF salt accepted, in bytes. iOS seals with 32 random bytes;
+/// anything under 16 is weak or truncated.
+pub const MIN_SALT_LEN: usize = 16;
+
+/// Shortest ciphertext a real payload could have. A payload always carries
+/// a base64 manifest of several hundred bytes, so anything shorter was cut.
+pub const MIN_PLAUSIBLE_CIPHERTEXT_LEN: usize = 256;
+
 /// Outer structure of a sealed proof bundle (.proofcapture file).
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -32,13 +46,65 @@ pub struct KdfParameters {
     pub parallelism: u32,
 }
 
+/// How a sealed bundle was protected, as reported after verification.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SealingInfo {
+    pub bundle_version: i32,
+    pub kdf_algorithm: String,
+    pub iterations: u32,
+    pub memory_cost_kb: u32,
+    pub parallelism: u32,
+}
+
 /// Decrypted payload containing audio and manifest.
+///
+/// The iOS app writes the recording as `mediaData` and `mediaFilename`, as in
+/// `fixtures/sealed_test.proofcapture`; those keys are read as `audioData` and
+/// `audioFilename`. A payload carrying both spellings of one is malformed.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DecryptedPayload {
+    #[serde(alias = "mediaData")]
     pub audio_data: String,     // Base64-encoded audio bytes
     pub manifest_data: String,  // Base64-encoded manifest JSON
+    #[serde(alias = "mediaFilename")]
     pub audio_filename: String,
+    /// Base64 SHA-256 of the decoded audio, checked when decoding it.
+    #[serde(default)]
+    pub audio_sha256: Option<String>,
+    /// Base64 SHA-256 of the decoded manifest, checked when decoding it.
+    #[serde(default)]
+    pub manifest_sha256: Option<String>,
+    /// Extra media sealed alongside the audio, e.g. a photo of the scene.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Base64 detached manifest signature, for manifests without `signature`.
+    #[serde(default)]
+    pub signature_data: Option<String>,
+    /// AES-GCM nonce the payload was sealed with: the first 12 bytes of
+    /// `encryptedPayload`. Not part of the payload JSON.
+    #[serde(skip)]
+    pub nonce: Vec<u8>,
+}
+
+/// An extra media file in a decrypted payload.
+///
+/// The declared hash is checked on extraction, but attachments are not
+/// covered by the manifest signature; only the AES-GCM seal protects them.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub filename: String,
+    pub data_base64: String, // Base64-encoded file bytes
+    pub sha256: String,      // Base64-encoded SHA-256 of the file bytes
+}
+
+/// An attachment whose declared hash has been checked.
+#[derive(Debug, Clone)]
+pub struct ExtractedAttachment {
+    pub filename: String,
+    pub data: Vec<u8>,
 }
 
 impl SealedProofBundle {
@@ -57,52 +123,202 @@ impl SealedProofBundle {
         Ok(())
     }
 
+    /// The bundle version and KDF settings, for reporting.
+    pub fn sealing_info(&self) -> SealingInfo {
+        SealingInfo {
+            bundle_version: self.version,
+            kdf_algorithm: self.kdf_algorithm.clone(),
+            iterations: self.kdf_parameters.iterations,
+            memory_cost_kb: self.kdf_parameters.memory_cost_kb,
+            parallelism: self.kdf_parameters.parallelism,
+        }
+    }
+
     /// Decrypt the bundle using the provided password.
     pub fn decrypt(&self, password: &str) -> Result<DecryptedPayload> {
+        self.decrypt_with_options(password, &VerifyOptions::default())
+    }
+
+    /// Decrypt the bundle, using the KDF cache from `options` if enabled.
+    ///
+    /// An empty or whitespace-only password fails with `PasswordEmpty`
+    /// before any key derivation. Other passwords are used exactly as given.
+    pub fn decrypt_with_options(&self, password: &str, options: &VerifyOptions) -> Result<DecryptedPayload> {
         // Validate version
         self.validate_version()?;
 
+        if password.trim().is_empty() {
+            return Err(VerifyError::PasswordEmpty);
+        }
+
         // Validate KDF algorithm
         if self.kdf_algorithm != "pbkdf2" {
             // Argon2id not yet supported
             return Err(VerifyError::DecryptionFailed);
         }
 
-        // Decode salt
+        // Decode salt, rejecting one too short to be a real random salt
         let salt = decode_base64(&self.salt)?;
+        if salt.len() < MIN_SALT_LEN {
+            return Err(VerifyError::BundleCorrupted);
+        }
 
         // Derive key using PBKDF2
-        let key = derive_key_pbkdf2(password, &salt, self.kdf_parameters.iterations);
+        let iterations = self.kdf_parameters.iterations;
+        let key = match options.kdf_cache() {
+            Some(cache) => cache.derive_key(password, &salt, iterations),
+            None => derive_key_pbkdf2(password, &salt, iterations),
+        };
 
         // Decode encrypted payload
         let encrypted = decode_base64(&self.encrypted_payload)?;
 
         // Decrypt using AES-256-GCM
-        let decrypted = decrypt_aes_gcm(&key, &encrypted)?;
+        let decrypted = match decrypt_aes_gcm(&key, &encrypted) {
+            Err(VerifyError::DecryptionFailed) if !self.payload_looks_intact(&encrypted) => {
+                return Err(VerifyError::BundleCorrupted);
+            }
+            result => result?,
+        };
 
         // Parse decrypted payload as JSON
-        let payload: DecryptedPayload =
+        let mut payload: DecryptedPayload =
             serde_json::from_slice(&decrypted).map_err(|_| VerifyError::BundleCorrupted)?;
+        payload.nonce = encrypted[..AES_GCM_NONCE_LEN].to_vec();
 
         Ok(payload)
     }
+
+    /// Structural clues that a payload which failed authentication is
+    /// damaged rather than opened with the wrong password.
+    ///
+    /// AES-GCM can't tell a wrong key from a modified ciphertext: both fail
+    /// the tag check. But a payload shorter than any real one, or whose nonce
+    /// differs from the envelope's copy, was truncated or tampered with.
+    /// A payload that passes these checks may still be corrupt.
+    fn payload_looks_intact(&self, encrypted: &[u8]) -> bool {
+        let ciphertext_len = encrypted.len().saturating_sub(AES_GCM_NONCE_LEN + AES_GCM_TAG_LEN);
+        let nonce_matches = match decode_base64(&self.nonce) {
+            Ok(nonce) if nonce.len() == AES_GCM_NONCE_LEN => encrypted.starts_with(&nonce),
+            // Producers that don't fill in the envelope nonce give no clue
+            _ => true,
+        };
+        ciphertext_len >= MIN_PLAUSIBLE_CIPHERTEXT_LEN && nonce_matches
+    }
+}
+
+/// Nonces seen across several sealed bundles.
+///
+/// Bundles sealed with the same password and salt share an AES-GCM key, so
+/// a nonce repeated between them breaks the encryption. Nonces are keyed by
+/// salt; callers verify a set of bundles with one password.
+#[derive(Debug, Default)]
+pub struct NonceTracker {
+    seen: Mutex<HashSet<(String, Vec<u8>)>>,
+}
+
+impl NonceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a payload's nonce under its bundle's salt, returning whether
+    /// the pair was already recorded.
+    pub fn record(&self, salt: &str, nonce: &[u8]) -> bool {
+        let mut seen = self.seen.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        !seen.insert((salt.to_string(), nonce.to_vec()))
+    }
+
+    /// Remove and return every recorded salt and nonce.
+    pub(crate) fn drain(&self) -> Vec<(String, Vec<u8>)> {
+        let mut seen = self.seen.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        seen.drain().collect()
+    }
 }
 
 impl DecryptedPayload {
-    /// Get the audio data as bytes.
+    /// Get the audio data as bytes, checked against `audioSha256` if given.
     pub fn audio_bytes(&self) -> Result<Vec<u8>> {
-        decode_base64(&self.audio_data)
+        decode_with_digest(&self.audio_data, self.audio_sha256.as_deref(), "audioData")
     }
 
-    /// Get the manifest data as bytes.
+    /// Get the manifest data as bytes, checked against `manifestSha256` if given.
     pub fn manifest_bytes(&self) -> Result<Vec<u8>> {
-        decode_base64(&self.manifest_data)
+        decode_with_digest(&self.manifest_data, self.manifest_sha256.as_deref(), "manifestData")
+    }
+
+    /// Decode every attachment, checking each against its declared hash.
+    ///
+    /// The audio and attachment filenames must be distinct bare file names,
+    /// ignoring ASCII case, so extracting them can't escape the directory or
+    /// overwrite one with another.
+    pub fn verified_attachments(&self) -> Result<Vec<ExtractedAttachment>> {
+        let mut names = vec![self.audio_filename.to_ascii_lowercase()];
+        for name in std::iter::once(&self.audio_filename).chain(self.attachments.iter().map(|a| &a.filename)) {
+            if !is_bare_filename(name) {
+                return Err(VerifyError::BundleCorrupted);
+            }
+        }
+        for attachment in &self.attachments {
+            let name = attachment.filename.to_ascii_lowercase();
+            if names.contains(&name) {
+                return Err(VerifyError::BundleCorrupted);
+            }
+            names.push(name);
+        }
+        self.attachments.iter().map(Attachment::verify).collect()
+    }
+}
+
+/// Decode a payload blob, failing with `PayloadDigestMismatch` if it doesn't
+/// match its declared digest.
+///
+/// This catches a blob damaged before sealing separately from
+/// `HashMismatch`, which compares the audio against the signed manifest.
+fn decode_with_digest(data: &str, sha256: Option<&str>, field: &str) -> Result<Vec<u8>> {
+    let bytes = decode_base64(data)?;
+    match sha256 {
+        Some(digest) if !sha256_matches(&bytes, digest) => Err(VerifyError::PayloadDigestMismatch {
+            field: field.to_string(),
+        }),
+        _ => Ok(bytes),
+    }
+}
+
+/// Whether `name` is a plain file name, with no directory part, that's safe
+/// to join onto an extraction directory.
+pub fn is_bare_filename(name: &str) -> bool {
+    let bare = Path::new(name).file_name().and_then(|n| n.to_str());
+    bare == Some(name) && name != ".." && !name.contains('\\')
+}
+
+impl Attachment {
+    /// Decode the attachment and check it against its declared hash.
+    ///
+    /// The filename must be a bare file name so it's safe to extract.
+    pub fn verify(&self) -> Result<ExtractedAttachment> {
+        if !is_bare_filename(&self.filename) {
+            return Err(VerifyError::BundleCorrupted);
+        }
+
+        let data = decode_base64(&self.data_base64)?;
+        if !sha256_matches(&data, &self.sha256) {
+            return Err(VerifyError::AttachmentHashMismatch {
+                filename: self.filename.clone(),
+            });
+        }
+
+        Ok(ExtractedAttachment {
+            filename: self.filename.clone(),
+            data,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 
     #[test]
     fn test_parse_bundle_structure() {
@@ -145,4 +361,176 @@ mod tests {
             Err(VerifyError::UnsupportedBundleVersion { version: 99 })
         ));
     }
+
+    /// A sealed fixture with a fast KDF (1,000 iterations), for decryption edge cases.
+    fn fast_fixture() -> serde_json::Value {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("sealed_fast_test.proofcapture");
+        serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+    }
+
+    fn decrypt_value(value: &serde_json::Value) -> Result<DecryptedPayload> {
+        SealedProofBundle::from_json(&serde_json::to_vec(value).unwrap())?.decrypt("test-password-123")
+    }
+
+    #[test]
+    fn test_undersized_salt_is_corrupt() {
+        let mut bundle = fast_fixture();
+        bundle["salt"] = BASE64.encode([0x42; MIN_SALT_LEN - 1]).into();
+        assert!(matches!(decrypt_value(&bundle), Err(VerifyError::BundleCorrupted)));
+
+        // Long enough, but not the salt it was sealed with
+        bundle["salt"] = BASE64.encode([0x42; MIN_SALT_LEN]).into();
+        assert!(matches!(decrypt_value(&bundle), Err(VerifyError::DecryptionFailed)));
+    }
+
+    #[test]
+    fn test_truncated_payload_is_corrupt_not_wrong_password() {
+        let fixture = fast_fixture();
+        let encrypted = BASE64.decode(fixture["encryptedPayload"].as_str().unwrap()).unwrap();
+        let with_payload = |bytes: &[u8]| {
+            let mut bundle = fixture.clone();
+            bundle["encryptedPayload"] = BASE64.encode(bytes).into();
+            decrypt_value(&bundle)
+        };
+
+        // Above the 28-byte minimum, but shorter than any real payload
+        assert!(matches!(with_payload(&encrypted[..100]), Err(VerifyError::BundleCorrupted)));
+        // Nonce no longer matches the envelope's copy
+        assert!(matches!(with_payload(&encrypted[1..]), Err(VerifyError::BundleCorrupted)));
+        // Cut at the end: structurally plausible, indistinguishable from a wrong password
+        assert!(matches!(with_payload(&encrypted[..encrypted.len() - 1]), Err(VerifyError::DecryptionFailed)));
+
+        let bundle = SealedProofBundle::from_json(&serde_json::to_vec(&fixture).unwrap()).unwrap();
+        assert!(matches!(bundle.decrypt("wrong-password"), Err(VerifyError::DecryptionFailed)));
+    }
+
+    #[test]
+    fn test_password_spaces_are_significant() {
+        let bundle = SealedProofBundle::from_json(&serde_json::to_vec(&fast_fixture()).unwrap()).unwrap();
+        assert!(bundle.decrypt("test-password-123").is_ok());
+        assert!(matches!(bundle.decrypt(" test-password-123 "), Err(VerifyError::DecryptionFailed)));
+        assert!(matches!(bundle.decrypt("   "), Err(VerifyError::PasswordEmpty)));
+    }
+
+    fn attachment_fixture() -> DecryptedPayload {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("sealed_attachment_test.proofcapture");
+        let bundle = SealedProofBundle::from_json(&std::fs::read(path).unwrap()).unwrap();
+        bundle.decrypt("test-password-123").unwrap()
+    }
+
+    #[test]
+    fn test_decrypt_exposes_payload_nonce() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("sealed_attachment_test.proofcapture");
+        let bundle = SealedProofBundle::from_json(&std::fs::read(path).unwrap()).unwrap();
+        let payload = bundle.decrypt("test-password-123").unwrap();
+
+        let encrypted = decode_base64(&bundle.encrypted_payload).unwrap();
+        assert_eq!(payload.nonce, encrypted[..12]);
+
+        let tracker = NonceTracker::new();
+        assert!(!tracker.record(&bundle.salt, &payload.nonce));
+        assert!(!tracker.record("b3RoZXIgc2FsdA==", &payload.nonce));
+        assert!(tracker.record(&bundle.salt, &payload.nonce));
+    }
+
+    #[test]
+    fn test_payload_attachments_verify() {
+        let attachments = attachment_fixture().verified_attachments().unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].filename, "scene.jpg");
+        assert!(attachments[0].data.starts_with(&[0xFF, 0xD8]));
+    }
+
+    #[test]
+    fn test_tampered_attachment_fails() {
+        let mut payload = attachment_fixture();
+        payload.attachments[0].data_base64 = BASE64.encode(b"not the photo");
+
+        assert!(matches!(
+            payload.verified_attachments(),
+            Err(VerifyError::AttachmentHashMismatch { ref filename }) if filename == "scene.jpg"
+        ));
+    }
+
+    #[test]
+    fn test_attachment_with_path_in_filename_fails() {
+        let mut payload = attachment_fixture();
+        payload.attachments[0].filename = "../scene.jpg".to_string();
+        assert!(matches!(payload.verified_attachments(), Err(VerifyError::BundleCorrupted)));
+    }
+
+    #[test]
+    fn test_audio_filename_with_path_fails() {
+        for name in ["../recording.m4a", "/tmp/recording.m4a", "dir/recording.m4a", "..\\recording.m4a", "", ".."] {
+            let mut payload = attachment_fixture();
+            payload.audio_filename = name.to_string();
+            assert!(matches!(payload.verified_attachments(), Err(VerifyError::BundleCorrupted)), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn test_duplicate_extracted_filenames_fail() {
+        let mut payload = attachment_fixture();
+        payload.attachments[0].filename = payload.audio_filename.to_uppercase();
+        assert!(matches!(payload.verified_attachments(), Err(VerifyError::BundleCorrupted)));
+    }
+
+    fn digest_fixture() -> DecryptedPayload {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("sealed_digest_test.proofcapture");
+        let bundle = SealedProofBundle::from_json(&std::fs::read(path).unwrap()).unwrap();
+        bundle.decrypt("test-password-123").unwrap()
+    }
+
+    #[test]
+    fn test_payload_digests_checked_on_decode() {
+        let payload = digest_fixture();
+        assert!(payload.audio_sha256.is_some() && payload.manifest_sha256.is_some());
+        assert!(payload.audio_bytes().is_ok());
+        assert!(payload.manifest_bytes().is_ok());
+    }
+
+    #[test]
+    fn test_corrupted_payload_blob_fails_digest() {
+        // Still valid base64, but no longer the bytes that were digested
+        let mut payload = digest_fixture();
+        let mut audio = payload.audio_bytes().unwrap();
+        audio[100] ^= 0xff;
+        payload.audio_data = BASE64.encode(&audio);
+        assert!(matches!(
+            payload.audio_bytes(),
+            Err(VerifyError::PayloadDigestMismatch { ref field }) if field == "audioData"
+        ));
+
+        let mut payload = digest_fixture();
+        payload.manifest_data = BASE64.encode(b"{}");
+        assert!(matches!(payload.manifest_bytes(), Err(VerifyError::PayloadDigestMismatch { .. })));
+
+        // Payloads without digests decode as before
+        payload.manifest_sha256 = None;
+        assert_eq!(payload.manifest_bytes().unwrap(), b"{}");
+    }
+
+    #[test]
+    fn test_ios_media_keys() {
+        let json = r#"{"mediaData": "AA==", "manifestData": "AA==", "mediaFilename": "clip.m4a"}"#;
+        let payload: DecryptedPayload = serde_json::from_str(json).unwrap();
+        assert_eq!(payload.audio_filename, "clip.m4a");
+        assert_eq!(payload.audio_bytes().unwrap(), [0]);
+
+        let both = r#"{"audioData": "AA==", "mediaData": "AA==", "manifestData": "AA==", "audioFilename": "a.m4a"}"#;
+        assert!(serde_json::from_str::<DecryptedPayload>(both).is_err());
+    }
+
+    #[test]
+    fn test_payload_without_attachments() {
+        let json = r#"{"audioData": "AA==", "manifestData": "AA==", "audioFilename": "recording.m4a"}"#;
+        let payload: DecryptedPayload = serde_json::from_str(json).unwrap();
+        assert!(payload.verified_attachments().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,159 @@
+//! Reader for tar archives of standard bundles.
+//!
+//! Some users package a bundle directory as `.tar` or `.tar.gz` instead of
+//! a zip. Archives are parsed with the `tar` crate, so ustar, GNU and pax
+//! headers (long names included) are all understood; only regular files are
+//! returned and other entry types (directories, links) are skipped. A
+//! gzipped archive is decompressed in memory, up to a size cap.
+
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+
+use crate::error::{Result, VerifyError};
+
+/// Cap on the unpacked size of an archive (1 GiB).
+pub const DEFAULT_MAX_UNPACKED_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Offset of the `ustar` magic in a header.
+const USTAR_MAGIC_OFFSET: usize = 257;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A regular file in an archive.
+#[derive(Debug)]
+pub struct TarEntry {
+    /// Path of the entry inside the archive.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Whether `data` starts with a ustar header.
+pub fn is_tar(data: &[u8]) -> bool {
+    data.get(USTAR_MAGIC_OFFSET..USTAR_MAGIC_OFFSET + 5) == Some(b"ustar")
+}
+
+/// Read the regular files of a tar archive, gunzipping it first if needed.
+///
+/// A truncated archive or a header with a bad checksum is reported as
+/// `ManifestMalformed`, as for an unreadable zip. Archives unpacking to more
+/// than [`DEFAULT_MAX_UNPACKED_BYTES`] are too; see [`read_entries_with_limit`].
+pub fn read_entries(data: &[u8]) -> Result<Vec<TarEntry>> {
+    read_entries_with_limit(data, DEFAULT_MAX_UNPACKED_BYTES)
+}
+
+/// [`read_entries`], failing with `ManifestMalformed` once a gzipped archive
+/// decompresses past `max_bytes`, so a gzip bomb is never fully inflated.
+pub fn read_entries_with_limit(data: &[u8], max_bytes: u64) -> Result<Vec<TarEntry>> {
+    if data.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(data)
+            .take(max_bytes + 1)
+            .read_to_end(&mut decompressed)
+            .map_err(|_| VerifyError::ManifestMalformed)?;
+        if decompressed.len() as u64 > max_bytes {
+            return Err(VerifyError::ManifestMalformed);
+        }
+        return read_tar(&decompressed);
+    }
+    read_tar(data)
+}
+
+fn read_tar(data: &[u8]) -> Result<Vec<TarEntry>> {
+    let mut archive = ::tar::Archive::new(data);
+    let mut entries = Vec::new();
+
+    for entry in archive.entries().map_err(|_| VerifyError::ManifestMalformed)? {
+        let mut entry = entry.map_err(|_| VerifyError::ManifestMalformed)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|_| VerifyError::ManifestMalformed)?
+            .to_string_lossy()
+            .into_owned();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).map_err(|_| VerifyError::ManifestMalformed)?;
+        entries.push(TarEntry { name, data });
+    }
+
+    Ok(entries)
+}
+
+/// Write a ustar archive of regular files; test-only.
+#[cfg(test)]
+pub(crate) fn write_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut builder = ::tar::Builder::new(Vec::new());
+    for (name, data) in files {
+        let mut header = ::tar::Header::new_ustar();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, name, *data).unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixtures_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+    }
+
+    #[test]
+    fn test_read_fixture_archives() {
+        let gzipped = std::fs::read(fixtures_dir().join("full_bundle.tar.gz")).unwrap();
+        let mut plain = Vec::new();
+        GzDecoder::new(gzipped.as_slice()).read_to_end(&mut plain).unwrap();
+        assert!(is_tar(&plain));
+
+        for data in [gzipped, plain] {
+            let mut names: Vec<String> = read_entries(&data).unwrap().into_iter().map(|e| e.name).collect();
+            names.sort();
+            assert!(names.iter().any(|n| n.ends_with("manifest.json")), "{names:?}");
+            assert!(names.iter().any(|n| n.ends_with("recording.m4a")), "{names:?}");
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_bad_checksum() {
+        let mut archive = write_archive(&[("a.txt", b"hello"), ("b.txt", &[7; 600])]);
+        assert!(is_tar(&archive));
+        let entries = read_entries(&archive).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].name.as_str(), entries[0].data.as_slice()), ("a.txt", b"hello".as_slice()));
+        assert_eq!(entries[1].data.len(), 600);
+
+        archive[0] = b'c';
+        assert!(matches!(read_entries(&archive), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_pax_path_overrides_header_name() {
+        let long_name = format!("{}/manifest.json", "nested".repeat(30));
+        let mut builder = ::tar::Builder::new(Vec::new());
+        builder.append_pax_extensions([("path", long_name.as_bytes())]).unwrap();
+        let mut header = ::tar::Header::new_ustar();
+        header.set_size(2);
+        builder.append_data(&mut header, "truncated-name", b"{}".as_slice()).unwrap();
+        let archive = builder.into_inner().unwrap();
+
+        let entries = read_entries(&archive).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, long_name);
+    }
+
+    #[test]
+    fn test_gzip_is_inflated_only_up_to_limit() {
+        let archive = write_archive(&[("a.bin", &[0; 64 * 1024])]);
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, &archive).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        assert_eq!(read_entries_with_limit(&gzipped, archive.len() as u64).unwrap().len(), 1);
+        assert!(matches!(read_entries_with_limit(&gzipped, 4096), Err(VerifyError::ManifestMalformed)));
+    }
+}
@@ -0,0 +1,30 @@
+//! Names in the IANA time zone database.
+//!
+//! The names come from the tzdata release bundled with the `chrono-tz`
+//! crate: every zone and link name, so legacy aliases such as `US/Pacific`
+//! are accepted alongside canonical names. Updating the crate picks up new
+//! zones.
+
+use std::str::FromStr;
+
+use chrono_tz::Tz;
+
+/// Whether `name` is a zone or link name in the IANA database. Case matters.
+pub fn is_iana_time_zone(name: &str) -> bool {
+    Tz::from_str(name).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iana_time_zones() {
+        for zone in ["America/Los_Angeles", "Europe/London", "UTC", "Etc/GMT+5", "US/Pacific", "America/Coyhaique"] {
+            assert!(is_iana_time_zone(zone), "{}", zone);
+        }
+        for zone in ["Narnia/Capital", "america/los_angeles", "PST", "+05:00", ""] {
+            assert!(!is_iana_time_zone(zone), "{}", zone);
+        }
+    }
+}
@@ -3,6 +3,14 @@
 //! Computes trust levels (A, B, C) based on present trust vectors.
 //! Level A is highest, Level C is lowest.
 
+use std::fmt;
+use std::str::FromStr;
+
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+use crate::clock::format_duration;
+use crate::error::{Result, VerifyError};
 use crate::manifest::TrustVectors;
 
 /// Trust level indicating verification completeness.
@@ -30,6 +38,15 @@ impl TrustLevel {
         }
     }
 
+    /// Single-letter grade: "A", "B" or "C".
+    pub fn letter(&self) -> &'static str {
+        match self {
+            TrustLevel::A => "A",
+            TrustLevel::B => "B",
+            TrustLevel::C => "C",
+        }
+    }
+
     /// Human-readable label.
     pub fn label(&self) -> &'static str {
         match self {
@@ -60,6 +77,175 @@ impl TrustLevel {
             TrustLevel::C => "\x1b[33m", // Orange/Yellow
         }
     }
+
+    /// Numeric rank, higher for more trust: A=3, B=2, C=1.
+    pub fn rank(&self) -> u8 {
+        match self {
+            TrustLevel::A => 3,
+            TrustLevel::B => 2,
+            TrustLevel::C => 1,
+        }
+    }
+}
+
+/// Levels order by trust, so `A > B > C`. A derived `Ord` would follow
+/// declaration order and put A lowest.
+impl Ord for TrustLevel {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for TrustLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A single kind of trust vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrustVector {
+    Location,
+    Motion,
+    Continuity,
+    Clock,
+}
+
+impl TrustVector {
+    /// All trust vector kinds, in manifest order.
+    pub const ALL: [TrustVector; 4] = [
+        TrustVector::Location,
+        TrustVector::Motion,
+        TrustVector::Continuity,
+        TrustVector::Clock,
+    ];
+
+    /// Lowercase name as used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrustVector::Location => "location",
+            TrustVector::Motion => "motion",
+            TrustVector::Continuity => "continuity",
+            TrustVector::Clock => "clock",
+        }
+    }
+
+    /// Abbreviated name for compact listings.
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            TrustVector::Location => "loc",
+            TrustVector::Motion => "motion",
+            TrustVector::Continuity => "cont",
+            TrustVector::Clock => "clock",
+        }
+    }
+
+    /// The vectors present in `vectors`, in manifest order.
+    pub fn present(vectors: &TrustVectors) -> Vec<TrustVector> {
+        TrustVector::ALL.into_iter().filter(|v| v.is_present(vectors)).collect()
+    }
+
+    /// Returns true if this vector is present in `vectors`.
+    pub fn is_present(&self, vectors: &TrustVectors) -> bool {
+        match self {
+            TrustVector::Location => vectors.location.is_some(),
+            TrustVector::Motion => vectors.motion.is_some(),
+            TrustVector::Continuity => vectors.continuity.is_some(),
+            TrustVector::Clock => vectors.clock.is_some(),
+        }
+    }
+}
+
+impl fmt::Display for TrustVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for TrustVector {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        TrustVector::ALL
+            .into_iter()
+            .find(|v| v.name() == lower)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown trust vector: {}. Use location, motion, continuity, or clock",
+                    s
+                )
+            })
+    }
+}
+
+/// Compact presence tag such as `[loc,motion,cont,clock]`, listing only the
+/// vectors given.
+pub fn vector_tag(present: &[TrustVector]) -> String {
+    let names: Vec<&str> = present.iter().map(TrustVector::short_name).collect();
+    format!("[{}]", names.join(","))
+}
+
+/// Check that every required trust vector is present.
+///
+/// This is independent of the A/B/C trust level: it lets policies demand
+/// specific vectors (e.g. location and clock) that the levels don't express.
+/// Fails with `RequiredVectorMissing` naming the first absent vector.
+pub fn check_required_vectors(vectors: &TrustVectors, required: &[TrustVector]) -> Result<()> {
+    match required.iter().find(|v| !v.is_present(vectors)) {
+        Some(missing) => Err(VerifyError::RequiredVectorMissing { vector: *missing }),
+        None => Ok(()),
+    }
+}
+
+/// Check that all four trust vectors are present.
+///
+/// Stricter than any trust level: a recording lacking any vector fails with
+/// `IncompleteContext`, listing every absent vector.
+pub fn check_full_context(vectors: &TrustVectors) -> Result<()> {
+    let missing: Vec<TrustVector> = TrustVector::ALL.into_iter().filter(|v| !v.is_present(vectors)).collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(VerifyError::IncompleteContext { missing })
+    }
+}
+
+/// Check the recording was interrupted at most `max` times.
+///
+/// Fails with `TooManyInterruptions` reporting the actual count. A recording
+/// without a continuity vector has no recorded interruptions and passes.
+pub fn check_interruption_limit(vectors: &TrustVectors, max: usize) -> Result<()> {
+    let count = vectors.continuity.as_ref().map_or(0, |c| c.interruption_events.len());
+    if count > max {
+        Err(VerifyError::TooManyInterruptions { count, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Fail if the recording ended more than `max_age` before `now`.
+///
+/// A `capture_end` that isn't RFC 3339 can't be checked and is reported as
+/// `ManifestMalformed`.
+pub fn check_max_age(capture_end: &str, max_age: Duration, now: OffsetDateTime) -> Result<()> {
+    let ended = OffsetDateTime::parse(capture_end, &Rfc3339).map_err(|_| VerifyError::ManifestMalformed)?;
+    if now - ended > max_age {
+        Err(VerifyError::RecordingTooOld {
+            capture_end: capture_end.to_string(),
+            max_age: format_duration(max_age),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Adjustments to how the trust level is computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TrustPolicy {
+    /// Still award Level A when interruptions total less than this many
+    /// seconds. Only applies when every event records its duration.
+    pub max_interruption_seconds: Option<f64>,
 }
 
 /// Compute trust level from trust vectors.
@@ -69,13 +255,24 @@ impl TrustLevel {
 /// - Level B: location + motion
 /// - Level C: default (valid signature only)
 pub fn compute_trust_level(vectors: &TrustVectors) -> TrustLevel {
+    compute_trust_level_with_policy(vectors, &TrustPolicy::default())
+}
+
+/// Compute trust level from trust vectors under `policy`.
+///
+/// As [`compute_trust_level`], except that with a `max_interruption_seconds`
+/// an interrupted recording still counts as continuous for Level A if its
+/// interruptions total less than that.
+pub fn compute_trust_level_with_policy(vectors: &TrustVectors, policy: &TrustPolicy) -> TrustLevel {
     let has_location = vectors.location.is_some();
     let has_motion = vectors.motion.is_some();
-    let is_uninterrupted = vectors
-        .continuity
-        .as_ref()
-        .map(|c| c.uninterrupted)
-        .unwrap_or(false);
+    let is_uninterrupted = vectors.continuity.as_ref().is_some_and(|c| {
+        c.uninterrupted
+            || policy
+                .max_interruption_seconds
+                .zip(c.total_interruption_seconds())
+                .is_some_and(|(max, total)| total < max)
+    });
 
     if has_location && has_motion && is_uninterrupted {
         TrustLevel::A
@@ -89,7 +286,7 @@ pub fn compute_trust_level(vectors: &TrustVectors) -> TrustLevel {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::manifest::{ContinuityVector, LocationSnapshot, LocationVector, MotionVector};
+    use crate::manifest::{ContinuityVector, InterruptionEvent, LocationSnapshot, LocationVector, MotionVector};
 
     fn make_location() -> LocationVector {
         LocationVector {
@@ -122,6 +319,91 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_trust_vector_parses_case_insensitively() {
+        assert_eq!("location".parse::<TrustVector>(), Ok(TrustVector::Location));
+        assert_eq!("CLOCK".parse::<TrustVector>(), Ok(TrustVector::Clock));
+        assert_eq!("Continuity".parse::<TrustVector>(), Ok(TrustVector::Continuity));
+        assert!("altitude".parse::<TrustVector>().is_err());
+    }
+
+    #[test]
+    fn test_required_vectors_present() {
+        let vectors = TrustVectors {
+            location: Some(make_location()),
+            motion: Some(make_motion()),
+            continuity: None,
+            clock: None,
+        };
+        assert!(check_required_vectors(&vectors, &[]).is_ok());
+        assert!(check_required_vectors(&vectors, &[TrustVector::Location, TrustVector::Motion]).is_ok());
+    }
+
+    #[test]
+    fn test_required_vectors_absent() {
+        let vectors = TrustVectors {
+            location: Some(make_location()),
+            motion: None,
+            continuity: Some(make_continuity(true)),
+            clock: None,
+        };
+        let result = check_required_vectors(&vectors, &[TrustVector::Location, TrustVector::Clock]);
+        assert!(matches!(
+            result,
+            Err(VerifyError::RequiredVectorMissing { vector: TrustVector::Clock })
+        ));
+    }
+
+    fn interrupted(durations: &[Option<f64>]) -> TrustVectors {
+        let interruption_events = durations
+            .iter()
+            .map(|&duration_seconds| InterruptionEvent {
+                timestamp: "2026-01-27T02:57:18Z".to_string(),
+                reason: "phoneCall".to_string(),
+                duration_seconds,
+            })
+            .collect();
+        TrustVectors {
+            location: Some(make_location()),
+            motion: Some(make_motion()),
+            continuity: Some(ContinuityVector { uninterrupted: false, interruption_events }),
+            clock: None,
+        }
+    }
+
+    #[test]
+    fn test_small_gaps_under_threshold_keep_level_a() {
+        let policy = TrustPolicy { max_interruption_seconds: Some(1.0) };
+        let vectors = interrupted(&[Some(0.25), Some(0.125), Some(0.5)]);
+        assert_eq!(vectors.continuity.as_ref().unwrap().total_interruption_seconds(), Some(0.875));
+        assert_eq!(compute_trust_level_with_policy(&vectors, &policy), TrustLevel::A);
+        // Without a policy any interruption rules out Level A
+        assert_eq!(compute_trust_level(&vectors), TrustLevel::B);
+
+        // Summed gaps reaching the threshold don't qualify
+        let vectors = interrupted(&[Some(0.5), Some(0.6)]);
+        assert_eq!(compute_trust_level_with_policy(&vectors, &policy), TrustLevel::B);
+
+        // An event of unknown length could have been any length
+        let vectors = interrupted(&[Some(0.1), None]);
+        assert_eq!(vectors.continuity.as_ref().unwrap().total_interruption_seconds(), None);
+        assert_eq!(compute_trust_level_with_policy(&vectors, &policy), TrustLevel::B);
+    }
+
+    #[test]
+    fn test_max_age_window() {
+        let now = OffsetDateTime::parse("2026-02-26T02:57:17Z", &Rfc3339).unwrap();
+        let max_age = Duration::days(30);
+
+        assert!(check_max_age("2026-01-27T02:57:17Z", max_age, now).is_ok());
+        assert!(check_max_age("2026-01-27T02:57:18.500Z", max_age, now).is_ok());
+        let err = check_max_age("2026-01-27T02:57:16Z", max_age, now).unwrap_err();
+        assert_eq!(err.to_string(), "Recording ended at 2026-01-27T02:57:16Z, more than 30d ago");
+        assert_eq!(err.exit_code(), 27);
+        assert!(check_max_age("2026-03-01T00:00:00Z", max_age, now).is_ok());
+        assert!(matches!(check_max_age("last week", max_age, now), Err(VerifyError::ManifestMalformed)));
+    }
+
     #[test]
     fn test_level_a() {
         let vectors = TrustVectors {
@@ -176,4 +458,16 @@ mod tests {
         };
         assert_eq!(compute_trust_level(&vectors), TrustLevel::C);
     }
+
+    #[test]
+    fn test_trust_levels_order_by_trust() {
+        assert!(TrustLevel::A > TrustLevel::C);
+        assert!(TrustLevel::A > TrustLevel::B && TrustLevel::B > TrustLevel::C);
+        assert_eq!([TrustLevel::A, TrustLevel::B, TrustLevel::C].map(|level| level.rank()), [3, 2, 1]);
+
+        let mut levels = vec![TrustLevel::B, TrustLevel::A, TrustLevel::C];
+        levels.sort();
+        assert_eq!(levels, [TrustLevel::C, TrustLevel::B, TrustLevel::A]);
+        assert_eq!(levels.iter().max(), Some(&TrustLevel::A));
+    }
 }
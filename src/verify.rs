@@ -3,20 +3,79 @@
 //! Implements the verification pipeline for both standard and sealed bundles.
 
 use std::fs;
-use std::io::Read;
-use std::path::Path;
-
-use crate::crypto::{decode_base64, parse_public_key, parse_signature, sha256_base64, verify_signature};
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use p256::ecdsa::{Signature, VerifyingKey};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{
+    decode_base64, parse_public_key, parse_signature, sha256_matches, verify_signature, verify_signature_prehash,
+};
 use crate::error::{Result, VerifyError};
-use crate::manifest::{compute_canonical_hash_from_bytes, SignedAudioManifest};
-use crate::sealed::SealedProofBundle;
-use crate::trust::{compute_trust_level, TrustLevel};
+use crate::manifest::{
+    compute_canonical_hash_detached, compute_canonical_hash_from_bytes, CURRENT_SCHEMA_VERSION, decompress_manifest, decompress_manifest_with_limit, validate_trust_vectors, SignatureMode, SignedAudioManifest,
+};
+use crate::mp4::{extract_embedded_manifest, read_audio_properties, AudioProperties};
+use crate::options::VerifyOptions;
+use crate::sealed::{is_bare_filename, DecryptedPayload, ExtractedAttachment, SealedProofBundle, SealingInfo};
+use crate::tar;
+use crate::trust::{check_full_context, check_interruption_limit, check_max_age, check_required_vectors, compute_trust_level_with_policy, TrustLevel};
+use crate::warnings::{audio_property_warnings, collect_warnings, Warning};
+
+/// Manifest filenames recognised inside bundles, in order of preference.
+const MANIFEST_FILENAMES: [&str; 2] = ["manifest.json", "manifest.json.gz"];
+
+/// Detached signature entry inside open bundles.
+const DETACHED_SIGNATURE_FILENAME: &str = "manifest.sig";
+
+/// File name suffixes of tar archive bundles.
+const TAR_SUFFIXES: [&str; 3] = [".tar", ".tgz", ".tar.gz"];
+
+/// Local file header signature that starts a zip archive.
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Bytes read from the start of an extensionless file to tell its type:
+/// past the tar magic, and past the sealed envelope's short fields, which
+/// all come before or around the one long `encryptedPayload` value.
+const SNIFF_PREFIX_BYTES: u64 = 512;
 
 /// Result of a successful verification.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VerificationResult {
     pub manifest: SignedAudioManifest,
     pub trust_level: TrustLevel,
+    /// SHA-256 of the canonical manifest, i.e. the signed content.
+    pub manifest_hash: [u8; 32],
+    /// Base64 of the signature that verified, whether embedded or detached.
+    pub signature: String,
+    pub warnings: Vec<Warning>,
+    /// KDF settings of a sealed bundle; `None` for unsealed bundles.
+    pub sealing: Option<SealingInfo>,
+    /// The manifest exactly as verified, gzipped if it was supplied so.
+    pub manifest_bytes: Vec<u8>,
+    /// Outcome of each of the manifest's co-signatures, in order.
+    pub co_signers: Vec<CoSignerResult>,
+    /// What the audio's MP4 container records; `None` if it isn't MP4.
+    pub audio_properties: Option<AudioProperties>,
+}
+
+/// Whether one co-signature verified against the canonical manifest hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CoSignerResult {
+    pub role: String,
+    pub public_key: String,
+    pub valid: bool,
+    /// Valid, and made by a key pinned with `VerifyOptions::with_trusted_cosigner`.
+    /// A valid signature by an unpinned key only shows the manifest was signed by
+    /// whoever holds that key.
+    pub trusted: bool,
 }
 
 /// Result of sealed bundle verification with extracted audio.
@@ -24,72 +83,557 @@ pub struct VerificationResult {
 pub struct SealedVerificationResult {
     pub manifest: SignedAudioManifest,
     pub trust_level: TrustLevel,
+    pub manifest_hash: [u8; 32],
+    pub signature: String,
+    pub warnings: Vec<Warning>,
+    pub sealing: SealingInfo,
+    pub manifest_bytes: Vec<u8>,
+    pub co_signers: Vec<CoSignerResult>,
+    pub audio_properties: Option<AudioProperties>,
     pub audio_data: Vec<u8>,
     pub audio_filename: String,
+    /// Extra media from the payload, hash-checked; empty if none.
+    pub attachments: Vec<ExtractedAttachment>,
+}
+
+impl From<SealedVerificationResult> for VerificationResult {
+    /// Drop the extracted media, keeping the verification outcome.
+    fn from(result: SealedVerificationResult) -> Self {
+        VerificationResult {
+            manifest: result.manifest,
+            trust_level: result.trust_level,
+            manifest_hash: result.manifest_hash,
+            signature: result.signature,
+            warnings: result.warnings,
+            sealing: Some(result.sealing),
+            manifest_bytes: result.manifest_bytes,
+            co_signers: result.co_signers,
+            audio_properties: result.audio_properties,
+        }
+    }
+}
+
+/// The kind of bundle at a path, which decides how it is verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleType {
+    /// Password-protected `.proofcapture` file.
+    Sealed,
+    /// Unencrypted `.proofbundle` zip.
+    Open,
+    /// Standard bundle packed as `.tar`, `.tgz` or `.tar.gz`.
+    Archive,
+    /// Directory, manifest file, or audio file.
+    Standard,
+}
+
+/// Detect the type of the bundle at `path` from its extension.
+///
+/// A directory is always a standard bundle, whatever its name ends in.
+/// A file without an extension is sniffed: a zip is an open bundle, a tar
+/// is an archive bundle, and a JSON object with an `encryptedPayload` is a sealed bundle. Anything else,
+/// including paths that don't exist, is a standard bundle.
+pub fn detect_bundle_type(path: &Path) -> BundleType {
+    if path.is_dir() {
+        return BundleType::Standard;
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("proofcapture") => BundleType::Sealed,
+        Some("proofbundle") => BundleType::Open,
+        _ if is_tar_archive_name(path) => BundleType::Archive,
+        None if path.is_file() => sniff_bundle_type(path),
+        _ => BundleType::Standard,
+    }
+}
+
+/// Which bundle to verify in a directory holding both a sealed bundle and a
+/// standard one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BundlePreference {
+    /// The `.proofcapture` file, which needs a password.
+    #[default]
+    Sealed,
+    /// The loose manifest and audio.
+    Standard,
+}
+
+impl FromStr for BundlePreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sealed" => Ok(BundlePreference::Sealed),
+            "standard" => Ok(BundlePreference::Standard),
+            _ => Err(format!("Unknown bundle preference: {}. Use 'sealed' or 'standard'", s)),
+        }
+    }
+}
+
+/// The bundle to verify at `path`, which may be a directory holding a
+/// sealed bundle next to (or instead of) a standard one.
+///
+/// For a directory with exactly one `.proofcapture` file directly inside:
+/// - without a manifest, the sealed file is verified;
+/// - with a manifest too, `prefer` decides.
+///
+/// Any other path, including a directory with several sealed files, is
+/// returned unchanged and verified as it would be without this step.
+pub fn resolve_bundle_path(path: &Path, prefer: BundlePreference) -> PathBuf {
+    let unchanged = path.to_path_buf();
+    let Ok(entries) = fs::read_dir(path) else {
+        return unchanged;
+    };
+    let sealed: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| p.is_file() && detect_bundle_type(p) == BundleType::Sealed)
+        .collect();
+    let [sealed] = sealed.as_slice() else {
+        return unchanged;
+    };
+    let has_manifest = bundle_manifest_path(path).is_ok();
+    match prefer {
+        BundlePreference::Standard if has_manifest => unchanged,
+        _ => sealed.clone(),
+    }
+}
+
+fn sniff_bundle_type(path: &Path) -> BundleType {
+    let mut prefix = Vec::new();
+    let read = fs::File::open(path).and_then(|file| file.take(SNIFF_PREFIX_BYTES).read_to_end(&mut prefix));
+    if read.is_err() {
+        return BundleType::Standard;
+    }
+    if prefix.starts_with(ZIP_MAGIC) {
+        return BundleType::Open;
+    }
+    if tar::is_tar(&prefix) {
+        return BundleType::Archive;
+    }
+    let is_json_object = prefix.trim_ascii_start().starts_with(b"{");
+    if is_json_object && prefix.windows(18).any(|w| w == b"\"encryptedPayload\"") {
+        return BundleType::Sealed;
+    }
+    BundleType::Standard
+}
+
+/// Verify any bundle, dispatching on its type.
+///
+/// Sealed bundles need `password`; without one they fail to decrypt.
+pub fn verify_bundle(path: &Path, password: Option<&str>, options: &VerifyOptions) -> Result<VerificationResult> {
+    match detect_bundle_type(path) {
+        BundleType::Sealed => {
+            let password = password.ok_or(VerifyError::DecryptionFailed)?;
+            verify_sealed_bundle_with_options(path, password, options)
+        }
+        BundleType::Open => verify_open_bundle_with_options(path, options),
+        BundleType::Archive => verify_archive_bundle_with_options(path, options),
+        BundleType::Standard => verify_standard_bundle_with_options(path, options),
+    }
+}
+
+/// Whether `path` is named like a tar archive bundle.
+pub(crate) fn is_tar_archive_name(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
+    TAR_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
 }
 
 /// Verify a standard proof bundle (directory or files).
 ///
 /// Expected structure:
 /// - recording.m4a (or similar audio file)
-/// - manifest.json
+/// - manifest.json (or gzip-compressed manifest.json.gz)
 pub fn verify_standard_bundle(bundle_path: &Path) -> Result<VerificationResult> {
-    // Determine if path is directory or file
-    let (audio_path, manifest_path) = if bundle_path.is_dir() {
-        // Look for audio and manifest files in directory
-        let audio = find_audio_file(bundle_path)?;
-        let manifest = bundle_path.join("manifest.json");
-        if !manifest.exists() {
-            return Err(VerifyError::ManifestMalformed);
+    verify_standard_bundle_with_options(bundle_path, &VerifyOptions::default())
+}
+
+/// Verify a standard proof bundle using the given options.
+///
+/// A loose MP4/M4A file is verified against its embedded manifest (see
+/// [`verify_embedded_manifest_file`]).
+pub fn verify_standard_bundle_with_options(bundle_path: &Path, options: &VerifyOptions) -> Result<VerificationResult> {
+    if has_extension(bundle_path, &EMBEDDED_MANIFEST_EXTENSIONS) {
+        return verify_embedded_manifest_file(bundle_path, options);
+    }
+    let (audio_path, manifest_path) = match resolve_standard_bundle(bundle_path, None, None) {
+        Err(VerifyError::AudioFileMissing) if options.follows_audio_refs() => {
+            return verify_audio_ref(&bundle_manifest_path(bundle_path)?, options);
         }
-        (audio, manifest)
+        located => located?,
+    };
+    verify_files(&audio_path, &manifest_path, options)
+}
+
+/// The manifest of a bundle directory, or `path` itself if it's a manifest file.
+pub(crate) fn bundle_manifest_path(path: &Path) -> Result<PathBuf> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    MANIFEST_FILENAMES
+        .iter()
+        .map(|name| path.join(name))
+        .find(|path| path.exists())
+        .ok_or(VerifyError::ManifestMalformed)
+}
+
+/// Locate the audio and manifest files of a standard bundle.
+///
+/// - A directory is searched for `manifest.json` and an audio file.
+/// - A manifest file (`.json` / `.json.gz`) is paired with a sibling
+///   `recording.*`, unless `audio` is given.
+/// - Either way, a manifest naming its audio in `audioFilename` is paired
+///   with that sibling file instead of guessing.
+/// - An audio file requires `manifest`; the verifier never guesses which
+///   manifest belongs to a loose recording.
+///
+/// `audio` and `manifest` override whatever would otherwise be located.
+pub fn resolve_standard_bundle(
+    path: &Path,
+    audio: Option<&Path>,
+    manifest: Option<&Path>,
+) -> Result<(PathBuf, PathBuf)> {
+    if path.is_dir() {
+        let manifest = match manifest {
+            Some(manifest) => manifest.to_path_buf(),
+            None => bundle_manifest_path(path)?,
+        };
+        let audio = match audio {
+            Some(audio) => audio.to_path_buf(),
+            None => match named_audio_file(&manifest)? {
+                Some(name) => path.join(name),
+                None => find_audio_file(path)?,
+            },
+        };
+        Ok((audio, manifest))
+    } else if is_manifest_file(path) {
+        let audio = match audio {
+            Some(audio) => audio.to_path_buf(),
+            None => {
+                let parent = path.parent().unwrap_or(Path::new("."));
+                match named_audio_file(path)? {
+                    Some(name) => parent.join(name),
+                    None => find_recording_file(parent).ok_or(VerifyError::AudioFileMissing)?,
+                }
+            }
+        };
+        Ok((audio, path.to_path_buf()))
+    } else if is_audio_file(path) {
+        let manifest = manifest.ok_or(VerifyError::ManifestMissing)?;
+        Ok((path.to_path_buf(), manifest.to_path_buf()))
+    } else if !path.exists() {
+        Err(VerifyError::AudioFileMissing)
+    } else {
+        Err(VerifyError::ManifestMalformed)
+    }
+}
+
+/// Delay before the first retry of a failed read.
+const READ_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Audio extensions that may carry an embedded manifest.
+const EMBEDDED_MANIFEST_EXTENSIONS: [&str; 2] = ["m4a", "mp4"];
+
+/// Verify an MP4/M4A file against the manifest embedded in its metadata.
+///
+/// The audio hash is checked over the file with the manifest box removed;
+/// see [`crate::mp4`]. Fails with `ManifestMissing` if nothing is embedded.
+pub fn verify_embedded_manifest_file(audio_path: &Path, options: &VerifyOptions) -> Result<VerificationResult> {
+    let bytes = read_file(audio_path, options).map_err(|_| VerifyError::AudioFileMissing)?;
+    ensure_not_empty(&bytes)?;
+    let embedded = extract_embedded_manifest(&bytes)?.ok_or(VerifyError::ManifestMissing)?;
+    verify_audio_and_manifest_with_options(&embedded.audio, &embedded.manifest, options)
+}
+
+/// The `audioFilename` named by the manifest at `manifest_path`, if any.
+///
+/// An unreadable manifest yields `None`; verification reports it later.
+fn named_audio_file(manifest_path: &Path) -> Result<Option<String>> {
+    match fs::read(manifest_path) {
+        Ok(bytes) => named_audio(&bytes),
+        Err(_) => Ok(None),
+    }
+}
+
+fn named_audio(manifest_bytes: &[u8]) -> Result<Option<String>> {
+    let named = SignedAudioManifest::from_json(manifest_bytes).ok().and_then(|m| m.audio_filename);
+    named.map(bare_filename).transpose()
+}
+
+/// Locate the audio for manifest bytes that aren't in a file, e.g. piped on
+/// stdin: `path` itself if it's a file, else the audio in the bundle
+/// directory `path`, preferring the manifest's `audioFilename`.
+pub fn resolve_audio_for_manifest(path: &Path, manifest_bytes: &[u8]) -> Result<PathBuf> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    match named_audio(manifest_bytes)? {
+        Some(name) => Ok(path.join(name)),
+        None => find_audio_file(path),
+    }
+}
+
+/// Reject names that would reach outside the bundle.
+fn bare_filename(name: String) -> Result<String> {
+    let bare = Path::new(&name).file_name().and_then(|n| n.to_str());
+    if bare != Some(name.as_str()) || name == ".." {
+        return Err(VerifyError::ManifestMalformed);
+    }
+    Ok(name)
+}
+
+/// Verify an explicit audio file against an explicit manifest file.
+///
+/// A sibling `.sig` file (see [`detached_signature_path`]) is used as the
+/// detached signature when present.
+pub fn verify_files(audio_path: &Path, manifest_path: &Path, options: &VerifyOptions) -> Result<VerificationResult> {
+    if !audio_path.exists() && options.follows_audio_refs() {
+        return verify_audio_ref(manifest_path, options);
+    }
+    #[cfg(feature = "mmap")]
+    if let Some(verified) = crate::mmap::with_mapped(audio_path, |audio| verify_manifest_file(audio, manifest_path, options)) {
+        return verified;
+    }
+    let audio_bytes = read_file(audio_path, options).map_err(|_| VerifyError::AudioFileMissing)?;
+    verify_manifest_file(&audio_bytes, manifest_path, options)
+}
+
+/// Verify a manifest file against audio fetched from its `audioRef`.
+///
+/// Fails with `AudioFileMissing` if the manifest has no reference or it
+/// can't be read. URLs need the `remote` feature.
+fn verify_audio_ref(manifest_path: &Path, options: &VerifyOptions) -> Result<VerificationResult> {
+    let manifest_bytes = read_file(manifest_path, options).map_err(|_| VerifyError::ManifestMalformed)?;
+    let audio_ref = SignedAudioManifest::from_json(&manifest_bytes)?
+        .audio_ref
+        .ok_or(VerifyError::AudioFileMissing)?;
+
+    let audio_bytes = if audio_ref.starts_with("http://") || audio_ref.starts_with("https://") {
+        fetch_audio_ref(&audio_ref)?
     } else {
-        // Single file - could be a zip or the manifest itself
-        // For now, treat as manifest and look for sibling audio
-        let parent = bundle_path.parent().unwrap_or(Path::new("."));
-        let audio = find_audio_file(parent)?;
-        (audio, bundle_path.to_path_buf())
+        // Relative references are relative to the manifest, not the working directory
+        let base = manifest_path.parent().unwrap_or(Path::new("."));
+        read_file(&base.join(&audio_ref), options).map_err(|_| VerifyError::AudioFileMissing)?
     };
+    verify_manifest_file(&audio_bytes, manifest_path, options)
+}
+
+#[cfg(feature = "remote")]
+fn fetch_audio_ref(url: &str) -> Result<Vec<u8>> {
+    crate::remote::fetch(url, crate::remote::DEFAULT_MAX_DOWNLOAD_BYTES)
+}
 
-    // Read files
-    let audio_bytes = fs::read(&audio_path).map_err(|_| VerifyError::AudioFileMissing)?;
-    let manifest_bytes = fs::read(&manifest_path).map_err(|_| VerifyError::ManifestMalformed)?;
+#[cfg(not(feature = "remote"))]
+fn fetch_audio_ref(url: &str) -> Result<Vec<u8>> {
+    Err(VerifyError::Network(format!("Fetching {} needs the remote feature", url)))
+}
+
+/// Verify audio bytes against a manifest file and its detached signature, if any.
+fn verify_manifest_file(audio_bytes: &[u8], manifest_path: &Path, options: &VerifyOptions) -> Result<VerificationResult> {
+    let manifest_bytes = read_file(manifest_path, options).map_err(|_| VerifyError::ManifestMalformed)?;
 
-    // Verify
-    verify_audio_and_manifest(&audio_bytes, &manifest_bytes)
+    let signature_path = detached_signature_path(manifest_path);
+    if signature_path.is_file() {
+        let signature = read_file(&signature_path, options).map_err(VerifyError::Io)?;
+        verify_audio_and_manifest_detached(audio_bytes, &manifest_bytes, &signature, options)
+    } else {
+        verify_audio_and_manifest_with_options(audio_bytes, &manifest_bytes, options)
+    }
+}
+
+/// Path of the detached signature for a manifest: `X.json` and `X.json.gz`
+/// both map to `X.sig`.
+pub fn detached_signature_path(manifest_path: &Path) -> PathBuf {
+    let name = manifest_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let stem = name.strip_suffix(".gz").unwrap_or(name);
+    let stem = stem.strip_suffix(".json").unwrap_or(stem);
+    manifest_path.with_file_name(format!("{}.sig", stem))
 }
 
 /// Verify a sealed proof bundle (.proofcapture file).
 pub fn verify_sealed_bundle(bundle_path: &Path, password: &str) -> Result<VerificationResult> {
-    let result = verify_and_extract_sealed_bundle(bundle_path, password)?;
-    Ok(VerificationResult {
-        manifest: result.manifest,
-        trust_level: result.trust_level,
-    })
+    verify_sealed_bundle_with_options(bundle_path, password, &VerifyOptions::default())
+}
+
+/// Verify a sealed proof bundle using the given options.
+pub fn verify_sealed_bundle_with_options(
+    bundle_path: &Path,
+    password: &str,
+    options: &VerifyOptions,
+) -> Result<VerificationResult> {
+    let bundle_bytes = read_file(bundle_path, options)?;
+    verify_sealed_bytes(&bundle_bytes, password, options)
 }
 
 /// Verify a sealed proof bundle and return the decrypted audio data.
 pub fn verify_and_extract_sealed_bundle(bundle_path: &Path, password: &str) -> Result<SealedVerificationResult> {
-    // Read bundle
-    let bundle_bytes = fs::read(bundle_path).map_err(|e| VerifyError::Io(e))?;
+    verify_and_extract_sealed_bundle_with_options(bundle_path, password, &VerifyOptions::default())
+}
 
-    // Parse and decrypt
-    let bundle = SealedProofBundle::from_json(&bundle_bytes)?;
-    let payload = bundle.decrypt(password)?;
+/// Verify a sealed proof bundle using the given options and return the decrypted audio data.
+pub fn verify_and_extract_sealed_bundle_with_options(
+    bundle_path: &Path,
+    password: &str,
+    options: &VerifyOptions,
+) -> Result<SealedVerificationResult> {
+    let bundle_bytes = read_file(bundle_path, options)?;
+    verify_and_extract_sealed_bytes(&bundle_bytes, password, options)
+}
 
-    // Get audio and manifest bytes
-    let audio_bytes = payload.audio_bytes()?;
-    let manifest_bytes = payload.manifest_bytes()?;
+/// Verify sealed bundle bytes already held in memory.
+///
+/// With a result cache in `options`, a repeat of the same bytes and password
+/// skips decryption and authentication; the policy and stateful checks
+/// still run under `options`.
+pub fn verify_sealed_bytes(bundle_bytes: &[u8], password: &str, options: &VerifyOptions) -> Result<VerificationResult> {
+    let bundle = cached(options, bundle_bytes, Some(password), || {
+        open_sealed(bundle_bytes, password, options).map(|(bundle, _)| bundle)
+    })?;
+    finish(bundle, options)
+}
 
-    // Verify
-    let verification = verify_audio_and_manifest(&audio_bytes, &manifest_bytes)?;
+/// Read a sealed bundle's unencrypted envelope without decrypting it.
+///
+/// No password is needed: `version`, the KDF settings and `created_at` can
+/// be read for triage, while the payload stays encrypted.
+pub fn inspect_sealed(bundle_bytes: &[u8]) -> Result<SealedProofBundle> {
+    ensure_not_empty(bundle_bytes)?;
+    SealedProofBundle::from_json(bundle_bytes)
+}
+
+/// Verify sealed bundle bytes already held in memory and return the decrypted audio data.
+pub fn verify_and_extract_sealed_bytes(
+    bundle_bytes: &[u8],
+    password: &str,
+    options: &VerifyOptions,
+) -> Result<SealedVerificationResult> {
+    let (bundle, contents) = open_sealed(bundle_bytes, password, options)?;
+    let verification = finish(bundle, options)?;
 
     Ok(SealedVerificationResult {
         manifest: verification.manifest,
         trust_level: verification.trust_level,
+        manifest_hash: verification.manifest_hash,
+        signature: verification.signature,
+        warnings: verification.warnings,
+        sealing: contents.sealing,
+        manifest_bytes: verification.manifest_bytes,
+        co_signers: verification.co_signers,
+        audio_properties: verification.audio_properties,
+        audio_data: contents.audio_data,
+        audio_filename: contents.audio_filename,
+        attachments: contents.attachments,
+    })
+}
+
+/// The decrypted files of a sealed bundle, for extraction.
+struct SealedContents {
+    sealing: SealingInfo,
+    audio_data: Vec<u8>,
+    audio_filename: String,
+    attachments: Vec<ExtractedAttachment>,
+}
+
+/// Decrypt a sealed bundle and authenticate its payload.
+fn open_sealed(
+    bundle_bytes: &[u8],
+    password: &str,
+    options: &VerifyOptions,
+) -> Result<(AuthenticatedBundle, SealedContents)> {
+    let (bundle, payload) = decrypt_sealed(bundle_bytes, password, options)?;
+    authenticate_payload(bundle, &payload, options)
+}
+
+/// Parse and decrypt a sealed bundle.
+fn decrypt_sealed(
+    bundle_bytes: &[u8],
+    password: &str,
+    options: &VerifyOptions,
+) -> Result<(SealedProofBundle, DecryptedPayload)> {
+    ensure_not_empty(bundle_bytes)?;
+    let bundle = SealedProofBundle::from_json(bundle_bytes)?;
+    let payload = bundle.decrypt_with_options(password, options)?;
+    Ok((bundle, payload))
+}
+
+/// Authenticate the decrypted payload of a sealed bundle.
+fn authenticate_payload(
+    bundle: SealedProofBundle,
+    payload: &DecryptedPayload,
+    options: &VerifyOptions,
+) -> Result<(AuthenticatedBundle, SealedContents)> {
+    // Get audio and manifest bytes
+    let audio_bytes = payload.audio_bytes()?;
+    let manifest_bytes = payload.manifest_bytes()?;
+    let detached = payload.signature_data.as_deref().map(str::as_bytes);
+
+    // Authenticate
+    let authenticated = check_integrity(&audio_bytes, &manifest_bytes, detached, options)?;
+    let attachments = payload.verified_attachments()?;
+
+    let sealing = bundle.sealing_info();
+    let authenticated = AuthenticatedBundle {
+        authenticated,
+        audio_properties: read_audio_properties(&audio_bytes),
+        sealed: Some(SealedEnvelope { sealing: sealing.clone(), salt: bundle.salt, nonce: payload.nonce.clone() }),
+    };
+    let contents = SealedContents {
+        sealing,
         audio_data: audio_bytes,
         audio_filename: payload.audio_filename.clone(),
-    })
+        attachments,
+    };
+    Ok((authenticated, contents))
+}
+
+/// Verify sealed bundle bytes, also returning the decrypted manifest if
+/// decryption succeeded, so a failure can be described without running the
+/// key derivation again.
+pub(crate) fn verify_sealed_bytes_keeping_manifest(
+    bundle_bytes: &[u8],
+    password: &str,
+    options: &VerifyOptions,
+) -> (Result<VerificationResult>, Option<Vec<u8>>) {
+    let (bundle, payload) = match decrypt_sealed(bundle_bytes, password, options) {
+        Ok(decrypted) => decrypted,
+        Err(error) => return (Err(error), None),
+    };
+    let manifest_bytes = payload.manifest_bytes().ok();
+    let outcome = authenticate_payload(bundle, &payload, options).and_then(|(bundle, _)| finish(bundle, options));
+    (outcome, manifest_bytes)
+}
+
+/// Create the directory extracted media is written to if needed, failing
+/// clearly if it can't hold files.
+pub fn prepare_extract_dir(dir: &Path) -> Result<()> {
+    if dir.exists() && !dir.is_dir() {
+        return Err(VerifyError::ExtractPathNotDirectory { path: dir.display().to_string() });
+    }
+    fs::create_dir_all(dir).map_err(|e| extract_io_error(dir, e))?;
+    if fs::metadata(dir)?.permissions().readonly() {
+        return Err(VerifyError::ExtractDirNotWritable { path: dir.display().to_string() });
+    }
+    Ok(())
+}
+
+/// Write one extracted file into `dir`, returning its path.
+///
+/// `filename` must be a bare file name; one with a directory part is
+/// refused as `BundleCorrupted`.
+pub fn write_extracted_file(dir: &Path, filename: &str, data: &[u8]) -> Result<PathBuf> {
+    if !is_bare_filename(filename) {
+        return Err(VerifyError::BundleCorrupted);
+    }
+    let path = dir.join(filename);
+    fs::write(&path, data).map_err(|e| extract_io_error(dir, e))?;
+    Ok(path)
+}
+
+/// Report a permission failure under the extract directory as such.
+fn extract_io_error(dir: &Path, error: std::io::Error) -> VerifyError {
+    match error.kind() {
+        std::io::ErrorKind::PermissionDenied => VerifyError::ExtractDirNotWritable { path: dir.display().to_string() },
+        std::io::ErrorKind::NotADirectory => VerifyError::ExtractPathNotDirectory { path: dir.display().to_string() },
+        _ => VerifyError::Io(error),
+    }
 }
 
 /// Verify an open proof bundle (.proofbundle zip file).
@@ -97,14 +641,102 @@ pub fn verify_and_extract_sealed_bundle(bundle_path: &Path, password: &str) -> R
 /// Extracts the zip archive in memory, finds manifest.json and the media file,
 /// then verifies using the standard audio+manifest pipeline.
 pub fn verify_open_bundle(bundle_path: &Path) -> Result<VerificationResult> {
-    let bundle_bytes = fs::read(bundle_path).map_err(|e| VerifyError::Io(e))?;
+    verify_open_bundle_with_options(bundle_path, &VerifyOptions::default())
+}
 
-    let cursor = std::io::Cursor::new(&bundle_bytes);
+/// Verify an open proof bundle using the given options.
+pub fn verify_open_bundle_with_options(bundle_path: &Path, options: &VerifyOptions) -> Result<VerificationResult> {
+    let bundle_bytes = read_file(bundle_path, options)?;
+    verify_open_bytes(&bundle_bytes, options)
+}
+
+/// Verify open bundle (zip) bytes already held in memory.
+///
+/// With a result cache in `options`, a repeat of the same bytes skips
+/// authentication; the policy and stateful checks still run under `options`.
+pub fn verify_open_bytes(bundle_bytes: &[u8], options: &VerifyOptions) -> Result<VerificationResult> {
+    let bundle = cached(options, bundle_bytes, None, || {
+        let contents = read_open_bytes(bundle_bytes, options)?;
+        Ok(AuthenticatedBundle {
+            authenticated: check_integrity(&contents.media, &contents.manifest, contents.signature.as_deref(), options)?,
+            audio_properties: read_audio_properties(&contents.media),
+            sealed: None,
+        })
+    })?;
+    finish(bundle, options)
+}
+
+/// Run `authenticate` through the options' result cache, if there is one.
+fn cached(
+    options: &VerifyOptions,
+    bundle_bytes: &[u8],
+    password: Option<&str>,
+    authenticate: impl FnOnce() -> Result<AuthenticatedBundle>,
+) -> Result<AuthenticatedBundle> {
+    match options.result_cache() {
+        Some(cache) => cache.get_or_authenticate(bundle_bytes, password, authenticate),
+        None => authenticate(),
+    }
+}
+
+/// Verify a standard bundle packed as a tar or gzipped tar archive.
+///
+/// The archive is read in memory; the manifest, audio and any detached
+/// signature are found by file name, wherever they sit in the archive.
+pub fn verify_archive_bundle(bundle_path: &Path) -> Result<VerificationResult> {
+    verify_archive_bundle_with_options(bundle_path, &VerifyOptions::default())
+}
+
+/// Verify a tar archive bundle using the given options.
+pub fn verify_archive_bundle_with_options(bundle_path: &Path, options: &VerifyOptions) -> Result<VerificationResult> {
+    let bundle_bytes = read_file(bundle_path, options)?;
+    let contents = read_archive_bytes(&bundle_bytes, options)?;
+    verify_core(&contents.media, &contents.manifest, contents.signature.as_deref(), options)
+}
+
+/// Read the manifest JSON of any bundle type without verifying it.
+///
+/// Sealed bundles need `password` to decrypt. Useful for diagnostics on
+/// manifests that fail verification.
+pub fn load_manifest_bytes(path: &Path, password: Option<&str>) -> Result<Vec<u8>> {
+    match detect_bundle_type(path) {
+        BundleType::Sealed => {
+            let bundle = SealedProofBundle::from_json(&fs::read(path)?)?;
+            let payload = bundle.decrypt(password.ok_or(VerifyError::DecryptionFailed)?)?;
+            payload.manifest_bytes()
+        }
+        BundleType::Open => Ok(read_open_bytes(&fs::read(path)?, &VerifyOptions::default())?.manifest),
+        BundleType::Archive => Ok(read_archive_bytes(&fs::read(path)?, &VerifyOptions::default())?.manifest),
+        _ if is_manifest_file(path) => fs::read(path).map_err(|_| VerifyError::ManifestMalformed),
+        _ if has_extension(path, &EMBEDDED_MANIFEST_EXTENSIONS) => {
+            let bytes = fs::read(path).map_err(|_| VerifyError::AudioFileMissing)?;
+            let embedded = extract_embedded_manifest(&bytes)?.ok_or(VerifyError::ManifestMissing)?;
+            Ok(embedded.manifest)
+        }
+        _ => {
+            let (_, manifest) = resolve_standard_bundle(path, None, None)?;
+            fs::read(manifest).map_err(|_| VerifyError::ManifestMalformed)
+        }
+    }
+}
+
+/// Files of an open bundle zip or tar archive bundle.
+struct OpenBundleContents {
+    media: Vec<u8>,
+    manifest: Vec<u8>,
+    signature: Option<Vec<u8>>,
+}
+
+/// Extract the media, manifest and any detached signature from an open bundle zip.
+fn read_open_bytes(bundle_bytes: &[u8], options: &VerifyOptions) -> Result<OpenBundleContents> {
+    ensure_not_empty(bundle_bytes)?;
+    let cursor = std::io::Cursor::new(bundle_bytes);
     let mut archive = zip::ZipArchive::new(cursor)
         .map_err(|_| VerifyError::ManifestMalformed)?;
 
     let mut manifest_bytes: Option<Vec<u8>> = None;
-    let mut media_bytes: Option<Vec<u8>> = None;
+    let mut signature_bytes: Option<Vec<u8>> = None;
+    let mut media: Vec<(String, Vec<u8>)> = Vec::new();
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)
@@ -113,19 +745,73 @@ pub fn verify_open_bundle(bundle_path: &Path) -> Result<VerificationResult> {
 
         let mut buf = Vec::new();
         file.read_to_end(&mut buf)
-            .map_err(|e| VerifyError::Io(e))?;
+            .map_err(VerifyError::Io)?;
 
-        if name == "manifest.json" {
+        if MANIFEST_FILENAMES.contains(&name.as_str()) {
             manifest_bytes = Some(buf);
+        } else if name == DETACHED_SIGNATURE_FILENAME {
+            signature_bytes = Some(buf);
         } else {
-            media_bytes = Some(buf);
+            media.push((name, buf));
         }
     }
 
     let manifest_bytes = manifest_bytes.ok_or(VerifyError::ManifestMalformed)?;
-    let media_bytes = media_bytes.ok_or(VerifyError::AudioFileMissing)?;
+    let media_bytes = select_media(media, &manifest_bytes, options)?;
 
-    verify_audio_and_manifest(&media_bytes, &manifest_bytes)
+    Ok(OpenBundleContents {
+        media: media_bytes,
+        manifest: manifest_bytes,
+        signature: signature_bytes,
+    })
+}
+
+/// Extract the media, manifest and any detached signature from a tar archive.
+///
+/// Entries are matched on their file name, ignoring directories; the audio
+/// is picked as in [`select_media`].
+fn read_archive_bytes(bundle_bytes: &[u8], options: &VerifyOptions) -> Result<OpenBundleContents> {
+    ensure_not_empty(bundle_bytes)?;
+
+    let mut manifest_bytes: Option<Vec<u8>> = None;
+    let mut signature_bytes: Option<Vec<u8>> = None;
+    let mut media: Vec<(String, Vec<u8>)> = Vec::new();
+
+    for entry in tar::read_entries(bundle_bytes)? {
+        let name = entry.name.rsplit('/').next().unwrap_or_default().to_string();
+        if MANIFEST_FILENAMES.contains(&name.as_str()) {
+            manifest_bytes = Some(entry.data);
+        } else if name == DETACHED_SIGNATURE_FILENAME {
+            signature_bytes = Some(entry.data);
+        } else {
+            media.push((name, entry.data));
+        }
+    }
+
+    let manifest_bytes = manifest_bytes.ok_or(VerifyError::ManifestMissing)?;
+    let media_bytes = select_media(media, &manifest_bytes, options)?;
+
+    Ok(OpenBundleContents {
+        media: media_bytes,
+        manifest: manifest_bytes,
+        signature: signature_bytes,
+    })
+}
+
+/// Pick the audio among the other files of a zip or tar bundle: the entry
+/// the manifest's `audioFilename` names, else the first with an audio
+/// extension, in archive order.
+fn select_media(media: Vec<(String, Vec<u8>)>, manifest_bytes: &[u8], options: &VerifyOptions) -> Result<Vec<u8>> {
+    let named = decompress_manifest_with_limit(manifest_bytes, options.max_manifest_bytes())
+        .ok()
+        .and_then(|json| SignedAudioManifest::from_json(&json).ok())
+        .and_then(|m| m.audio_filename);
+    match named {
+        Some(name) => media.into_iter().find(|(entry, _)| *entry == name),
+        None => media.into_iter().find(|(entry, _)| is_audio_file(Path::new(entry))),
+    }
+    .map(|(_, bytes)| bytes)
+    .ok_or(VerifyError::AudioFileMissing)
 }
 
 /// Core verification of audio bytes against manifest.
@@ -133,15 +819,252 @@ pub fn verify_audio_and_manifest(
     audio_bytes: &[u8],
     manifest_bytes: &[u8],
 ) -> Result<VerificationResult> {
+    verify_audio_and_manifest_with_options(audio_bytes, manifest_bytes, &VerifyOptions::default())
+}
+
+/// Core verification of audio bytes against manifest, then the policy checks in `options`.
+pub fn verify_audio_and_manifest_with_options(
+    audio_bytes: &[u8],
+    manifest_bytes: &[u8],
+    options: &VerifyOptions,
+) -> Result<VerificationResult> {
+    verify_core(audio_bytes, manifest_bytes, None, options)
+}
+
+/// Core verification of a manifest without an embedded `signature` against
+/// a detached signature, given as raw 64 bytes or base64 text.
+pub fn verify_audio_and_manifest_detached(
+    audio_bytes: &[u8],
+    manifest_bytes: &[u8],
+    signature: &[u8],
+    options: &VerifyOptions,
+) -> Result<VerificationResult> {
+    verify_core(audio_bytes, manifest_bytes, Some(signature), options)
+}
+
+/// Check only that the audio matches the manifest and the manifest's
+/// signature is valid.
+///
+/// The fast path for callers who just need "is this authentic?": no trust
+/// level, policy checks, warnings or result are computed. It accepts exactly
+/// the inputs that [`verify_audio_and_manifest`] accepts with default options.
+pub fn verify_integrity_only(audio_bytes: &[u8], manifest_bytes: &[u8]) -> Result<()> {
+    check_integrity(audio_bytes, manifest_bytes, None, &VerifyOptions::default()).map(|_| ())
+}
+
+/// Verify audio against a manifest the caller has already parsed.
+///
+/// For services that cache parsed manifests: `manifest` supplies the fields
+/// and `manifest_bytes` (the JSON it was parsed from, optionally gzipped)
+/// supplies the canonical hash, so the full manifest isn't parsed again.
+/// The signature only covers the bytes, so if `manifest` disagrees with
+/// them on `audioHash` or `publicKey` it fails with `SignatureInvalid`.
+/// Other fields are reported from `manifest` as given.
+pub fn verify_audio_with_manifest_and_bytes(
+    audio_bytes: &[u8],
+    manifest: &SignedAudioManifest,
+    manifest_bytes: &[u8],
+) -> Result<VerificationResult> {
+    ensure_not_empty(audio_bytes)?;
+    ensure_not_empty(manifest_bytes)?;
+    let decompressed = decompress_manifest(manifest_bytes)?;
+    ensure_manifest_size(&decompressed, &VerifyOptions::default())?;
+    let signed: SignedBinding = serde_json::from_slice(&decompressed).map_err(|_| VerifyError::ManifestMalformed)?;
+    if signed.audio_hash != manifest.audio_hash || signed.public_key != manifest.public_key {
+        return Err(VerifyError::SignatureInvalid);
+    }
+    let (manifest_hash, signature) = authenticate(audio_bytes, manifest, &decompressed, None, CURRENT_SCHEMA_VERSION)?;
+    evaluate(
+        Authenticated {
+            manifest: manifest.clone(),
+            manifest_hash,
+            signature,
+            manifest_bytes: manifest_bytes.to_vec(),
+            json_len: decompressed.len(),
+        },
+        read_audio_properties(audio_bytes),
+        &VerifyOptions::default(),
+    )
+}
+
+/// The manifest fields authentication trusts, read from the signed bytes
+/// without parsing the rest.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedBinding {
+    audio_hash: String,
+    public_key: String,
+}
+
+/// An authenticated manifest with the hash and signature it was verified by.
+#[derive(Debug, Clone)]
+struct Authenticated {
+    manifest: SignedAudioManifest,
+    manifest_hash: [u8; 32],
+    signature: String,
+    /// The manifest as supplied, before decompression.
+    manifest_bytes: Vec<u8>,
+    /// Length of the decompressed manifest JSON.
+    json_len: usize,
+}
+
+/// What authenticating a bundle's bytes establishes, before any policy or
+/// stateful check.
+///
+/// It depends only on the bundle bytes and password, so it is what the
+/// result cache keeps.
+#[derive(Debug, Clone)]
+pub(crate) struct AuthenticatedBundle {
+    authenticated: Authenticated,
+    audio_properties: Option<AudioProperties>,
+    sealed: Option<SealedEnvelope>,
+}
+
+/// The envelope details of a sealed bundle needed after authentication.
+#[derive(Debug, Clone)]
+struct SealedEnvelope {
+    sealing: SealingInfo,
+    salt: String,
+    nonce: Vec<u8>,
+}
+
+fn verify_core(
+    audio_bytes: &[u8],
+    manifest_bytes: &[u8],
+    detached: Option<&[u8]>,
+    options: &VerifyOptions,
+) -> Result<VerificationResult> {
+    let authenticated = check_integrity(audio_bytes, manifest_bytes, detached, options)?;
+    evaluate(authenticated, read_audio_properties(audio_bytes), options)
+}
+
+/// Complete verification of a bundle authenticated earlier, possibly under
+/// other options, as on a result cache hit.
+///
+/// The option-dependent authentication limits are checked again, then the
+/// policy and stateful checks run under `options`.
+fn finish(bundle: AuthenticatedBundle, options: &VerifyOptions) -> Result<VerificationResult> {
+    let AuthenticatedBundle { authenticated, audio_properties, sealed } = bundle;
+    authenticated.manifest.validate_schema_up_to(options.max_schema_version())?;
+    if authenticated.json_len > options.max_manifest_bytes() {
+        return Err(VerifyError::ManifestMalformed);
+    }
+
+    let mut verification = evaluate(authenticated, audio_properties, options)?;
+    if let Some(sealed) = sealed {
+        if options.nonce_tracker().is_some_and(|tracker| tracker.record(&sealed.salt, &sealed.nonce)) {
+            verification.warnings.push(Warning::NonceReused);
+        }
+        verification.sealing = Some(sealed.sealing);
+    }
+    Ok(verification)
+}
+
+/// Steps 5-9: trust level, policy checks and warnings for an authenticated manifest.
+fn evaluate(
+    authenticated: Authenticated,
+    audio_properties: Option<AudioProperties>,
+    options: &VerifyOptions,
+) -> Result<VerificationResult> {
+    let Authenticated { manifest, manifest_hash, signature: encoded_signature, manifest_bytes, .. } = authenticated;
+
+    // Step 5: Compute trust level
+    let trust_level = compute_trust_level_with_policy(&manifest.trust_vectors, options.trust_policy());
+
+    // Step 6: Policy checks, only meaningful once authenticity is established
+    check_required_vectors(&manifest.trust_vectors, options.required_vectors())?;
+    if options.requires_full_context() {
+        check_full_context(&manifest.trust_vectors)?;
+    }
+    if let Some(max) = options.max_interruptions() {
+        check_interruption_limit(&manifest.trust_vectors, max)?;
+    }
+    if let Some(max_age) = options.max_age() {
+        check_max_age(&manifest.capture_end, max_age, options.clock().now())?;
+    }
+
+    // Step 7: Non-fatal warnings about the trust vectors, audio container and co-signatures
+    let mut warnings = collect_warnings(&manifest);
+    if let Some(detected) = &audio_properties {
+        warnings.extend(audio_property_warnings(&manifest, detected));
+    }
+    let co_signers = verify_co_signatures(&manifest, &manifest_hash, options.trusted_cosigners());
+    warnings.extend(co_signers.iter().filter(|c| !c.valid).map(|c| Warning::CoSignatureInvalid { role: c.role.clone() }));
+
+    // Step 8: Duplicate detection, recording the hash for later runs
+    if let Some(registry) = options.hash_registry() {
+        if registry.register(&manifest.audio_hash)? {
+            if options.rejects_duplicates() {
+                return Err(VerifyError::DuplicateRecording);
+            }
+            warnings.push(Warning::DuplicateRecording);
+        }
+    }
+
+    // Step 9: Custom checks, which can only add warnings
+    for check in options.checks() {
+        warnings.extend(check(&manifest));
+    }
+
+    Ok(VerificationResult {
+        manifest,
+        trust_level,
+        manifest_hash,
+        signature: encoded_signature,
+        warnings,
+        sealing: None,
+        manifest_bytes,
+        co_signers,
+        audio_properties,
+    })
+}
+
+/// Parse the manifest, then check the audio hash and signature.
+fn check_integrity(
+    audio_bytes: &[u8],
+    manifest_bytes: &[u8],
+    detached: Option<&[u8]>,
+    options: &VerifyOptions,
+) -> Result<Authenticated> {
+    ensure_not_empty(audio_bytes)?;
+    ensure_not_empty(manifest_bytes)?;
+
+    // Decompress once up front; the signature covers the decompressed JSON
+    let decompressed = decompress_manifest_with_limit(manifest_bytes, options.max_manifest_bytes())?;
+    ensure_manifest_size(&decompressed, options)?;
+
     // Parse manifest
-    let manifest = SignedAudioManifest::from_json(manifest_bytes)?;
+    let manifest = SignedAudioManifest::from_json(&decompressed)?;
+    let (manifest_hash, signature) =
+        authenticate(audio_bytes, &manifest, &decompressed, detached, options.max_schema_version())?;
 
+    Ok(Authenticated {
+        manifest,
+        manifest_hash,
+        signature,
+        manifest_bytes: manifest_bytes.to_vec(),
+        json_len: decompressed.len(),
+    })
+}
+
+/// Steps 1-4 for a parsed manifest and the decompressed JSON it came from.
+///
+/// Returns the canonical manifest hash and the base64 signature that verified.
+fn authenticate(
+    audio_bytes: &[u8],
+    manifest: &SignedAudioManifest,
+    manifest_bytes: &[u8],
+    detached: Option<&[u8]>,
+    max_schema_version: i32,
+) -> Result<([u8; 32], String)> {
     // Validate schema version
-    manifest.validate_schema()?;
+    manifest.validate_schema_up_to(max_schema_version)?;
+
+    // Reject physically impossible trust vector values
+    validate_trust_vectors(&manifest.trust_vectors)?;
 
     // Step 1: Verify audio hash
-    let computed_hash = sha256_base64(audio_bytes);
-    if computed_hash != manifest.audio_hash {
+    if !sha256_matches(audio_bytes, &manifest.audio_hash) {
         return Err(VerifyError::HashMismatch);
     }
 
@@ -149,46 +1072,90 @@ pub fn verify_audio_and_manifest(
     let public_key_bytes = decode_base64(&manifest.public_key)?;
     let public_key = parse_public_key(&public_key_bytes)?;
 
-    // Step 3: Compute canonical manifest hash (use original bytes to preserve formatting)
-    let manifest_hash = compute_canonical_hash_from_bytes(manifest_bytes)?;
+    // Step 3: Compute canonical manifest hash (use original bytes to preserve formatting).
+    // A detached signature covers the whole manifest; exactly one source is allowed.
+    let (manifest_hash, encoded_signature) = match (detached, &manifest.signature) {
+        (None, Some(embedded)) => (compute_canonical_hash_from_bytes(manifest_bytes)?, embedded.clone()),
+        (Some(detached), None) => (compute_canonical_hash_detached(manifest_bytes)?, encode_detached_signature(detached)?),
+        _ => return Err(VerifyError::ManifestMalformed),
+    };
 
     // Step 4: Parse and verify signature
-    let signature_bytes = decode_base64(&manifest.signature)?;
-    let signature = parse_signature(&signature_bytes)?;
+    let signature = parse_signature(&decode_base64(&encoded_signature)?)?;
 
-    if !verify_signature(&public_key, &manifest_hash, &signature) {
+    if !signature_verifies(manifest, &public_key, &manifest_hash, &signature) {
         return Err(VerifyError::SignatureInvalid);
     }
 
-    // Step 5: Compute trust level
-    let trust_level = compute_trust_level(&manifest.trust_vectors);
+    Ok((manifest_hash, encoded_signature))
+}
 
-    Ok(VerificationResult {
-        manifest,
-        trust_level,
-    })
+fn signature_verifies(
+    manifest: &SignedAudioManifest,
+    public_key: &VerifyingKey,
+    manifest_hash: &[u8; 32],
+    signature: &Signature,
+) -> bool {
+    match manifest.signature_mode.unwrap_or(SignatureMode::Digest) {
+        SignatureMode::Digest => verify_signature(public_key, manifest_hash, signature),
+        SignatureMode::Message => verify_signature_prehash(public_key, manifest_hash, signature),
+    }
 }
 
+/// Check each co-signature against the hash the device signature covers,
+/// and whether its key is one of the `trusted` ones.
+///
+/// A co-signature that doesn't decode counts as invalid, not as an error:
+/// the device signature alone decides whether the recording verifies.
+fn verify_co_signatures(manifest: &SignedAudioManifest, manifest_hash: &[u8; 32], trusted: &[String]) -> Vec<CoSignerResult> {
+    let trusted: Vec<Vec<u8>> = trusted.iter().filter_map(|key| decode_base64(key).ok()).collect();
+    manifest
+        .signatures
+        .iter()
+        .map(|co| {
+            let key_bytes = decode_base64(&co.public_key).ok();
+            let key = key_bytes.as_deref().and_then(|bytes| parse_public_key(bytes).ok());
+            let signature = decode_base64(&co.signature).and_then(|bytes| parse_signature(&bytes)).ok();
+            let valid = match (key, signature) {
+                (Some(key), Some(signature)) => signature_verifies(manifest, &key, manifest_hash, &signature),
+                _ => false,
+            };
+            let pinned = key_bytes.is_some_and(|bytes| trusted.contains(&bytes));
+            CoSignerResult {
+                role: co.role.clone(),
+                public_key: co.public_key.clone(),
+                valid,
+                trusted: valid && pinned,
+            }
+        })
+        .collect()
+}
+
+/// Base64 form of a detached signature given as raw 64-byte r||s or base64 text.
+fn encode_detached_signature(bytes: &[u8]) -> Result<String> {
+    if bytes.len() == 64 {
+        return Ok(BASE64.encode(bytes));
+    }
+    let text = std::str::from_utf8(bytes).map_err(|_| VerifyError::SignatureInvalid)?;
+    Ok(text.trim().to_string())
+}
+
+/// Audio file extensions recognised in standard bundles.
+const AUDIO_EXTENSIONS: [&str; 4] = ["m4a", "aac", "mp4", "wav"];
+
 /// Find an audio file in a directory.
-fn find_audio_file(dir: &Path) -> Result<std::path::PathBuf> {
-    let extensions = ["m4a", "aac", "mp4", "wav"];
-
-    for ext in &extensions {
-        // Try "recording.{ext}" first
-        let recording = dir.join(format!("recording.{}", ext));
-        if recording.exists() {
-            return Ok(recording);
-        }
+fn find_audio_file(dir: &Path) -> Result<PathBuf> {
+    // Try "recording.{ext}" first
+    if let Some(recording) = find_recording_file(dir) {
+        return Ok(recording);
     }
 
     // Look for any audio file
     if let Ok(entries) = fs::read_dir(dir) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if let Some(ext) = path.extension() {
-                if extensions.contains(&ext.to_str().unwrap_or("")) {
-                    return Ok(path);
-                }
+            if is_audio_file(&path) {
+                return Ok(path);
             }
         }
     }
@@ -196,10 +1163,88 @@ fn find_audio_file(dir: &Path) -> Result<std::path::PathBuf> {
     Err(VerifyError::AudioFileMissing)
 }
 
+/// Find `recording.{ext}` in a directory.
+fn find_recording_file(dir: &Path) -> Option<PathBuf> {
+    AUDIO_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("recording.{}", ext)))
+        .find(|path| path.exists())
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    has_extension(path, &AUDIO_EXTENSIONS)
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+}
+
+fn is_manifest_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
+    name.ends_with(".json") || name.ends_with(".json.gz")
+}
+
+/// Reject empty input before it surfaces as a confusing parse or hash error.
+fn ensure_not_empty(bytes: &[u8]) -> Result<()> {
+    if bytes.is_empty() {
+        return Err(VerifyError::EmptyFile);
+    }
+    Ok(())
+}
+
+/// Read `path`, retrying IO errors that may pass as many times as the
+/// options allow.
+pub(crate) fn read_file(path: &Path, options: &VerifyOptions) -> std::io::Result<Vec<u8>> {
+    retry_io(options.read_retries(), READ_RETRY_BACKOFF, || fs::read(path))
+}
+
+/// Run `read`, sleeping `backoff` (doubled each time) before each of up to
+/// `retries` further attempts while it fails transiently.
+fn retry_io<T>(retries: u32, backoff: Duration, mut read: impl FnMut() -> std::io::Result<T>) -> std::io::Result<T> {
+    let mut delay = backoff;
+    for _ in 0..retries {
+        match read() {
+            Err(e) if is_transient(&e) => {
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+            }
+            result => return result,
+        }
+    }
+    read()
+}
+
+/// Only errors known to pass are retried: an interrupted or would-block
+/// read, a timeout, and (on Unix) a stale NFS file handle.
+fn is_transient(error: &std::io::Error) -> bool {
+    if matches!(error.kind(), ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::TimedOut) {
+        return true;
+    }
+    #[cfg(unix)]
+    if error.raw_os_error() == Some(libc::ESTALE) {
+        return true;
+    }
+    false
+}
+
+/// Refuse to parse manifest JSON larger than the options allow.
+fn ensure_manifest_size(json: &[u8], options: &VerifyOptions) -> Result<()> {
+    if json.len() > options.max_manifest_bytes() {
+        return Err(VerifyError::ManifestMalformed);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use crate::registry::HashRegistry;
+    use crate::manifest::DEFAULT_MAX_MANIFEST_BYTES;
+    use crate::sealed::NonceTracker;
+    use std::sync::Arc;
+    use crate::trust::TrustVector;
 
     /// Get the fixtures directory path
     fn fixtures_dir() -> PathBuf {
@@ -221,6 +1266,27 @@ mod tests {
         assert_eq!(verification.manifest.app_bundle_id, "com.bestdaylabs.proofcapture");
     }
 
+    #[test]
+    fn test_verify_bundle_without_trust_vectors_is_level_c() {
+        let result = verify_standard_bundle(&fixtures_dir().join("no_trust_vectors_bundle")).unwrap();
+
+        assert_eq!(result.trust_level, TrustLevel::C);
+        assert!(result.manifest.trust_vectors.location.is_none());
+        assert!(result.manifest.trust_vectors.clock.is_none());
+    }
+
+    #[test]
+    fn test_verify_reads_container_audio_properties() {
+        let result = verify_standard_bundle(&fixtures_dir().join("container_duration_mismatch_bundle")).unwrap();
+        let detected = result.audio_properties.unwrap();
+
+        assert_eq!(detected.duration_seconds, Some(12.0));
+        assert_eq!(detected.codec.as_deref(), Some("mp4a"));
+        assert_eq!((detected.channels, detected.sample_rate_hz), (Some(2), Some(44_100)));
+        // The fixtures' placeholder audio isn't MP4
+        assert!(verify_standard_bundle(&fixtures_dir().join("minimal_bundle")).unwrap().audio_properties.is_none());
+    }
+
     #[test]
     fn test_verify_full_bundle_succeeds() {
         let bundle_path = fixtures_dir().join("full_bundle");
@@ -268,8 +1334,325 @@ mod tests {
         assert!(continuity.interruption_events.is_empty());
     }
 
+    #[test]
+    fn test_verify_gzip_manifest_bundle_matches_uncompressed_twin() {
+        let gzipped = verify_standard_bundle(&fixtures_dir().join("gzip_bundle")).unwrap();
+        let plain = verify_standard_bundle(&fixtures_dir().join("minimal_bundle")).unwrap();
+
+        assert_eq!(gzipped.trust_level, plain.trust_level);
+        assert_eq!(gzipped.manifest.audio_hash, plain.manifest.audio_hash);
+        assert_eq!(gzipped.signature, plain.signature);
+    }
+
+    // ==================== Single-File Tests ====================
+
+    #[test]
+    fn test_verify_manifest_file_uses_sibling_recording() {
+        let manifest_path = fixtures_dir().join("minimal_bundle").join("manifest.json");
+        let result = verify_standard_bundle(&manifest_path);
+
+        assert!(result.is_ok(), "Manifest file should verify: {:?}", result.err());
+    }
+
+    #[test]
+    fn test_verify_manifest_file_without_recording_fails() {
+        // Other audio in the directory must not be picked up as the recording
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::copy(
+            fixtures_dir().join("minimal_bundle").join("manifest.json"),
+            temp_dir.path().join("manifest.json"),
+        )
+        .unwrap();
+        fs::copy(
+            fixtures_dir().join("minimal_bundle").join("recording.m4a"),
+            temp_dir.path().join("other.m4a"),
+        )
+        .unwrap();
+
+        let result = verify_standard_bundle(&temp_dir.path().join("manifest.json"));
+        assert!(matches!(result.unwrap_err(), VerifyError::AudioFileMissing));
+    }
+
+    #[test]
+    fn test_verify_manifest_file_with_explicit_audio() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest_path = temp_dir.path().join("evidence.json");
+        let audio_path = temp_dir.path().join("other.m4a");
+        fs::copy(fixtures_dir().join("minimal_bundle").join("manifest.json"), &manifest_path).unwrap();
+        fs::copy(fixtures_dir().join("minimal_bundle").join("recording.m4a"), &audio_path).unwrap();
+
+        let (audio, manifest) = resolve_standard_bundle(&manifest_path, Some(&audio_path), None).unwrap();
+        assert_eq!(audio, audio_path);
+        assert_eq!(manifest, manifest_path);
+        assert!(verify_files(&audio, &manifest, &VerifyOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_audio_file_requires_manifest() {
+        let audio_path = fixtures_dir().join("minimal_bundle").join("recording.m4a");
+        let result = verify_standard_bundle(&audio_path);
+
+        assert!(matches!(result.unwrap_err(), VerifyError::ManifestMissing));
+    }
+
+    #[test]
+    fn test_verify_audio_file_with_explicit_manifest() {
+        let audio_path = fixtures_dir().join("minimal_bundle").join("recording.m4a");
+        let manifest_path = fixtures_dir().join("minimal_bundle").join("manifest.json");
+
+        let (audio, manifest) = resolve_standard_bundle(&audio_path, None, Some(&manifest_path)).unwrap();
+        assert!(verify_files(&audio, &manifest, &VerifyOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_embedded_manifest() {
+        let path = fixtures_dir().join("embedded_manifest.m4a");
+        let result = verify_standard_bundle(&path).unwrap();
+        assert_eq!(result.trust_level, TrustLevel::C);
+
+        // The hash covers the media, not just the manifest box
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[40] ^= 0xFF;
+        let embedded = extract_embedded_manifest(&bytes).unwrap().unwrap();
+        let result = verify_audio_and_manifest(&embedded.audio, &embedded.manifest);
+        assert!(matches!(result, Err(VerifyError::HashMismatch)));
+    }
+
+    #[test]
+    fn test_audio_ref_followed_only_when_enabled() {
+        let bundle_path = fixtures_dir().join("audio_ref_bundle");
+        let result = verify_standard_bundle(&bundle_path);
+        assert!(matches!(result, Err(VerifyError::AudioFileMissing)));
+
+        let options = VerifyOptions::new().with_audio_refs_followed(true);
+        let result = verify_standard_bundle_with_options(&bundle_path, &options).unwrap();
+        assert_eq!(result.manifest.audio_ref.as_deref(), Some("../message_signed_bundle/recording.m4a"));
+
+        // Relative to the manifest, wherever the verifier runs from
+        let manifest = bundle_path.join("manifest.json");
+        assert!(verify_standard_bundle_with_options(&manifest, &options).is_ok());
+    }
+
+    #[test]
+    fn test_audio_ref_content_is_hash_checked() {
+        let root = tempfile::tempdir().unwrap();
+        let bundle = root.path().join("bundle");
+        let shared = root.path().join("message_signed_bundle");
+        fs::create_dir_all(&bundle).unwrap();
+        fs::create_dir_all(&shared).unwrap();
+        fs::copy(fixtures_dir().join("audio_ref_bundle/manifest.json"), bundle.join("manifest.json")).unwrap();
+        fs::write(shared.join("recording.m4a"), b"substituted").unwrap();
+
+        let options = VerifyOptions::new().with_audio_refs_followed(true);
+        let result = verify_standard_bundle_with_options(&bundle, &options);
+        assert!(matches!(result, Err(VerifyError::HashMismatch)));
+    }
+
+    #[test]
+    fn test_detect_bundle_type_by_extension() {
+        assert_eq!(detect_bundle_type(Path::new("evidence.proofcapture")), BundleType::Sealed);
+        assert_eq!(detect_bundle_type(Path::new("evidence.proofbundle")), BundleType::Open);
+        assert_eq!(detect_bundle_type(Path::new("recording.m4a")), BundleType::Standard);
+        assert_eq!(detect_bundle_type(&fixtures_dir().join("minimal_bundle")), BundleType::Standard);
+    }
+
+    #[test]
+    fn test_directory_named_like_sealed_bundle_is_standard() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("evidence.proofcapture");
+        fs::create_dir(&bundle).unwrap();
+        for name in ["manifest.json", "recording.m4a"] {
+            fs::copy(fixtures_dir().join("minimal_bundle").join(name), bundle.join(name)).unwrap();
+        }
+
+        assert_eq!(detect_bundle_type(&bundle), BundleType::Standard);
+        assert!(verify_bundle(&bundle, None, &VerifyOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_detect_bundle_type_sniffs_extensionless_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let sealed = dir.path().join("evidence");
+        fs::copy(fixtures_dir().join("sealed_test.proofcapture"), &sealed).unwrap();
+        assert_eq!(detect_bundle_type(&sealed), BundleType::Sealed);
+        assert!(verify_bundle(&sealed, Some("test-password-123"), &VerifyOptions::default()).is_ok());
+
+        // Only the header is read, so a truncated envelope still sniffs as sealed
+        let truncated = dir.path().join("truncated");
+        fs::write(&truncated, &fs::read(&sealed).unwrap()[..SNIFF_PREFIX_BYTES as usize]).unwrap();
+        assert_eq!(detect_bundle_type(&truncated), BundleType::Sealed);
+
+        let open = dir.path().join("archive");
+        fs::write(&open, b"PK\x03\x04rest of the zip").unwrap();
+        assert_eq!(detect_bundle_type(&open), BundleType::Open);
+
+        let manifest = dir.path().join("manifest");
+        fs::copy(fixtures_dir().join("minimal_bundle/manifest.json"), &manifest).unwrap();
+        assert_eq!(detect_bundle_type(&manifest), BundleType::Standard);
+    }
+
+    #[test]
+    fn test_verify_unrecognised_file_fails() {
+        let readme = fixtures_dir().join("minimal_bundle").join("README.txt");
+        assert!(matches!(verify_standard_bundle(&readme).unwrap_err(), VerifyError::ManifestMalformed));
+    }
+
+    #[test]
+    fn test_transient_read_errors_are_retried() {
+        // Fails with EAGAIN twice, then reads
+        let flaky = |attempts: &mut u32| {
+            *attempts += 1;
+            match *attempts {
+                1 | 2 => Err(std::io::Error::from(ErrorKind::WouldBlock)),
+                _ => Ok(b"bundle".to_vec()),
+            }
+        };
+
+        let mut attempts = 0;
+        assert_eq!(retry_io(2, Duration::ZERO, || flaky(&mut attempts)).unwrap(), b"bundle");
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        assert!(retry_io(1, Duration::ZERO, || flaky(&mut attempts)).is_err());
+        assert_eq!(attempts, 2);
+
+        let mut attempts = 0;
+        let missing = retry_io(5, Duration::ZERO, || -> std::io::Result<()> {
+            attempts += 1;
+            Err(ErrorKind::NotFound.into())
+        });
+        assert!(missing.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_only_known_transient_errors_are_retried() {
+        for kind in [ErrorKind::Interrupted, ErrorKind::WouldBlock, ErrorKind::TimedOut] {
+            assert!(is_transient(&kind.into()), "{kind:?}");
+        }
+        for kind in [ErrorKind::NotFound, ErrorKind::PermissionDenied, ErrorKind::InvalidData, ErrorKind::Other] {
+            assert!(!is_transient(&kind.into()), "{kind:?}");
+        }
+        #[cfg(unix)]
+        assert!(is_transient(&std::io::Error::from_raw_os_error(libc::ESTALE)));
+    }
+
+    #[test]
+    fn test_verify_prefers_manifest_audio_filename() {
+        let temp_dir = copy_bundle("named_audio_bundle");
+        fs::write(temp_dir.path().join("recording.m4a"), b"decoy").unwrap();
+
+        assert!(verify_standard_bundle(temp_dir.path()).is_ok());
+        assert!(verify_standard_bundle(&temp_dir.path().join("manifest.json")).is_ok());
+    }
+
+    #[test]
+    fn test_verify_missing_named_audio_does_not_guess() {
+        let temp_dir = copy_bundle("named_audio_bundle");
+        fs::rename(temp_dir.path().join("take2.m4a"), temp_dir.path().join("recording.m4a")).unwrap();
+
+        assert!(matches!(verify_standard_bundle(temp_dir.path()), Err(VerifyError::AudioFileMissing)));
+    }
+
+    #[test]
+    fn test_audio_filename_outside_bundle_is_malformed() {
+        let temp_dir = copy_bundle("named_audio_bundle");
+        let manifest_path = temp_dir.path().join("manifest.json");
+        let edited = fs::read_to_string(&manifest_path).unwrap().replace("take2.m4a", "../take2.m4a");
+        fs::write(&manifest_path, edited).unwrap();
+
+        assert!(matches!(verify_standard_bundle(temp_dir.path()), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_verify_open_bundle_uses_named_entry() {
+        use std::io::Write;
+
+        let dir = fixtures_dir().join("named_audio_bundle");
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, data) in [
+            ("take2.m4a", fs::read(dir.join("take2.m4a")).unwrap()),
+            ("manifest.json", fs::read(dir.join("manifest.json")).unwrap()),
+            ("recording.m4a", b"decoy".to_vec()),
+        ] {
+            zip.start_file(name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(&data).unwrap();
+        }
+        let bundle = zip.finish().unwrap().into_inner();
+
+        assert!(verify_open_bytes(&bundle, &VerifyOptions::default()).is_ok());
+    }
+
     // ==================== Sealed Bundle Tests ====================
 
+    #[test]
+    fn test_empty_inputs_are_reported_as_empty() {
+        let options = VerifyOptions::default();
+        assert!(matches!(
+            verify_sealed_bytes(b"", "test-password-123", &options),
+            Err(VerifyError::EmptyFile)
+        ));
+        assert!(matches!(verify_open_bytes(b"", &options), Err(VerifyError::EmptyFile)));
+
+        let manifest = fs::read(fixtures_dir().join("minimal_bundle/manifest.json")).unwrap();
+        assert!(matches!(
+            verify_audio_and_manifest_with_options(b"", &manifest, &options),
+            Err(VerifyError::EmptyFile)
+        ));
+    }
+
+    #[test]
+    fn test_oversized_manifest_is_rejected_before_parsing() {
+        let audio = fs::read(fixtures_dir().join("minimal_bundle/recording.m4a")).unwrap();
+        let manifest = fs::read(fixtures_dir().join("minimal_bundle/manifest.json")).unwrap();
+        let options = VerifyOptions::new().with_max_manifest_bytes(manifest.len());
+        assert!(verify_audio_and_manifest_with_options(&audio, &manifest, &options).is_ok());
+
+        let options = VerifyOptions::new().with_max_manifest_bytes(manifest.len() - 1);
+        assert!(matches!(
+            verify_audio_and_manifest_with_options(&audio, &manifest, &options),
+            Err(VerifyError::ManifestMalformed)
+        ));
+
+        // Over the default limit, however well-formed the JSON
+        let mut padded = manifest.clone();
+        padded.resize(DEFAULT_MAX_MANIFEST_BYTES + 1, b' ');
+        assert!(matches!(verify_audio_and_manifest(&audio, &padded), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_mixed_directory_resolves_by_preference() {
+        let mixed = fixtures_dir().join("mixed_bundle");
+        let sealed = mixed.join("evidence.proofcapture");
+        assert_eq!(resolve_bundle_path(&mixed, BundlePreference::Sealed), sealed);
+        assert_eq!(resolve_bundle_path(&mixed, BundlePreference::Standard), mixed);
+        assert!(verify_standard_bundle(&mixed).unwrap().sealing.is_none());
+        assert!(verify_sealed_bundle(&sealed, "test-password-123").unwrap().sealing.is_some());
+
+        // Without a manifest there is nothing else to verify
+        let dir = tempfile::tempdir().unwrap();
+        fs::copy(&sealed, dir.path().join("only.proofcapture")).unwrap();
+        assert_eq!(resolve_bundle_path(dir.path(), BundlePreference::Standard), dir.path().join("only.proofcapture"));
+
+        // Never chosen between
+        fs::copy(&sealed, dir.path().join("other.proofcapture")).unwrap();
+        assert_eq!(resolve_bundle_path(dir.path(), BundlePreference::Sealed), dir.path());
+
+        let full = fixtures_dir().join("full_bundle");
+        assert_eq!(resolve_bundle_path(&full, BundlePreference::Sealed), full);
+        assert_eq!(resolve_bundle_path(&sealed, BundlePreference::Standard), sealed);
+    }
+
+    #[test]
+    fn test_empty_audio_file_in_standard_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::copy(fixtures_dir().join("minimal_bundle/manifest.json"), dir.path().join("manifest.json")).unwrap();
+        fs::write(dir.path().join("recording.m4a"), b"").unwrap();
+
+        let result = verify_standard_bundle_with_options(dir.path(), &VerifyOptions::default());
+        assert!(matches!(result, Err(VerifyError::EmptyFile)));
+    }
+
     #[test]
     fn test_verify_sealed_bundle_with_correct_password() {
         let bundle_path = fixtures_dir().join("sealed_test.proofcapture");
@@ -281,6 +1664,35 @@ mod tests {
         assert_eq!(verification.manifest.app_bundle_id, "com.bestdaylabs.proofcapture");
     }
 
+    #[test]
+    fn test_nonce_shared_between_sealed_bundles_warns() {
+        let sealed = fs::read(fixtures_dir().join("sealed_test.proofcapture")).unwrap();
+        let other = fs::read(fixtures_dir().join("sealed_attachment_test.proofcapture")).unwrap();
+        let options = VerifyOptions::new().with_nonce_tracker(Arc::new(NonceTracker::new()));
+
+        let first = verify_sealed_bytes(&sealed, "test-password-123", &options).unwrap();
+        assert!(!first.warnings.contains(&Warning::NonceReused));
+        let unrelated = verify_sealed_bytes(&other, "test-password-123", &options).unwrap();
+        assert!(!unrelated.warnings.contains(&Warning::NonceReused));
+
+        // A second payload under the same salt and nonce, e.g. a copy
+        let repeat = verify_sealed_bytes(&sealed, "test-password-123", &options).unwrap();
+        assert!(repeat.warnings.contains(&Warning::NonceReused));
+    }
+
+    #[test]
+    fn test_inspect_sealed_needs_no_password() {
+        let bundle = inspect_sealed(&fs::read(fixtures_dir().join("sealed_test.proofcapture")).unwrap()).unwrap();
+        assert_eq!(bundle.version, 1);
+        assert_eq!(bundle.created_at, "2026-01-27T02:57:16Z");
+        assert_eq!(bundle.kdf_algorithm, "pbkdf2");
+        assert_eq!(bundle.kdf_parameters.iterations, 600_000);
+
+        let manifest = fs::read(fixtures_dir().join("full_bundle/manifest.json")).unwrap();
+        assert!(matches!(inspect_sealed(&manifest), Err(VerifyError::BundleCorrupted)));
+        assert!(matches!(inspect_sealed(b""), Err(VerifyError::EmptyFile)));
+    }
+
     #[test]
     fn test_verify_sealed_bundle_with_wrong_password_fails() {
         let bundle_path = fixtures_dir().join("sealed_test.proofcapture");
@@ -294,8 +1706,38 @@ mod tests {
     fn test_verify_sealed_bundle_with_empty_password_fails() {
         let bundle_path = fixtures_dir().join("sealed_test.proofcapture");
         let result = verify_sealed_bundle(&bundle_path, "");
+        assert!(matches!(result, Err(VerifyError::PasswordEmpty)));
 
-        assert!(result.is_err());
+        let result = verify_sealed_bundle(&bundle_path, "  \t ");
+        assert!(matches!(result, Err(VerifyError::PasswordEmpty)));
+    }
+
+    #[test]
+    fn test_verify_sealed_bundle_with_kdf_cache_matches_uncached() {
+        let bundle_path = fixtures_dir().join("sealed_test.proofcapture");
+        let options = VerifyOptions::new().with_kdf_cache(4);
+
+        let uncached = verify_sealed_bundle(&bundle_path, "test-password-123").unwrap();
+        let cached_miss = verify_sealed_bundle_with_options(&bundle_path, "test-password-123", &options).unwrap();
+        let cached_hit = verify_sealed_bundle_with_options(&bundle_path, "test-password-123", &options).unwrap();
+
+        assert_eq!(options.kdf_cache().unwrap().len(), 1);
+        for result in [&cached_miss, &cached_hit] {
+            assert_eq!(result.trust_level, uncached.trust_level);
+            assert_eq!(result.manifest.audio_hash, uncached.manifest.audio_hash);
+            assert_eq!(result.signature, uncached.signature);
+        }
+    }
+
+    #[test]
+    fn test_verify_sealed_bundle_with_kdf_cache_wrong_password_fails() {
+        let bundle_path = fixtures_dir().join("sealed_test.proofcapture");
+        let options = VerifyOptions::new().with_kdf_cache(4);
+
+        verify_sealed_bundle_with_options(&bundle_path, "test-password-123", &options).unwrap();
+        let result = verify_sealed_bundle_with_options(&bundle_path, "wrong-password", &options);
+
+        assert!(matches!(result.unwrap_err(), VerifyError::DecryptionFailed));
     }
 
     #[test]
@@ -308,6 +1750,19 @@ mod tests {
         assert!(result.manifest.trust_vectors.clock.is_some());
     }
 
+    #[test]
+    fn test_verify_and_extract_sealed_bundle_with_attachment() {
+        let bundle_path = fixtures_dir().join("sealed_attachment_test.proofcapture");
+        let result = verify_and_extract_sealed_bundle(&bundle_path, "test-password-123").unwrap();
+
+        assert_eq!(result.audio_filename, "recording.m4a");
+        assert_eq!(result.attachments.len(), 1);
+        assert_eq!(result.attachments[0].filename, "scene.jpg");
+
+        let plain = verify_and_extract_sealed_bundle(&fixtures_dir().join("sealed_fast_test.proofcapture"), "test-password-123").unwrap();
+        assert!(plain.attachments.is_empty());
+    }
+
     // ==================== Error Case Tests ====================
 
     #[test]
@@ -372,6 +1827,485 @@ mod tests {
         let _ = fs::remove_dir_all(&temp_dir);
     }
 
+    // ==================== Signature Mode Tests ====================
+
+    fn verify_fixture_with_manifest(name: &str, edit: impl FnOnce(&mut serde_json::Value)) -> Result<VerificationResult> {
+        let dir = fixtures_dir().join(name);
+        let audio = fs::read(dir.join("recording.m4a")).unwrap();
+        let mut manifest: serde_json::Value = serde_json::from_slice(&fs::read(dir.join("manifest.json")).unwrap()).unwrap();
+        edit(&mut manifest);
+        verify_audio_and_manifest(&audio, &serde_json::to_vec(&manifest).unwrap())
+    }
+
+    #[test]
+    fn test_verify_message_signed_bundle() {
+        let result = verify_standard_bundle(&fixtures_dir().join("message_signed_bundle")).unwrap();
+        assert_eq!(result.manifest.signature_mode, Some(SignatureMode::Message));
+        assert_eq!(result.trust_level, TrustLevel::C);
+
+        let digest = verify_standard_bundle(&fixtures_dir().join("minimal_bundle")).unwrap();
+        assert_eq!(digest.manifest.signature_mode, None);
+    }
+
+    #[test]
+    fn test_verify_co_signed_bundle() {
+        let result = verify_standard_bundle(&fixtures_dir().join("cosigned_bundle")).unwrap();
+        let roles: Vec<_> = result.co_signers.iter().map(|c| (c.role.as_str(), c.valid)).collect();
+        assert_eq!(roles, [("notary", true)]);
+        assert!(result.warnings.is_empty());
+        // Co-signatures don't affect the trust level
+        assert_eq!(result.trust_level, TrustLevel::C);
+    }
+
+    #[test]
+    fn test_only_pinned_co_signers_are_trusted() {
+        let path = fixtures_dir().join("cosigned_partial_bundle");
+        let result = verify_standard_bundle(&path).unwrap();
+        assert!(result.co_signers.iter().all(|c| !c.trusted));
+
+        // Pinning the witness's key doesn't make its invalid signature trusted
+        let keys: Vec<String> = result.co_signers.iter().map(|c| c.public_key.clone()).collect();
+        let options = keys.iter().fold(VerifyOptions::new(), |options, key| options.with_trusted_cosigner(key.as_str()));
+        let result = verify_standard_bundle_with_options(&path, &options).unwrap();
+        let trusted: Vec<_> = result.co_signers.iter().map(|c| (c.role.as_str(), c.trusted)).collect();
+        assert_eq!(trusted, [("notary", true), ("witness", false)]);
+    }
+
+    #[test]
+    fn test_partially_valid_co_signatures_are_reported() {
+        let result = verify_standard_bundle(&fixtures_dir().join("cosigned_partial_bundle")).unwrap();
+        let roles: Vec<_> = result.co_signers.iter().map(|c| (c.role.as_str(), c.valid)).collect();
+        assert_eq!(roles, [("notary", true), ("witness", false)]);
+        assert_eq!(result.warnings, [Warning::CoSignatureInvalid { role: "witness".to_string() }]);
+
+        // A co-signature that doesn't even decode is invalid, not an error
+        let result = verify_fixture_with_manifest("cosigned_bundle", |m| m["signatures"][0]["signature"] = "AAAA".into()).unwrap();
+        assert!(!result.co_signers[0].valid);
+
+        // Editing the co-signatures leaves the device signature intact
+        let result = verify_fixture_with_manifest("cosigned_bundle", |m| m["signatures"] = serde_json::json!([])).unwrap();
+        assert!(result.co_signers.is_empty());
+    }
+
+    #[test]
+    fn test_verify_der_public_key_bundle() {
+        let result = verify_standard_bundle(&fixtures_dir().join("android_key_bundle")).unwrap();
+        assert!(result.manifest.public_key.starts_with("MFkw"));
+        // deviceKeyId fingerprints the raw coordinates, whatever the encoding
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_signature_mode_is_bound_by_signature() {
+        let switched = verify_fixture_with_manifest("message_signed_bundle", |m| m["signatureMode"] = "digest".into());
+        assert!(matches!(switched, Err(VerifyError::SignatureInvalid)));
+
+        let added = verify_fixture_with_manifest("minimal_bundle", |m| m["signatureMode"] = "message".into());
+        assert!(matches!(added, Err(VerifyError::SignatureInvalid)));
+    }
+
+    #[test]
+    fn test_unknown_signature_mode_is_malformed() {
+        let result = verify_fixture_with_manifest("message_signed_bundle", |m| m["signatureMode"] = "raw".into());
+        assert!(matches!(result, Err(VerifyError::ManifestMalformed)));
+    }
+
+    // ==================== Detached Signature Tests ====================
+
+    fn copy_bundle(name: &str) -> tempfile::TempDir {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for entry in fs::read_dir(fixtures_dir().join(name)).unwrap() {
+            let path = entry.unwrap().path();
+            fs::copy(&path, temp_dir.path().join(path.file_name().unwrap())).unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_verify_detached_bundle() {
+        let dir = fixtures_dir().join("detached_bundle");
+        let result = verify_standard_bundle(&dir).unwrap();
+
+        assert_eq!(result.manifest.signature, None);
+        assert_eq!(result.trust_level, TrustLevel::C);
+        assert_eq!(result.signature, fs::read_to_string(dir.join("manifest.sig")).unwrap().trim());
+    }
+
+    #[test]
+    fn test_verify_detached_signature_as_raw_bytes() {
+        let temp_dir = copy_bundle("detached_bundle");
+        let sig_path = temp_dir.path().join("manifest.sig");
+        let raw = decode_base64(fs::read_to_string(&sig_path).unwrap().trim()).unwrap();
+        fs::write(&sig_path, raw).unwrap();
+
+        assert!(verify_standard_bundle(temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_detached_signature_covers_whole_manifest() {
+        let temp_dir = copy_bundle("detached_bundle");
+        let manifest_path = temp_dir.path().join("manifest.json");
+        let tampered = fs::read_to_string(&manifest_path).unwrap().replace("1.0.0", "2.0.0");
+        fs::write(&manifest_path, tampered).unwrap();
+
+        assert!(matches!(verify_standard_bundle(temp_dir.path()), Err(VerifyError::SignatureInvalid)));
+    }
+
+    #[test]
+    fn test_embedded_and_detached_signature_together_is_malformed() {
+        let temp_dir = copy_bundle("minimal_bundle");
+        fs::copy(fixtures_dir().join("detached_bundle").join("manifest.sig"), temp_dir.path().join("manifest.sig")).unwrap();
+
+        assert!(matches!(verify_standard_bundle(temp_dir.path()), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_missing_signature_is_malformed() {
+        let temp_dir = copy_bundle("detached_bundle");
+        fs::remove_file(temp_dir.path().join("manifest.sig")).unwrap();
+
+        assert!(matches!(verify_standard_bundle(temp_dir.path()), Err(VerifyError::ManifestMalformed)));
+    }
+
+    #[test]
+    fn test_verify_open_bundle_with_detached_signature() {
+        use std::io::Write;
+
+        let dir = fixtures_dir().join("detached_bundle");
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for name in ["recording.m4a", "manifest.json", "manifest.sig"] {
+            zip.start_file(name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(&fs::read(dir.join(name)).unwrap()).unwrap();
+        }
+        let bundle = zip.finish().unwrap().into_inner();
+
+        assert!(verify_open_bytes(&bundle, &VerifyOptions::default()).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn test_large_standard_bundle_is_verified_through_mmap() {
+        use p256::ecdsa::signature::Signer;
+        use sha2::Digest;
+
+        let dir = tempfile::tempdir().unwrap();
+        let audio: Vec<u8> = (0..crate::mmap::MMAP_THRESHOLD_BYTES + 1).map(|i| (i % 251) as u8).collect();
+        let key = p256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let mut manifest: serde_json::Value = serde_json::from_slice(&fs::read(fixtures_dir().join("minimal_bundle/manifest.json")).unwrap()).unwrap();
+        manifest["publicKey"] = BASE64.encode(crate::crypto::raw_public_key(key.verifying_key())).into();
+        manifest["audioHash"] = BASE64.encode(sha2::Sha256::digest(&audio)).into();
+        manifest["audioSizeBytes"] = audio.len().into();
+        let hash = compute_canonical_hash_from_bytes(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        let signature: p256::ecdsa::Signature = key.sign(&hash);
+        manifest["signature"] = BASE64.encode(signature.to_bytes()).into();
+        fs::write(dir.path().join("manifest.json"), serde_json::to_vec(&manifest).unwrap()).unwrap();
+        fs::write(dir.path().join("recording.m4a"), &audio).unwrap();
+
+        verify_standard_bundle(dir.path()).unwrap();
+        fs::write(dir.path().join("recording.m4a"), &audio[1..]).unwrap();
+        assert!(matches!(verify_standard_bundle(dir.path()), Err(VerifyError::HashMismatch)));
+    }
+
+    #[test]
+    fn test_verify_tar_archive_bundles() {
+        let full = verify_standard_bundle(&fixtures_dir().join("full_bundle")).unwrap();
+        // Only the gzipped archive is checked in; the plain one is unpacked from it
+        let gzipped = fixtures_dir().join("full_bundle.tar.gz");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plain = temp_dir.path().join("full_bundle.tar");
+        let mut tar_bytes = Vec::new();
+        flate2::read::GzDecoder::new(fs::File::open(&gzipped).unwrap()).read_to_end(&mut tar_bytes).unwrap();
+        fs::write(&plain, tar_bytes).unwrap();
+
+        for path in [plain, gzipped] {
+            assert_eq!(detect_bundle_type(&path), BundleType::Archive);
+            let result = verify_bundle(&path, None, &VerifyOptions::default()).unwrap();
+            assert_eq!(result.manifest_hash, full.manifest_hash);
+            assert_eq!(load_manifest_bytes(&path, None).unwrap(), fs::read(fixtures_dir().join("full_bundle/manifest.json")).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_zip_and_tar_pick_the_first_audio_entry() {
+        use std::io::Write;
+
+        let dir = fixtures_dir().join("minimal_bundle");
+        let (audio, manifest) = (fs::read(dir.join("recording.m4a")).unwrap(), fs::read(dir.join("manifest.json")).unwrap());
+        let files: [(&str, &[u8]); 4] =
+            [("notes.txt", b"not audio"), ("recording.m4a", &audio), ("manifest.json", &manifest), ("decoy.m4a", b"decoy")];
+
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, data) in files {
+            zip.start_file(name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        let zipped = zip.finish().unwrap().into_inner();
+        let archive = tar::write_archive(&files);
+
+        let options = VerifyOptions::default();
+        assert_eq!(read_open_bytes(&zipped, &options).unwrap().media, audio);
+        assert_eq!(read_archive_bytes(&archive, &options).unwrap().media, audio);
+    }
+
+    #[test]
+    fn test_tar_archive_missing_required_files() {
+        let dir = fixtures_dir().join("detached_bundle");
+        let read = |name: &str| fs::read(dir.join(name)).unwrap();
+        let (audio, manifest, signature) = (read("recording.m4a"), read("manifest.json"), read("manifest.sig"));
+
+        let archive = tar::write_archive(&[("b/manifest.sig", &signature), ("b/recording.m4a", &audio), ("b/manifest.json", &manifest)]);
+        let contents = read_archive_bytes(&archive, &VerifyOptions::default()).unwrap();
+        assert!(verify_core(&contents.media, &contents.manifest, contents.signature.as_deref(), &VerifyOptions::default()).is_ok());
+
+        let archive = tar::write_archive(&[("recording.m4a", &audio)]);
+        assert!(matches!(read_archive_bytes(&archive, &VerifyOptions::default()), Err(VerifyError::ManifestMissing)));
+        let archive = tar::write_archive(&[("manifest.json", &manifest), ("notes.txt", b"not audio")]);
+        assert!(matches!(read_archive_bytes(&archive, &VerifyOptions::default()), Err(VerifyError::AudioFileMissing)));
+    }
+
+    #[test]
+    fn test_extract_path_is_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("out");
+        fs::write(&file, b"not a directory").unwrap();
+
+        let error = prepare_extract_dir(&file).unwrap_err();
+        assert!(matches!(error, VerifyError::ExtractPathNotDirectory { .. }));
+        assert_eq!(error.exit_code(), 25);
+        // A file further up the path is reported the same way
+        assert!(matches!(prepare_extract_dir(&file.join("nested")), Err(VerifyError::ExtractPathNotDirectory { .. })));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_extract_dir_not_writable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        fs::create_dir(&out).unwrap();
+        fs::set_permissions(&out, fs::Permissions::from_mode(0o555)).unwrap();
+
+        let error = prepare_extract_dir(&out).unwrap_err();
+        fs::set_permissions(&out, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(matches!(error, VerifyError::ExtractDirNotWritable { .. }));
+        assert_eq!(error.exit_code(), 26);
+        assert!(prepare_extract_dir(&out).is_ok());
+    }
+
+    #[test]
+    fn test_detached_signature_path() {
+        assert_eq!(detached_signature_path(Path::new("a/take2.json")), Path::new("a/take2.sig"));
+        assert_eq!(detached_signature_path(Path::new("a/manifest.json.gz")), Path::new("a/manifest.sig"));
+    }
+
+    // ==================== Policy Tests ====================
+
+    #[test]
+    fn test_required_vectors_present_in_full_bundle() {
+        let bundle_path = fixtures_dir().join("full_bundle");
+        let options = VerifyOptions::new().with_required_vectors([TrustVector::Location, TrustVector::Clock]);
+
+        assert!(verify_standard_bundle_with_options(&bundle_path, &options).is_ok());
+    }
+
+    #[test]
+    fn test_required_vectors_absent_from_minimal_bundle() {
+        let bundle_path = fixtures_dir().join("minimal_bundle");
+        let options = VerifyOptions::new().with_required_vectors([TrustVector::Location, TrustVector::Clock]);
+
+        let result = verify_standard_bundle_with_options(&bundle_path, &options);
+        assert!(matches!(
+            result.unwrap_err(),
+            VerifyError::RequiredVectorMissing { vector: TrustVector::Location }
+        ));
+    }
+
+    #[test]
+    fn test_full_context_present_in_full_bundle() {
+        let bundle_path = fixtures_dir().join("full_bundle");
+        let options = VerifyOptions::new().with_full_context_required(true);
+
+        assert!(verify_standard_bundle_with_options(&bundle_path, &options).is_ok());
+    }
+
+    #[test]
+    fn test_full_context_absent_from_minimal_bundle() {
+        let bundle_path = fixtures_dir().join("minimal_bundle");
+        let options = VerifyOptions::new().with_full_context_required(true);
+
+        let error = verify_standard_bundle_with_options(&bundle_path, &options).unwrap_err();
+        match &error {
+            VerifyError::IncompleteContext { missing } => assert_eq!(missing, &TrustVector::ALL),
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert_eq!(error.exit_code(), 19);
+        assert_eq!(
+            error.to_string(),
+            "Full context required; missing trust vectors: location, motion, continuity, clock"
+        );
+    }
+
+    #[test]
+    fn test_max_interruptions_limit() {
+        // Two interruption events
+        let interrupted = fixtures_dir().join("interrupted_bundle");
+        let limit = |max| verify_standard_bundle_with_options(&interrupted, &VerifyOptions::new().with_max_interruptions(max));
+
+        assert!(limit(3).is_ok());
+        assert!(limit(2).is_ok());
+        let error = limit(1).unwrap_err();
+        assert!(matches!(error, VerifyError::TooManyInterruptions { count: 2, max: 1 }));
+        assert_eq!(error.exit_code(), 23);
+
+        // No continuity vector means no recorded interruptions
+        let minimal = fixtures_dir().join("minimal_bundle");
+        assert!(verify_standard_bundle_with_options(&minimal, &VerifyOptions::new().with_max_interruptions(0)).is_ok());
+    }
+
+    #[test]
+    fn test_integrity_only_agrees_with_full_verification() {
+        let read = |bundle: &str, name: &str| fs::read(fixtures_dir().join(bundle).join(name)).unwrap();
+        let mut cases = Vec::new();
+        for bundle in ["full_bundle", "minimal_bundle", "message_signed_bundle", "android_key_bundle"] {
+            cases.push((read(bundle, "recording.m4a"), read(bundle, "manifest.json")));
+        }
+        let (audio, manifest) = cases[0].clone();
+        let mut tampered_audio = audio.clone();
+        tampered_audio[0] ^= 0xFF;
+        let tampered_manifest = String::from_utf8(manifest.clone()).unwrap().replace("1.0.0", "9.9.9").into_bytes();
+        cases.push((tampered_audio, manifest));
+        cases.push((audio, tampered_manifest));
+
+        for (audio, manifest) in &cases {
+            let fast = verify_integrity_only(audio, manifest);
+            let full = verify_audio_and_manifest(audio, manifest);
+            assert_eq!(fast.is_ok(), full.is_ok());
+            if let (Err(fast), Err(full)) = (fast, full) {
+                assert_eq!(fast.exit_code(), full.exit_code());
+            }
+        }
+        assert_eq!(cases.iter().filter(|(a, m)| verify_integrity_only(a, m).is_ok()).count(), 4);
+    }
+
+    #[test]
+    fn test_verify_with_parsed_manifest_matches_full_path() {
+        for bundle in ["full_bundle", "message_signed_bundle", "gzip_bundle"] {
+            let dir = fixtures_dir().join(bundle);
+            let audio = fs::read(dir.join("recording.m4a")).unwrap();
+            let manifest_bytes = load_manifest_bytes(&dir, None).unwrap();
+            let manifest = SignedAudioManifest::from_json(&decompress_manifest(&manifest_bytes).unwrap()).unwrap();
+
+            let cached = verify_audio_with_manifest_and_bytes(&audio, &manifest, &manifest_bytes).unwrap();
+            let full = verify_audio_and_manifest(&audio, &manifest_bytes).unwrap();
+            assert_eq!(cached.manifest_hash, full.manifest_hash);
+            assert_eq!(cached.trust_level, full.trust_level);
+            assert_eq!(cached.signature, full.signature);
+            assert_eq!(cached.warnings.len(), full.warnings.len());
+
+            let result = verify_audio_with_manifest_and_bytes(b"other audio", &manifest, &manifest_bytes);
+            assert!(matches!(result, Err(VerifyError::HashMismatch)));
+        }
+    }
+
+    #[test]
+    fn test_verify_with_parsed_manifest_rejects_unsigned_fields() {
+        let dir = fixtures_dir().join("full_bundle");
+        let audio = fs::read(dir.join("recording.m4a")).unwrap();
+        let manifest_bytes = load_manifest_bytes(&dir, None).unwrap();
+        let manifest = SignedAudioManifest::from_json(&manifest_bytes).unwrap();
+
+        let mut other_hash = manifest.clone();
+        other_hash.audio_hash = crate::crypto::sha256_base64(b"other audio");
+        let result = verify_audio_with_manifest_and_bytes(b"other audio", &other_hash, &manifest_bytes);
+        assert!(matches!(result, Err(VerifyError::SignatureInvalid)));
+
+        let mut other_key = manifest.clone();
+        other_key.public_key = fs::read_to_string(fixtures_dir().join("android_key_bundle/manifest.json"))
+            .map(|json| SignedAudioManifest::from_json(json.as_bytes()).unwrap().public_key)
+            .unwrap();
+        let result = verify_audio_with_manifest_and_bytes(&audio, &other_key, &manifest_bytes);
+        assert!(matches!(result, Err(VerifyError::SignatureInvalid)));
+    }
+
+    #[test]
+    fn test_custom_checks_add_warnings_in_order() {
+        fn geofence(manifest: &SignedAudioManifest) -> Vec<Warning> {
+            match &manifest.trust_vectors.location {
+                Some(location) if location.start.lat > 37.7 => Vec::new(),
+                _ => vec![Warning::Custom { code: "outside_geofence", message: "Recorded outside the office".to_string() }],
+            }
+        }
+        fn always(_: &SignedAudioManifest) -> Vec<Warning> {
+            vec![Warning::Custom { code: "reviewed", message: "Needs review".to_string() }]
+        }
+        let options = VerifyOptions::new().with_check(geofence).with_check(always);
+
+        let full = verify_standard_bundle_with_options(&fixtures_dir().join("full_bundle"), &options).unwrap();
+        let codes: Vec<_> = full.warnings.iter().map(Warning::code).collect();
+        assert_eq!(codes, ["reviewed"]);
+
+        let minimal = verify_standard_bundle_with_options(&fixtures_dir().join("minimal_bundle"), &options).unwrap();
+        let codes: Vec<_> = minimal.warnings.iter().map(Warning::code).collect();
+        assert_eq!(codes, ["outside_geofence", "reviewed"]);
+        assert_eq!(minimal.warnings[0].to_string(), "Recorded outside the office");
+
+        // Authenticity is decided before checks run
+        let mut audio = fs::read(fixtures_dir().join("full_bundle/recording.m4a")).unwrap();
+        audio[0] ^= 0xFF;
+        let manifest = fs::read(fixtures_dir().join("full_bundle/manifest.json")).unwrap();
+        assert!(matches!(verify_audio_and_manifest_with_options(&audio, &manifest, &options), Err(VerifyError::HashMismatch)));
+    }
+
+    #[test]
+    fn test_newer_schema_accepted_only_with_override() {
+        let bundle = fixtures_dir().join("schema_v2_bundle");
+        let result = verify_standard_bundle(&bundle);
+        assert!(matches!(result, Err(VerifyError::SchemaUnsupported { version: 2 })));
+
+        let options = VerifyOptions::new().with_allowed_schema_version(2);
+        assert_eq!(options.max_schema_version(), 2);
+        let result = verify_standard_bundle_with_options(&bundle, &options).unwrap();
+        assert_eq!(result.manifest.schema_version, 2);
+
+        // Lowering the ceiling isn't possible
+        let options = VerifyOptions::new().with_allowed_schema_version(0);
+        assert!(verify_standard_bundle_with_options(&fixtures_dir().join("minimal_bundle"), &options).is_ok());
+    }
+
+    #[test]
+    fn test_hash_registry_flags_duplicate_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = HashRegistry::new(&dir.path().join("seen.txt"));
+        let options = VerifyOptions::new().with_hash_registry(registry);
+        let bundle_path = fixtures_dir().join("full_bundle");
+
+        let first = verify_standard_bundle_with_options(&bundle_path, &options).unwrap();
+        assert!(!first.warnings.contains(&Warning::DuplicateRecording));
+        let second = verify_standard_bundle_with_options(&bundle_path, &options).unwrap();
+        assert!(second.warnings.contains(&Warning::DuplicateRecording));
+
+        let strict = options.with_duplicates_rejected(true);
+        let result = verify_standard_bundle_with_options(&bundle_path, &strict);
+        assert!(matches!(result, Err(VerifyError::DuplicateRecording)));
+    }
+
+    #[test]
+    fn test_required_vectors_checked_after_signature() {
+        // Tampered audio must still report the hash mismatch, not a policy failure
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::copy(
+            fixtures_dir().join("minimal_bundle").join("manifest.json"),
+            temp_dir.path().join("manifest.json"),
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("recording.m4a"), b"tampered").unwrap();
+
+        let options = VerifyOptions::new().with_required_vectors([TrustVector::Location]);
+        let result = verify_standard_bundle_with_options(temp_dir.path(), &options);
+        assert!(matches!(result.unwrap_err(), VerifyError::HashMismatch));
+    }
+
     // ==================== Trust Level Tests ====================
 
     #[test]
@@ -0,0 +1,434 @@
+//! Verification warnings.
+//!
+//! Warnings flag manifest data that is suspicious but doesn't make a
+//! recording fail verification, such as a wall clock that disagrees with the
+//! monotonic clock. They're computed only after the signature has verified.
+
+use std::fmt;
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::crypto::{decode_base64, parse_public_key, raw_public_key, sha256_matches};
+use crate::manifest::{LocationSnapshot, SignedAudioManifest};
+use crate::mp4::AudioProperties;
+use crate::timezones::is_iana_time_zone;
+
+/// `durationSeconds`/capture timestamp disagreement tolerated before warning, in seconds.
+pub const DURATION_TOLERANCE_SECONDS: f64 = 2.0;
+
+/// Wall-clock/monotonic disagreement tolerated before warning, in seconds.
+pub const CLOCK_SKEW_TOLERANCE_SECONDS: f64 = 2.0;
+
+/// Monotonic delta/`durationSeconds` disagreement tolerated before warning, in seconds.
+pub const MONOTONIC_DELTA_TOLERANCE_SECONDS: f64 = 2.0;
+
+/// Slowest plausible motion sensor sampling rate, in Hz.
+pub const MIN_MOTION_SAMPLE_RATE_HZ: f64 = 10.0;
+
+/// Fastest plausible motion sensor sampling rate, in Hz.
+pub const MAX_MOTION_SAMPLE_RATE_HZ: f64 = 100.0;
+
+/// Minimum start-to-end distance reported as drift, in meters.
+pub const LOCATION_DRIFT_MIN_METERS: f64 = 100.0;
+
+/// A custom check run after verification; see [`VerifyOptions::with_check`].
+///
+/// [`VerifyOptions::with_check`]: crate::options::VerifyOptions::with_check
+pub type CheckFn = fn(&SignedAudioManifest) -> Vec<Warning>;
+
+/// A suspicious but non-fatal finding.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// `deviceKeyId` isn't the SHA-256 fingerprint of `publicKey`.
+    DeviceKeyIdMismatch,
+    /// `audioFormat` isn't a format bundles carry.
+    UnknownAudioFormat { format: String },
+    /// `durationSeconds` disagrees with the capture timestamps.
+    DurationMismatch { duration_seconds: f64, span_seconds: f64 },
+    /// The wall-clock span differs from the monotonic span.
+    ClockSkew { skew_seconds: f64 },
+    /// `durationSeconds` disagrees with the duration the audio container records.
+    AudioDurationMismatch { declared_seconds: f64, container_seconds: f64 },
+    /// `audioFormat` disagrees with the codec the audio container holds.
+    AudioFormatMismatch { declared: String, codec: String },
+    /// The monotonic delta isn't positive or disagrees with `durationSeconds`.
+    ImplausibleMonotonicDelta { delta_seconds: f64, duration_seconds: f64 },
+    /// `sampleCount` over the motion `duration` is outside the rates motion sensors sample at.
+    ImplausibleSampleRate { sample_rate_hz: f64 },
+    /// The motion `duration` disagrees with `durationSeconds`.
+    MotionDurationMismatch { motion_seconds: f64, duration_seconds: f64 },
+    /// The device moved further than location accuracy explains.
+    LocationDrift { distance_meters: f64 },
+    /// Interruption events timestamped outside `captureStart..=captureEnd`.
+    InterruptionOutsideCapture { count: usize },
+    /// The audio hash is already in the seen-hashes registry.
+    DuplicateRecording,
+    /// Another sealed bundle with the same salt was sealed with this nonce.
+    NonceReused,
+    /// A co-signature in `signatures` doesn't verify.
+    CoSignatureInvalid { role: String },
+    /// The clock vector's `timeZone` isn't in the IANA time zone database.
+    UnknownTimeZone { time_zone: String },
+    /// A password was given for a bundle that isn't sealed. Raised by the CLI.
+    PasswordUnused,
+    /// Raised by a custom check registered by the library caller. `code`
+    /// should be snake_case like the built-in codes, as the report schema
+    /// requires.
+    Custom { code: &'static str, message: String },
+}
+
+impl Warning {
+    /// Stable machine-readable identifier.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Warning::DeviceKeyIdMismatch => "device_key_id_mismatch",
+            Warning::UnknownAudioFormat { .. } => "unknown_audio_format",
+            Warning::DurationMismatch { .. } => "duration_mismatch",
+            Warning::AudioDurationMismatch { .. } => "audio_duration_mismatch",
+            Warning::AudioFormatMismatch { .. } => "audio_format_mismatch",
+            Warning::ClockSkew { .. } => "clock_skew",
+            Warning::ImplausibleMonotonicDelta { .. } => "implausible_monotonic_delta",
+            Warning::ImplausibleSampleRate { .. } => "implausible_sample_rate",
+            Warning::MotionDurationMismatch { .. } => "motion_duration_mismatch",
+            Warning::LocationDrift { .. } => "location_drift",
+            Warning::InterruptionOutsideCapture { .. } => "interruption_outside_capture",
+            Warning::DuplicateRecording => "duplicate_recording",
+            Warning::NonceReused => "nonce_reused",
+            Warning::CoSignatureInvalid { .. } => "co_signature_invalid",
+            Warning::UnknownTimeZone { .. } => "unknown_time_zone",
+            Warning::PasswordUnused => "password_unused",
+            Warning::Custom { code, .. } => code,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::DeviceKeyIdMismatch => write!(f, "Device key ID is not the fingerprint of the public key"),
+            Warning::UnknownAudioFormat { format } => write!(f, "Unknown audio format {:?}", format),
+            Warning::DurationMismatch { duration_seconds, span_seconds } => write!(
+                f,
+                "Duration is {:.1}s but capture timestamps span {:.1}s",
+                duration_seconds, span_seconds
+            ),
+            Warning::AudioDurationMismatch { declared_seconds, container_seconds } => write!(
+                f,
+                "Duration is {:.1}s but the audio container says {:.1}s",
+                declared_seconds, container_seconds
+            ),
+            Warning::AudioFormatMismatch { declared, codec } => {
+                write!(f, "Audio format is {:?} but the audio container holds {:?}", declared, codec)
+            }
+            Warning::ClockSkew { skew_seconds } => {
+                write!(f, "Wall clock and monotonic clock disagree by {:.1}s", skew_seconds)
+            }
+            Warning::ImplausibleMonotonicDelta { delta_seconds, .. } if *delta_seconds <= 0.0 => {
+                write!(f, "Monotonic clock delta is {:.1}s; it must be positive", delta_seconds)
+            }
+            Warning::ImplausibleMonotonicDelta { delta_seconds, duration_seconds } => write!(
+                f,
+                "Monotonic clock advanced {:.1}s but the duration is {:.1}s",
+                delta_seconds, duration_seconds
+            ),
+            Warning::ImplausibleSampleRate { sample_rate_hz } => write!(
+                f,
+                "Motion was sampled at {:.1} Hz, outside the plausible {}-{} Hz",
+                sample_rate_hz, MIN_MOTION_SAMPLE_RATE_HZ, MAX_MOTION_SAMPLE_RATE_HZ
+            ),
+            Warning::MotionDurationMismatch { motion_seconds, duration_seconds } => write!(
+                f,
+                "Motion was recorded for {:.1}s but the duration is {:.1}s",
+                motion_seconds, duration_seconds
+            ),
+            Warning::LocationDrift { distance_meters } => {
+                write!(f, "Location moved {:.0}m during capture", distance_meters)
+            }
+            Warning::InterruptionOutsideCapture { count } => {
+                write!(f, "{} interruption event(s) fall outside the capture window", count)
+            }
+            Warning::DuplicateRecording => write!(f, "This recording has been verified before"),
+            Warning::NonceReused => write!(f, "Encryption nonce reused from another sealed bundle with the same salt"),
+            Warning::CoSignatureInvalid { role } => write!(f, "Co-signature by {:?} does not verify", role),
+            Warning::UnknownTimeZone { time_zone } => write!(f, "Unknown time zone {:?}", time_zone),
+            Warning::PasswordUnused => write!(f, "A password was given but the bundle is not sealed, so it had no effect"),
+            Warning::Custom { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Compare a verified manifest with the properties its audio container records.
+pub fn audio_property_warnings(manifest: &SignedAudioManifest, detected: &AudioProperties) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    if let Some(container_seconds) = detected.duration_seconds {
+        if (container_seconds - manifest.duration_seconds).abs() > DURATION_TOLERANCE_SECONDS {
+            warnings.push(Warning::AudioDurationMismatch {
+                declared_seconds: manifest.duration_seconds,
+                container_seconds,
+            });
+        }
+    }
+
+    // m4a and mp4 name the container, so only aac and wav say what it holds
+    let codec = detected.codec.as_deref().unwrap_or("mp4");
+    let mismatch = match manifest.audio_format.to_ascii_lowercase().as_str() {
+        "aac" => detected.codec.as_deref().is_some_and(|codec| codec != "mp4a"),
+        "wav" => true,
+        _ => false,
+    };
+    if mismatch {
+        warnings.push(Warning::AudioFormatMismatch {
+            declared: manifest.audio_format.clone(),
+            codec: codec.to_string(),
+        });
+    }
+
+    warnings
+}
+
+/// Collect warnings for a verified manifest.
+pub fn collect_warnings(manifest: &SignedAudioManifest) -> Vec<Warning> {
+    let vectors = &manifest.trust_vectors;
+    let mut warnings = Vec::new();
+
+    // deviceKeyId = Base64(SHA256(raw 64-byte public key))
+    let fingerprint_matches = decode_base64(&manifest.public_key)
+        .and_then(|bytes| parse_public_key(&bytes))
+        .is_ok_and(|public_key| sha256_matches(&raw_public_key(&public_key), &manifest.device_key_id));
+    if !fingerprint_matches {
+        warnings.push(Warning::DeviceKeyIdMismatch);
+    }
+
+    if !manifest.has_known_audio_format() {
+        warnings.push(Warning::UnknownAudioFormat {
+            format: manifest.audio_format.clone(),
+        });
+    }
+
+    // RFC 3339 offsets are applied when parsing, so the span is timezone-correct
+    let capture = parse_timestamp(&manifest.capture_start).zip(parse_timestamp(&manifest.capture_end));
+    if let Some((start, end)) = capture {
+        let span_seconds = (end - start).as_seconds_f64();
+        if (span_seconds - manifest.duration_seconds).abs() > DURATION_TOLERANCE_SECONDS {
+            warnings.push(Warning::DurationMismatch {
+                duration_seconds: manifest.duration_seconds,
+                span_seconds,
+            });
+        }
+    }
+
+    if let Some(clock) = &vectors.clock {
+        if let (Some(start), Some(end)) = (parse_timestamp(&clock.wall_clock_start), parse_timestamp(&clock.wall_clock_end)) {
+            let wall_seconds = (end - start).as_seconds_f64();
+            let skew_seconds = (wall_seconds - clock.monotonic_delta).abs();
+            if skew_seconds > CLOCK_SKEW_TOLERANCE_SECONDS {
+                warnings.push(Warning::ClockSkew { skew_seconds });
+            }
+        }
+
+        // The monotonic clock can't be set, so it should track the recording itself
+        let delta_seconds = clock.monotonic_delta;
+        if delta_seconds <= 0.0 || (delta_seconds - manifest.duration_seconds).abs() > MONOTONIC_DELTA_TOLERANCE_SECONDS {
+            warnings.push(Warning::ImplausibleMonotonicDelta {
+                delta_seconds,
+                duration_seconds: manifest.duration_seconds,
+            });
+        }
+
+        if !is_iana_time_zone(&clock.time_zone) {
+            warnings.push(Warning::UnknownTimeZone {
+                time_zone: clock.time_zone.clone(),
+            });
+        }
+    }
+
+    if let Some(motion) = &vectors.motion {
+        // No samples over no time says nothing about the sensor
+        if motion.sample_count > 0 || motion.duration > 0.0 {
+            let sample_rate_hz = motion.sample_count as f64 / motion.duration;
+            if !(MIN_MOTION_SAMPLE_RATE_HZ..=MAX_MOTION_SAMPLE_RATE_HZ).contains(&sample_rate_hz) {
+                warnings.push(Warning::ImplausibleSampleRate { sample_rate_hz });
+            }
+        }
+
+        if (motion.duration - manifest.duration_seconds).abs() > DURATION_TOLERANCE_SECONDS {
+            warnings.push(Warning::MotionDurationMismatch {
+                motion_seconds: motion.duration,
+                duration_seconds: manifest.duration_seconds,
+            });
+        }
+    }
+
+    if let Some(location) = &vectors.location {
+        let distance_meters = distance_meters(&location.start, &location.end);
+        let tolerance = (location.start.accuracy + location.end.accuracy).max(LOCATION_DRIFT_MIN_METERS);
+        if distance_meters > tolerance {
+            warnings.push(Warning::LocationDrift { distance_meters });
+        }
+    }
+
+    if let (Some(continuity), Some((start, end))) = (&vectors.continuity, capture) {
+        // Unparseable event timestamps can't be placed, so aren't counted
+        let count = continuity
+            .interruption_events
+            .iter()
+            .filter_map(|event| parse_timestamp(&event.timestamp))
+            .filter(|at| !(start..=end).contains(at))
+            .count();
+        if count > 0 {
+            warnings.push(Warning::InterruptionOutsideCapture { count });
+        }
+    }
+
+    warnings
+}
+
+fn parse_timestamp(s: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(s, &Rfc3339).ok()
+}
+
+/// Great-circle distance between two snapshots (haversine).
+pub fn distance_meters(a: &LocationSnapshot, b: &LocationSnapshot) -> f64 {
+    const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+    let (lat1, lat2) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (b.lon - a.lon).to_radians();
+
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_METERS * h.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::verify_standard_bundle;
+    use std::path::PathBuf;
+
+    fn fixture_warnings(name: &str) -> Vec<Warning> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name);
+        verify_standard_bundle(&path).unwrap().warnings
+    }
+
+    #[test]
+    fn test_clean_bundles_have_no_warnings() {
+        assert!(fixture_warnings("full_bundle").is_empty());
+        assert!(fixture_warnings("minimal_bundle").is_empty());
+    }
+
+    #[test]
+    fn test_device_key_id_must_fingerprint_public_key() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/full_bundle/manifest.json");
+        let mut manifest = SignedAudioManifest::from_json(&std::fs::read(path).unwrap()).unwrap();
+        assert!(!collect_warnings(&manifest).contains(&Warning::DeviceKeyIdMismatch));
+
+        manifest.device_key_id = crate::crypto::sha256_base64(b"some other key");
+        assert_eq!(collect_warnings(&manifest), [Warning::DeviceKeyIdMismatch]);
+    }
+
+    #[test]
+    fn test_audio_format_must_be_known() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/full_bundle/manifest.json");
+        let mut manifest = SignedAudioManifest::from_json(&std::fs::read(path).unwrap()).unwrap();
+        for format in ["aac", "M4A", "wav"] {
+            manifest.audio_format = format.to_string();
+            assert!(collect_warnings(&manifest).is_empty(), "{format}");
+        }
+
+        manifest.audio_format = "mp3".to_string();
+        let warnings = collect_warnings(&manifest);
+        assert_eq!(warnings, [Warning::UnknownAudioFormat { format: "mp3".to_string() }]);
+        assert_eq!(warnings[0].code(), "unknown_audio_format");
+    }
+
+    #[test]
+    fn test_declared_audio_disagreeing_with_container() {
+        let warnings = fixture_warnings("container_duration_mismatch_bundle");
+        assert_eq!(warnings, [Warning::AudioDurationMismatch { declared_seconds: 60.0, container_seconds: 12.0 }]);
+        assert_eq!(warnings[0].code(), "audio_duration_mismatch");
+
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/full_bundle/manifest.json");
+        let mut manifest = SignedAudioManifest::from_json(&std::fs::read(path).unwrap()).unwrap();
+        let detected = AudioProperties {
+            duration_seconds: Some(manifest.duration_seconds + 1.0),
+            codec: Some("alac".to_string()),
+            ..AudioProperties::default()
+        };
+        manifest.audio_format = "m4a".to_string();
+        assert!(audio_property_warnings(&manifest, &detected).is_empty());
+        manifest.audio_format = "AAC".to_string();
+        assert_eq!(
+            audio_property_warnings(&manifest, &detected),
+            [Warning::AudioFormatMismatch { declared: "AAC".to_string(), codec: "alac".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_duration_disagreeing_with_timestamps() {
+        // captureEnd carries a +02:00 offset; the true span is 1s
+        assert_eq!(
+            fixture_warnings("duration_mismatch_bundle"),
+            [Warning::DurationMismatch { duration_seconds: 30.0, span_seconds: 1.0 }]
+        );
+    }
+
+    #[test]
+    fn test_interruption_outside_capture_window() {
+        assert_eq!(
+            fixture_warnings("interrupted_bundle"),
+            [Warning::InterruptionOutsideCapture { count: 1 }]
+        );
+    }
+
+    #[test]
+    fn test_monotonic_delta_must_be_positive_and_match_duration() {
+        let warnings = fixture_warnings("negative_delta_bundle");
+        let codes: Vec<_> = warnings.iter().map(Warning::code).collect();
+        assert_eq!(codes, ["clock_skew", "implausible_monotonic_delta"]);
+        assert_eq!(warnings[1].to_string(), "Monotonic clock delta is -5.0s; it must be positive");
+
+        // Consistent with the wall clock, but not with the recording
+        assert_eq!(
+            fixture_warnings("long_delta_bundle"),
+            [Warning::ImplausibleMonotonicDelta { delta_seconds: 600.0, duration_seconds: 5.0 }]
+        );
+    }
+
+    #[test]
+    fn test_motion_sample_rate_must_be_plausible() {
+        assert_eq!(fixture_warnings("fast_motion_bundle"), [Warning::ImplausibleSampleRate { sample_rate_hz: 6000.0 }]);
+
+        let warnings = fixture_warnings("sparse_motion_bundle");
+        let codes: Vec<_> = warnings.iter().map(Warning::code).collect();
+        assert_eq!(codes, ["implausible_sample_rate", "motion_duration_mismatch"]);
+        assert_eq!(warnings[1].to_string(), "Motion was recorded for 60.0s but the duration is 1.0s");
+    }
+
+    #[test]
+    fn test_clock_time_zone_must_be_iana() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/full_bundle/manifest.json");
+        let mut manifest = SignedAudioManifest::from_json(&std::fs::read(path).unwrap()).unwrap();
+        let clock = manifest.trust_vectors.clock.as_mut().unwrap();
+
+        clock.time_zone = "America/Los_Angeles".to_string();
+        assert!(collect_warnings(&manifest).is_empty());
+
+        manifest.trust_vectors.clock.as_mut().unwrap().time_zone = "Narnia/Capital".to_string();
+        assert_eq!(
+            collect_warnings(&manifest),
+            [Warning::UnknownTimeZone { time_zone: "Narnia/Capital".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_skewed_bundle_warns_about_clock_and_location() {
+        let warnings = fixture_warnings("skewed_bundle");
+        let codes: Vec<_> = warnings.iter().map(Warning::code).collect();
+        assert_eq!(codes, ["clock_skew", "location_drift"]);
+
+        assert_eq!(warnings[0], Warning::ClockSkew { skew_seconds: 5.0 });
+        assert!(matches!(warnings[1], Warning::LocationDrift { distance_meters } if (1100.0..1125.0).contains(&distance_meters)));
+    }
+}
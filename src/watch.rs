@@ -0,0 +1,319 @@
+//! Watching an intake directory for bundles to verify.
+//!
+//! File events from the platform's native watcher (via `notify`) trigger a
+//! scan of the directory. Scans also run every [`POLL_INTERVAL`] while a
+//! bundle is settling, and every [`IDLE_RESCAN_INTERVAL`] otherwise, so a
+//! network filesystem that delivers no events is still picked up; if no
+//! native watcher can be started, the directory is polled.
+//!
+//! A bundle is handed out once its size and modification time have stopped
+//! changing for the settle time, so a bundle still being copied in isn't
+//! verified half-written. Each version of a bundle is handed out once; one
+//! that changes afterwards is watched again.
+//!
+//! Results can be filed into `verified/` and `failed/` subdirectories, which
+//! are never watched themselves.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use notify::{recommended_watcher, Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::batch::{discover_bundles, needs_password, verify_batch, BatchEntry, BatchRecord};
+use crate::error::{Result, VerifyError};
+use crate::options::VerifyOptions;
+
+/// How long a bundle must stay unchanged before it is verified, by default.
+pub const DEFAULT_SETTLE: Duration = Duration::from_secs(2);
+
+/// Delay between scans while a bundle is settling, or without file events.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Delay between scans of an idle directory when file events are delivered.
+pub const IDLE_RESCAN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Subdirectory verified bundles are moved to.
+pub const VERIFIED_DIR: &str = "verified";
+
+/// Subdirectory bundles that failed verification are moved to.
+pub const FAILED_DIR: &str = "failed";
+
+/// Size and modification time of a bundle, compared between scans.
+///
+/// For a bundle directory, the total size of the files directly inside it
+/// and the latest of their modification times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileSnapshot {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl FileSnapshot {
+    /// Snapshot the bundle at `path`.
+    pub fn of(path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(path)?;
+        if !metadata.is_dir() {
+            return Ok(FileSnapshot {
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+            });
+        }
+
+        let mut snapshot = FileSnapshot {
+            len: 0,
+            modified: metadata.modified().ok(),
+        };
+        for entry in fs::read_dir(path)? {
+            let metadata = entry?.metadata()?;
+            snapshot.len += metadata.len();
+            snapshot.modified = snapshot.modified.max(metadata.modified().ok());
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Tracks when each path last changed, to tell finished writes from partial ones.
+#[derive(Debug)]
+pub struct StabilityTracker {
+    settle: Duration,
+    /// Snapshot of each path not yet handed out, and when it was first seen so.
+    pending: HashMap<PathBuf, (FileSnapshot, Instant)>,
+    /// Snapshot each path was handed out with.
+    handled: HashMap<PathBuf, FileSnapshot>,
+}
+
+impl StabilityTracker {
+    /// Creates a tracker that waits `settle` for a path to stop changing.
+    pub fn new(settle: Duration) -> Self {
+        StabilityTracker {
+            settle,
+            pending: HashMap::new(),
+            handled: HashMap::new(),
+        }
+    }
+
+    /// Record `path` as seen with `snapshot` at `now`.
+    ///
+    /// Returns true, once per version, when the path has kept the same
+    /// snapshot for at least the settle time.
+    pub fn observe(&mut self, path: &Path, snapshot: FileSnapshot, now: Instant) -> bool {
+        if self.handled.get(path) == Some(&snapshot) {
+            return false;
+        }
+        match self.pending.get(path) {
+            Some((seen, since)) if *seen == snapshot => {
+                if now.duration_since(*since) < self.settle {
+                    return false;
+                }
+                self.pending.remove(path);
+                self.handled.insert(path.to_path_buf(), snapshot);
+                true
+            }
+            // New, or changed since the last scan: start waiting again
+            _ => {
+                self.pending.insert(path.to_path_buf(), (snapshot, now));
+                false
+            }
+        }
+    }
+
+    /// Forget every path not in `present`, e.g. bundles moved away.
+    pub fn retain(&mut self, present: &HashSet<PathBuf>) {
+        self.pending.retain(|path, _| present.contains(path));
+        self.handled.retain(|path, _| present.contains(path));
+    }
+
+    /// Returns true while some path is waiting out the settle time.
+    pub fn is_settling(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+/// Watches a directory for bundles that have finished arriving.
+#[derive(Debug)]
+pub struct DirectoryWatcher {
+    dir: PathBuf,
+    tracker: StabilityTracker,
+    /// The native watcher and its events, if one could be started. Dropping
+    /// the watcher stops the events.
+    events: Option<(RecommendedWatcher, Receiver<notify::Result<Event>>)>,
+}
+
+impl DirectoryWatcher {
+    /// Watch the bundles directly inside `dir`, waiting `settle` for each.
+    pub fn new(dir: &Path, settle: Duration) -> Self {
+        DirectoryWatcher {
+            dir: dir.to_path_buf(),
+            tracker: StabilityTracker::new(settle),
+            events: watch_events(dir),
+        }
+    }
+
+    /// Returns true if file events wake [`wait`](Self::wait), rather than
+    /// only the poll interval.
+    pub fn has_file_events(&self) -> bool {
+        self.events.is_some()
+    }
+
+    /// Block until the next scan is due: a file event arrives, or the poll
+    /// or idle rescan interval passes.
+    pub fn wait(&self) {
+        let Some((_, events)) = &self.events else {
+            thread::sleep(POLL_INTERVAL);
+            return;
+        };
+        let timeout = if self.tracker.is_settling() { POLL_INTERVAL } else { IDLE_RESCAN_INTERVAL };
+        if events.recv_timeout(timeout).is_ok() {
+            // One scan covers a burst of events, e.g. a file being copied in
+            while events.try_recv().is_ok() {}
+        }
+    }
+
+    /// Scan the directory once, returning the bundles ready to verify.
+    pub fn poll(&mut self, now: Instant) -> Result<Vec<PathBuf>> {
+        let results = [self.dir.join(VERIFIED_DIR), self.dir.join(FAILED_DIR)];
+        let bundles: Vec<PathBuf> = discover_bundles(&self.dir)?.into_iter().filter(|p| !results.contains(p)).collect();
+
+        let mut ready = Vec::new();
+        for path in &bundles {
+            // A bundle removed mid-scan is simply gone by the next one
+            if let Ok(snapshot) = FileSnapshot::of(path) {
+                if self.tracker.observe(path, snapshot, now) {
+                    ready.push(path.clone());
+                }
+            }
+        }
+        self.tracker.retain(&bundles.into_iter().collect());
+        Ok(ready)
+    }
+}
+
+/// Start a native watcher on `dir` itself (its bundles are entries in it),
+/// or None if the platform or filesystem can't provide one.
+fn watch_events(dir: &Path) -> Option<(RecommendedWatcher, Receiver<notify::Result<Event>>)> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = recommended_watcher(sender).ok()?;
+    watcher.watch(dir, RecursiveMode::NonRecursive).ok()?;
+    Some((watcher, receiver))
+}
+
+/// Verify one bundle that has arrived, as a batch of one.
+///
+/// There is nobody to prompt, so without a password a sealed bundle fails
+/// with `PasswordRequired`.
+pub fn verify_arrival(path: &Path, password: Option<&str>, options: &VerifyOptions) -> Result<BatchEntry> {
+    let bundle = [path.to_path_buf()];
+    if password.is_none() && needs_password(&bundle) {
+        let record = BatchRecord::new(String::new(), Err(&VerifyError::PasswordRequired));
+        return Ok(BatchEntry { path: path.to_path_buf(), record, skipped: false });
+    }
+    let mut entries = verify_batch(&bundle, password, options, None, None, 1, |_| Ok(()))?;
+    Ok(entries.remove(0))
+}
+
+/// Move a bundle into the `verified/` or `failed/` subdirectory of its
+/// directory, returning where it went. Existing files are never replaced.
+pub fn file_result(bundle: &Path, verified: bool) -> Result<PathBuf> {
+    let parent = bundle.parent().unwrap_or(Path::new("."));
+    let dir = parent.join(if verified { VERIFIED_DIR } else { FAILED_DIR });
+    fs::create_dir_all(&dir)?;
+
+    let destination = dir.join(bundle.file_name().unwrap_or_default());
+    if destination.exists() {
+        return Err(VerifyError::OverwriteRefused {
+            path: destination.display().to_string(),
+        });
+    }
+    fs::rename(bundle, &destination)?;
+    Ok(destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(len: u64) -> FileSnapshot {
+        FileSnapshot { len, modified: None }
+    }
+
+    #[test]
+    fn test_path_is_ready_once_unchanged_for_settle_time() {
+        let mut tracker = StabilityTracker::new(Duration::from_secs(2));
+        let path = Path::new("intake/evidence.proofcapture");
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(!tracker.observe(path, snapshot(100), at(0)));
+        // Still being written: the wait starts over
+        assert!(!tracker.observe(path, snapshot(200), at(1)));
+        assert!(!tracker.observe(path, snapshot(200), at(2)));
+        assert!(tracker.observe(path, snapshot(200), at(3)));
+        // Handed out once
+        assert!(!tracker.observe(path, snapshot(200), at(10)));
+
+        // Replaced with new content, so it's watched again
+        assert!(!tracker.observe(path, snapshot(300), at(11)));
+        assert!(tracker.observe(path, snapshot(300), at(13)));
+    }
+
+    #[test]
+    fn test_watcher_skips_partial_writes_and_files_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut watcher = DirectoryWatcher::new(dir.path(), Duration::ZERO);
+        let bundle = dir.path().join("evidence.proofcapture");
+        let now = Instant::now();
+
+        fs::write(&bundle, b"{\"partial").unwrap();
+        assert!(watcher.poll(now).unwrap().is_empty());
+        fs::write(&bundle, b"{\"partial\": \"and the rest\"}").unwrap();
+        assert!(watcher.poll(now).unwrap().is_empty());
+        assert_eq!(watcher.poll(now).unwrap(), vec![bundle.clone()]);
+        assert!(watcher.poll(now).unwrap().is_empty());
+
+        let filed = file_result(&bundle, false).unwrap();
+        assert_eq!(filed, dir.path().join(FAILED_DIR).join("evidence.proofcapture"));
+        // Result directories aren't bundles to watch
+        assert!(watcher.poll(now).unwrap().is_empty());
+        assert!(watcher.poll(now).unwrap().is_empty());
+
+        fs::write(&bundle, b"again").unwrap();
+        assert!(matches!(file_result(&bundle, false), Err(VerifyError::OverwriteRefused { .. })));
+    }
+
+    #[test]
+    fn test_file_event_wakes_the_watcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let watcher = DirectoryWatcher::new(dir.path(), Duration::ZERO);
+        if !watcher.has_file_events() {
+            // Polling only here, e.g. inotify instances exhausted
+            return;
+        }
+
+        let started = Instant::now();
+        let writer = thread::spawn({
+            let bundle = dir.path().join("evidence.proofcapture");
+            move || {
+                thread::sleep(Duration::from_millis(50));
+                fs::write(bundle, b"{}").unwrap();
+            }
+        });
+        watcher.wait();
+        writer.join().unwrap();
+        assert!(started.elapsed() < IDLE_RESCAN_INTERVAL);
+    }
+
+    #[test]
+    fn test_sealed_arrival_without_password_fails() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let options = VerifyOptions::default();
+
+        let sealed = verify_arrival(&fixtures.join("sealed_test.proofcapture"), None, &options).unwrap();
+        assert_eq!(sealed.record.exit_code, VerifyError::PasswordRequired.exit_code());
+        let verified = verify_arrival(&fixtures.join("full_bundle"), None, &options).unwrap();
+        assert!(verified.record.is_verified());
+    }
+}